@@ -10,37 +10,23 @@ pub fn to_output_derive(input: TokenStream) -> TokenStream {
     let generics = input.generics;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let fields_output = match input.data {
+    let fields: Vec<_> = match input.data {
         Data::Struct(data_struct) => match data_struct.fields {
             syn::Fields::Named(fields_named) => {
-                let mut field_calls = Vec::new();
-                for field in fields_named.named {
+                let fields = fields_named.named.into_iter().map(|field| {
                     let field_name = field.ident.unwrap();
-                    field_calls.push(quote! {
-                        res.push_str(&self.#field_name.to_output());
-                        if res.chars().last() != Some('\n') {
-                            res.push('\n');
-                        }
-                    });
-                }
-                quote! { #(#field_calls)* }
+                    quote! { self.#field_name }
+                });
+                fields.collect()
             }
             syn::Fields::Unnamed(fields_unnamed) => {
-                let mut field_calls = Vec::new();
-                for (idx, _) in fields_unnamed.unnamed.into_iter().enumerate() {
+                let fields = fields_unnamed.unnamed.into_iter().enumerate().map(|(idx, _)| {
                     let index = syn::Index::from(idx);
-                    field_calls.push(quote! {
-                        res.push_str(&self.#index.to_output());
-                        if res.chars().last() != Some('\n') {
-                            res.push('\n');
-                        }
-                    });
-                }
-                quote! { #(#field_calls)* }
-            }
-            syn::Fields::Unit => {
-                quote! {}
+                    quote! { self.#index }
+                });
+                fields.collect()
             }
+            syn::Fields::Unit => Vec::new(),
         },
         _ => panic!("ToOutput can only be derived for structs"),
     };
@@ -50,7 +36,23 @@ pub fn to_output_derive(input: TokenStream) -> TokenStream {
         impl #impl_generics ToOutput for #name #ty_generics #where_clause {
             fn to_output(self) -> String {
                 let mut res = String::new();
-                #fields_output
+                #(
+                    res.push_str(&#fields.to_output());
+                    if res.chars().last() != Some('\n') {
+                        res.push('\n');
+                    }
+                )*
+                res
+            }
+
+            fn to_output_with_rng(self, rng: &mut ::ezcp::GeneratorRng) -> String {
+                let mut res = String::new();
+                #(
+                    res.push_str(&#fields.to_output_with_rng(rng));
+                    if res.chars().last() != Some('\n') {
+                        res.push('\n');
+                    }
+                )*
                 res
             }
         }
@@ -5,7 +5,13 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 use zip::write::SimpleFileOptions;
 
-pub fn archive_files(files: &[PathBuf], archive_path: &Path, logger: &MultiProgress) -> Result<()> {
+/// Pairs a file with its own file name, to be archived under it.
+pub fn named_by_file_name(file: &Path) -> (PathBuf, String) {
+    (file.to_owned(), file.file_name().map_or("", |a| a.to_str().unwrap_or("")).to_owned())
+}
+
+/// Archives files under the given names: `(path, name_in_archive)`.
+pub fn archive_named_files(files: &[(PathBuf, String)], archive_path: &Path, logger: &MultiProgress) -> Result<()> {
     let mut zipper = zip::ZipWriter::new(std::fs::File::create(archive_path).map_err(|err| Error::IOError {
         err,
         file: archive_path.to_str().unwrap().to_owned(),
@@ -14,13 +20,11 @@ pub fn archive_files(files: &[PathBuf], archive_path: &Path, logger: &MultiProgr
 
     let progress_bar = logger.add(ProgressBar::new(files.len() as u64));
 
-    for file in files {
+    for (file, name) in files {
         progress_bar.inc(1);
         let file_name = file.to_str().unwrap().to_owned();
 
-        zipper
-            .start_file(file.file_name().map_or("", |a| a.to_str().unwrap_or("")), options)
-            .map_err(|err| Error::ZipError { err })?;
+        zipper.start_file(name.as_str(), options).map_err(|err| Error::ZipError { err })?;
         let input_file = std::fs::read(file).map_err(|err| Error::IOError { err, file: file_name.clone() })?;
         zipper.write_all(&input_file).map_err(|err| Error::IOError { err, file: file_name.clone() })?;
     }
@@ -0,0 +1,45 @@
+use crate::report::GeneratorStats;
+
+/// Share of the robust test search that picks generators uniformly at random instead of the best one.
+pub const EXPLORATION_RATE: f64 = 0.1;
+
+/// Chooses the generators of the robust test search as a multi-armed bandit: a generator is
+/// chosen by its rate of tests that break a bad solution, so generators that never break anything
+/// are tried less often. Generators that were tried less have a higher estimate until they are tried more.
+pub struct GeneratorBandit {
+    stats: Vec<GeneratorStats>,
+}
+
+impl GeneratorBandit {
+    pub fn new(num_generators: usize) -> Self {
+        Self {
+            stats: vec![GeneratorStats::default(); num_generators],
+        }
+    }
+
+    /// Returns the generator with the highest estimated hit rate, the first one on ties,
+    /// or `None` if there are no generators.
+    pub fn best_generator(&self) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for (gen_idx, stats) in self.stats.iter().enumerate() {
+            // hit rate with one hit and one miss added, so untried generators start at 1/2
+            let estimate = (stats.hits + 1) as f64 / (stats.tries + 2) as f64;
+            if best.is_none_or(|(_, best_estimate)| estimate > best_estimate) {
+                best = Some((gen_idx, estimate));
+            }
+        }
+        best.map(|(gen_idx, _)| gen_idx)
+    }
+
+    /// Records whether a test of the generator broke a bad solution that still needed failures.
+    pub fn record(&mut self, gen_idx: usize, hit: bool) {
+        self.stats[gen_idx].tries += 1;
+        if hit {
+            self.stats[gen_idx].hits += 1;
+        }
+    }
+
+    pub fn into_stats(self) -> Vec<GeneratorStats> {
+        self.stats
+    }
+}
@@ -0,0 +1,163 @@
+//! Command-line interface for a task.
+//!
+//! A task binary can call `ezcp::cli::main(task)` instead of `task.run()`, so it can run only a part of the work:
+//!
+//! ```text
+//! cargo run -- generate
+//! cargo run -- verify
+//! cargo run -- run-solution my_solution.cpp
+//! cargo run -- stress 2 --count 500
+//! cargo run -- export --format cms
+//! ```
+
+use crate::export::ExportFormat;
+use crate::solution::{detect_language, read_source_file};
+use crate::task::path_str;
+use crate::test::GeneratorRng;
+use crate::test_cache::SubtaskPrograms;
+use crate::{Error, Result, Task, ToOutput};
+use rand::SeedableRng;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "Usage: <task> [command]
+
+Commands:
+    run                             Generate the tests, verify all solutions and package the tests (default)
+    generate                        Generate the tests without verifying the partial solutions
+    verify                          Run all solutions on the generated tests
+    run-solution <file>             Judge a solution from a file on the generated tests
+    stress <subtask> [--count <n>]  Stress test the solutions on tests of a subtask (numbered from 1), without saving them
+    export [--format <format>]      Package the generated tests as zip, polygon, cms or kattis
+    help                            Print this message";
+
+/// Number of tests per generator in the `stress` command, if neither `--count` nor `Subtask::do_stress_test` sets it.
+const DEFAULT_STRESS_TESTS: usize = 100;
+
+/// A parsed command.
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Run,
+    Generate,
+    Verify,
+    RunSolution(PathBuf),
+    Stress { subtask_number: usize, num_tests: Option<usize> },
+    Export(Option<ExportFormat>),
+    Help,
+}
+
+/// Runs the command given in the command-line arguments of the program on the task.
+pub fn main<T: ToOutput>(task: Task<T>) -> Result<()> {
+    run(task, std::env::args().skip(1))
+}
+
+/// Runs the command given in `args` (without the program name) on the task, see `main`.
+pub fn run<T: ToOutput, I: IntoIterator<Item = String>>(task: Task<T>, args: I) -> Result<()> {
+    match parse_args(&args.into_iter().collect::<Vec<_>>())? {
+        Command::Run => task.run().map(|_| ()),
+        Command::Generate => task.run_command(Task::generate_command),
+        Command::Verify => task.run_command(Task::verify_command),
+        Command::RunSolution(path) => task.run_command(|task| task.run_solution_command(&path)),
+        Command::Stress { subtask_number, num_tests } => task.run_command(|task| task.stress_command(subtask_number, num_tests)),
+        Command::Export(format) => task.run_command(|task| task.export_command(format.unwrap_or(task.export_format))),
+        Command::Help => {
+            println!("{USAGE}");
+            Ok(())
+        }
+    }
+}
+
+fn invalid_arguments(message: &str) -> Error {
+    Error::InvalidArguments { message: message.to_owned() }
+}
+
+fn parse_args(args: &[String]) -> Result<Command> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let command = match args.as_slice() {
+        [] | ["run"] => Command::Run,
+        ["generate"] => Command::Generate,
+        ["verify"] => Command::Verify,
+        ["run-solution", file] => Command::RunSolution(PathBuf::from(file)),
+        ["stress", subtask, options @ ..] => {
+            let subtask_number = subtask.parse().map_err(|err| invalid_arguments(&format!("{subtask} is not a subtask number ({err})")))?;
+            let num_tests = match options {
+                [] => None,
+                ["--count", count] => Some(count.parse().map_err(|err| invalid_arguments(&format!("{count} is not a number of tests ({err})")))?),
+                _ => return Err(invalid_arguments("stress only takes the --count option")),
+            };
+            Command::Stress { subtask_number, num_tests }
+        }
+        ["export"] => Command::Export(None),
+        ["export", "--format", format] => Command::Export(Some(parse_format(format)?)),
+        ["help" | "--help" | "-h"] => Command::Help,
+        [command, ..] => return Err(invalid_arguments(&format!("unknown command or wrong arguments of {command}"))),
+    };
+    Ok(command)
+}
+
+fn parse_format(format: &str) -> Result<ExportFormat> {
+    match format {
+        "zip" => Ok(ExportFormat::Zip),
+        "polygon" => Ok(ExportFormat::Polygon),
+        "cms" => Ok(ExportFormat::Cms),
+        "kattis" => Ok(ExportFormat::Kattis),
+        _ => Err(invalid_arguments(&format!("unknown export format {format}"))),
+    }
+}
+
+impl<T: ToOutput> Task<T> {
+    /// Generates the tests without verifying the partial solutions or packaging the tests.
+    fn generate_command(&self) -> Result<()> {
+        let mut programs = self.prepare_programs("Generating tests")?;
+        let (test_files, _) = self.generate_tests(&mut programs)?;
+        self.log_test_counts(&test_files)
+    }
+
+    /// Runs the main solution and all partial solutions on the generated tests.
+    fn verify_command(&self) -> Result<()> {
+        let mut programs = self.prepare_programs("Verifying solutions")?;
+        let test_files = self.read_layout()?;
+        self.verify_solutions(&mut programs, &test_files, &mut Vec::new())
+    }
+
+    /// Judges a solution from a file on the generated tests and logs its points.
+    fn run_solution_command(&self, path: &Path) -> Result<()> {
+        self.print_command_title("Judging a solution");
+        let language = detect_language(path)?;
+        let test_files = self.read_layout()?;
+        self.log_result(&format!("Running solution {}:", path_str(path)))?;
+        let (report, time_limit) = self.judge_solution_file(path, language, &test_files.subtasks)?;
+        self.log_solution_report(&report, None, read_source_file(path)?.split('\n').count(), time_limit)
+    }
+
+    /// Stress tests the main solution and the partial solutions that have to pass the subtask.
+    fn stress_command(&self, subtask_number: usize, num_tests: Option<usize>) -> Result<()> {
+        if subtask_number == 0 || subtask_number > self.subtasks.len() {
+            return Err(invalid_arguments(&format!("there is no subtask {subtask_number}")));
+        }
+        let subtask_idx = subtask_number - 1;
+        let subtask = &self.subtasks[subtask_idx];
+        let num_tests = num_tests.unwrap_or(if subtask.stress_tests > 0 { subtask.stress_tests as usize } else { DEFAULT_STRESS_TESTS });
+
+        let mut programs = self.prepare_programs(&format!("Stress testing subtask {subtask_number}"))?;
+        let (good_solution_handles, _) = self.split_solutions(subtask_idx, &programs.solution_handles);
+        let subtask_programs = SubtaskPrograms {
+            solution: programs.solution_handle,
+            good_solutions: &good_solution_handles,
+            bad_solutions: &[],
+            validators: &programs.validator_handles[subtask_idx],
+        };
+        let seed = self.get_seed();
+        self.log_result(&format!("Seed: {seed}"))?;
+        let mut rng = GeneratorRng::seed_from_u64(seed);
+        self.stress_test_subtask(subtask_idx, num_tests, &mut rng, &subtask_programs, &mut programs.cpp_runner)?;
+        self.log_result(&format!("Subtask {subtask_number}: {num_tests} tests of every generator passed"))
+    }
+
+    /// Packages the generated tests in the given format.
+    fn export_command(&self, export_format: ExportFormat) -> Result<()> {
+        let mut programs = self.prepare_programs("Exporting tests")?;
+        let test_files = self.read_layout()?;
+        self.calibrate_time_limit(&mut programs, &test_files)?;
+        self.export_package(export_format, &programs, &test_files)
+    }
+}
@@ -1,18 +1,26 @@
 use crate::Error::SolutionFailed;
 use crate::Result;
+use crate::bandit::{EXPLORATION_RATE, GeneratorBandit};
+use crate::params::{MUTATION_RATE, Params};
+use crate::report::{GeneratedTest, RobustTestStats, SubtaskTestsReport, Verdict};
+use crate::runner::checker::CheckerVerdict;
 use crate::runner::cpp_runner::{CppRunner, ProgramHandle};
 use crate::runner::exec_runner::RunResult;
+use crate::shrink;
 use crate::task::path_str;
+use crate::test::GeneratorRng;
+use crate::test_cache::{SubtaskPrograms, SubtaskTests, read_cached_tests, write_cached_tests};
 use crate::{Error, Subtask, Task, ToOutput};
 use indicatif::ProgressBar;
-use log::{error, info, warn};
+use log::{debug, error, info, warn};
 use rand::prelude::SliceRandom;
+use rand::{RngExt, SeedableRng};
 use std::collections::HashSet;
 use std::fs;
 use std::hash::{DefaultHasher, Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-fn trim_whitespace(input: &str) -> String {
+pub fn trim_whitespace(input: &str) -> String {
     let mut result = String::with_capacity(input.len());
     let mut in_block = false;
     let mut has_newline = false;
@@ -41,12 +49,74 @@ fn trim_whitespace(input: &str) -> String {
     result
 }
 
+/// Short verdict with the message of the checker, if there is one.
+fn verdict_text(verdict: &CheckerVerdict) -> String {
+    if verdict.message().is_empty() {
+        verdict.to_display_string()
+    } else {
+        format!("{}: {}", verdict.to_display_string(), verdict.message())
+    }
+}
+
+/// Seed of the random number generator of a subtask, derived from the task seed.
+const fn subtask_seed(seed: u64, subtask_idx: usize) -> u64 {
+    seed ^ (subtask_idx as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
 fn hash_string(s: &str) -> u64 {
     let mut hasher = DefaultHasher::new();
     s.hash(&mut hasher);
     hasher.finish()
 }
 
+/// Where a test comes from.
+pub enum TestSource {
+    /// Generated by a generator of the subtask, it can be reproduced with `Subtask::generate_test_with_params`.
+    /// The parameters are empty if the generator is not parameterized.
+    Generator { gen_idx: usize, seed: u64, params: Params },
+    /// Hand-written test read from a file, see `Subtask::with_tests_from_dir`.
+    File(PathBuf),
+}
+
+/// Records where a saved test comes from.
+pub struct TestRecord {
+    pub input_path: PathBuf,
+    pub subtask_idx: usize,
+    pub source: TestSource,
+}
+
+/// Hand-written test: the input file, its contents and the expected output, if there is an output file.
+#[derive(Hash)]
+pub struct ManualTest {
+    path: PathBuf,
+    input: String,
+    output: Option<String>,
+}
+
+/// Reads every `.in` file in the directory (sorted by name) with its `.out` or `.ans` file, if there is one.
+fn read_manual_tests(dir: &Path) -> Result<Vec<ManualTest>> {
+    let read_error = |err| Error::IOError { err, file: path_str(dir) };
+    let mut input_paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        if path.is_file() && path.extension().is_some_and(|extension| extension == "in") {
+            input_paths.push(path);
+        }
+    }
+    input_paths.sort();
+
+    let mut tests = Vec::new();
+    for path in input_paths {
+        let input = fs::read_to_string(&path).map_err(|err| Error::IOError { err, file: path_str(&path) })?;
+        let output_path = ["out", "ans"].iter().map(|extension| path.with_extension(extension)).find(|output_path| output_path.is_file());
+        let output = output_path
+            .map(|output_path| fs::read_to_string(&output_path).map_err(|err| Error::IOError { err, file: path_str(&output_path) }))
+            .transpose()?;
+        tests.push(ManualTest { path, input, output });
+    }
+    Ok(tests)
+}
+
 impl<T: ToOutput> Task<T> {
     pub(super) fn create_tests_for_subtask(
         &self,
@@ -54,53 +124,208 @@ impl<T: ToOutput> Task<T> {
         subtask: &Subtask<T>,
         global_test_id: &mut i32,
         all_test_files: &mut Vec<Vec<(PathBuf, PathBuf)>>,
+        test_records: &mut Vec<TestRecord>,
+        seed: u64,
         solution_handles: &[ProgramHandle],
         solution_handle: ProgramHandle,
+        validator_handles: &[ProgramHandle],
         cpp_runner: &mut CppRunner,
-    ) -> Result<()> {
+        slowest_main_run: &mut i32,
+    ) -> Result<SubtaskTestsReport> {
+        let (good_solution_handles, bad_solution_handles) = self.split_solutions(subtask_idx, solution_handles);
+        let programs = SubtaskPrograms {
+            solution: solution_handle,
+            good_solutions: &good_solution_handles,
+            bad_solutions: &bad_solution_handles,
+            validators: validator_handles,
+        };
+
+        let mut manual_tests = Vec::new();
+        for dir in &subtask.test_dirs {
+            manual_tests.extend(read_manual_tests(dir)?);
+        }
+
+        let cache_path = self.test_cache_path(subtask_idx);
+        let cache_key = if self.test_cache && self.can_cache_tests(subtask_idx) {
+            Some(self.test_cache_key(subtask_idx, seed, &programs, &manual_tests, cpp_runner)?)
+        } else {
+            None
+        };
+        let (subtask_tests, robust) = if let Some(cache_key) = cache_key
+            && let Some(tests) = read_cached_tests(&cache_path, cache_key)?
+            && self.cached_tests_are_valid(subtask_idx, &tests, validator_handles, cpp_runner)?
+        {
+            info!("Using {} cached tests", tests.len());
+            (tests, None)
+        } else {
+            let (tests, robust) = self.generate_subtask_tests(subtask_idx, subtask, manual_tests, seed, &programs, cpp_runner, slowest_main_run)?;
+            // without enough robust tests, they are searched for again in the next run
+            if let Some(cache_key) = cache_key
+                && robust.is_complete()
+            {
+                write_cached_tests(&cache_path, cache_key, &tests)?;
+            }
+            (tests, Some(robust))
+        };
+
+        // Write the tests to disk
+        let mut subtask_files = Vec::new();
+        let mut report = SubtaskTestsReport {
+            name: subtask.name.clone(),
+            tests: Vec::new(),
+            size: 0,
+            robust,
+        };
+        for (test_id_in_subtask, (input, output, source)) in subtask_tests.into_iter().enumerate() {
+            let input_path = self.get_input_file_path(*global_test_id, subtask_idx as i32, test_id_in_subtask as i32);
+            let output_path = self.get_output_file_path(*global_test_id, subtask_idx as i32, test_id_in_subtask as i32);
+
+            fs::write(&input_path, &input).map_err(|err| Error::IOError { err, file: path_str(&input_path) })?;
+            fs::write(&output_path, &output).map_err(|err| Error::IOError { err, file: path_str(&output_path) })?;
+
+            let (generator, seed, params, source_file) = match &source {
+                TestSource::Generator { gen_idx, seed, params } => (Some(*gen_idx), Some(*seed), params.clone(), None),
+                TestSource::File(path) => (None, None, Params::default(), Some(path.clone())),
+            };
+            let size = (input.len() + output.len()) as u64;
+            report.size += size;
+            report.tests.push(GeneratedTest {
+                input_file: input_path.clone(),
+                output_file: output_path.clone(),
+                generator,
+                seed,
+                params,
+                source_file,
+                size,
+            });
+            test_records.push(TestRecord {
+                input_path: input_path.clone(),
+                subtask_idx,
+                source,
+            });
+            subtask_files.push((input_path, output_path));
+            *global_test_id += 1;
+        }
+        all_test_files.push(subtask_files);
+        Ok(report)
+    }
+
+    /// Splits the partial solutions into the ones that have to pass the subtask (with their indices)
+    /// and the ones that the robust tests of the subtask have to break (also with their indices).
+    pub(crate) fn split_solutions(&self, subtask_idx: usize, solution_handles: &[ProgramHandle]) -> (Vec<(usize, ProgramHandle)>, Vec<(usize, ProgramHandle)>) {
         let mut good_solution_handles = Vec::new();
         let mut bad_solution_handles = Vec::new();
+        let dependencies: Vec<usize> = self.included_subtasks(subtask_idx).into_iter().filter(|&idx| idx != subtask_idx).collect();
         for (i, solution) in self.solutions.iter().enumerate() {
             if solution.passes_subtasks.contains(&subtask_idx) {
                 good_solution_handles.push((i, solution_handles[i]));
-            } else {
-                bad_solution_handles.push(solution_handles[i]);
+            } else if solution.should_fail(subtask_idx) && !dependencies.iter().any(|&dependency| solution.should_fail(dependency)) {
+                // solutions that fail an included subtask already fail on its tests
+                bad_solution_handles.push((i, solution_handles[i]));
             }
         }
+        (good_solution_handles, bad_solution_handles)
+    }
 
+    /// Runs the main solution and the good solutions on `num_tests` tests of every generator of the subtask,
+    /// without saving the tests. Fails on the first invalid test or on the first test that a good solution fails.
+    pub(crate) fn stress_test_subtask(&self, subtask_idx: usize, num_tests: usize, rng: &mut GeneratorRng, programs: &SubtaskPrograms, cpp_runner: &mut CppRunner) -> Result<()> {
+        if num_tests == 0 {
+            return Ok(());
+        }
+        let subtask = &self.subtasks[subtask_idx];
+        for gen_idx in 0..subtask.get_num_generators() {
+            info!("Stress testing generator {gen_idx}");
+            let stress_testing_progress_bar = self.logger.add(ProgressBar::new(num_tests as u64));
+            for _ in 0..num_tests {
+                let mut test_str = subtask.generate_test(gen_idx, rng.random());
+                if self.trim_whitespace {
+                    test_str = trim_whitespace(&test_str);
+                }
+
+                stress_testing_progress_bar.inc(1);
+
+                self.validate_test(&test_str, subtask_idx, programs.validators, cpp_runner, gen_idx)?;
+                self.run_test(
+                    &test_str,
+                    programs.solution,
+                    programs.good_solutions,
+                    &[],
+                    programs.validators,
+                    cpp_runner,
+                    subtask_idx,
+                    gen_idx,
+                    &mut 0,
+                )?;
+            }
+            self.logger.remove(&stress_testing_progress_bar);
+        }
+        Ok(())
+    }
+
+    /// Creates the tests of a subtask: hand-written tests, initial tests of every generator and robust tests
+    /// that the bad solutions fail, in random order. Returns the tests with the statistics of the search for robust tests.
+    #[allow(clippy::too_many_lines)]
+    fn generate_subtask_tests(
+        &self,
+        subtask_idx: usize,
+        subtask: &Subtask<T>,
+        manual_tests: Vec<ManualTest>,
+        seed: u64,
+        programs: &SubtaskPrograms,
+        cpp_runner: &mut CppRunner,
+        slowest_main_run: &mut i32,
+    ) -> Result<(SubtaskTests, RobustTestStats)> {
+        let mut rng = GeneratorRng::seed_from_u64(subtask_seed(seed, subtask_idx));
         let mut tried_inputs = HashSet::new();
         let mut subtask_tests = Vec::new();
         let mut robust_found_count = 0;
+        // number of robust tests that every bad solution fails
+        let mut failure_counts = vec![0; programs.bad_solutions.len()];
 
         let total_initial: usize = subtask.initial_counts.iter().sum();
-        let target_robust = if bad_solution_handles.is_empty() {
+        let target_robust = if programs.bad_solutions.is_empty() {
             0
         } else {
             subtask.min_failures_per_solution.unwrap_or(self.min_failures_per_solution)
         };
 
-        let found_count_progress_bar = self.logger.add(ProgressBar::new((total_initial + target_robust) as u64));
-        let tries_progress_bar = self.logger.add(ProgressBar::new(self.max_tries as u64));
-        
-        // Phase 1 (optional): Stress tests
-        if subtask.stress_tests != 0 {
-            for gen_idx in 0..subtask.get_num_generators() {
-                info!("Stress testing generator {gen_idx}");
-                let stress_testing_progress_bar = self.logger.add(ProgressBar::new(subtask.stress_tests as u64));
-                for _ in 0..subtask.stress_tests {
-                    let mut test_str = subtask.generate_test(gen_idx).to_output();
-                    if self.trim_whitespace {
-                        test_str = trim_whitespace(&test_str);
-                    }
-
-                    stress_testing_progress_bar.inc(1);
-
-                    self.is_robust_test(&test_str, solution_handle, &good_solution_handles, &[], cpp_runner, subtask_idx, gen_idx)?;
+        // Phase 0: Hand-written tests (only good solutions must pass)
+        for manual_test in manual_tests {
+            let mut input = manual_test.input;
+            if self.trim_whitespace {
+                input = trim_whitespace(&input);
+            }
+            if !tried_inputs.insert(hash_string(&input)) {
+                warn!("Skipping {}, because it repeats another test of the subtask.", path_str(&manual_test.path));
+                continue;
+            }
+            if let Err(message) = self.run_validators(&input, subtask_idx, programs.validators, cpp_runner)? {
+                return Err(Error::InvalidManualTest {
+                    subtask_number: subtask_idx + 1,
+                    message,
+                    file: path_str(&manual_test.path),
+                });
+            }
+            match self.run_fixed_test(&input, manual_test.output.as_deref(), programs.solution, programs.good_solutions, cpp_runner)? {
+                Ok(output) => subtask_tests.push((input, output, TestSource::File(manual_test.path))),
+                Err((solution_name, verdict)) => {
+                    return Err(Error::ManualTestFailed {
+                        subtask_number: subtask_idx + 1,
+                        solution_name,
+                        verdict,
+                        file: path_str(&manual_test.path),
+                    });
                 }
-                self.logger.remove(&stress_testing_progress_bar);
             }
         }
 
+        let found_count_progress_bar = self.logger.add(ProgressBar::new((total_initial + target_robust * failure_counts.len()) as u64));
+        let tries_progress_bar = self.logger.add(ProgressBar::new(self.max_tries as u64));
+        
+        // Phase 1 (optional): Stress tests
+        self.stress_test_subtask(subtask_idx, subtask.stress_tests as usize, &mut rng, programs, cpp_runner)?;
+
 
         // Phase 2: Initial tests from each generator (only good solutions must pass)
         for gen_idx in 0..subtask.get_num_generators() {
@@ -108,7 +333,8 @@ impl<T: ToOutput> Task<T> {
             let mut got = 0;
             let mut fails = 0;
             while got < needed && fails < 100 {
-                let mut candidate = subtask.generate_test(gen_idx).to_output();
+                let test_seed = rng.random();
+                let mut candidate = subtask.generate_test(gen_idx, test_seed);
                 if self.trim_whitespace {
                     candidate = trim_whitespace(&candidate);
                 }
@@ -118,12 +344,22 @@ impl<T: ToOutput> Task<T> {
                     continue;
                 }
                 tried_inputs.insert(hash_string(&candidate));
+                self.validate_test(&candidate, subtask_idx, programs.validators, cpp_runner, gen_idx)?;
 
                 // We check only good solutions in Phase 1 (no bad_progs passed)
-                let Some(main_output) = self.is_robust_test(&candidate, solution_handle, &good_solution_handles, &[], cpp_runner, subtask_idx, gen_idx)? else {
-                    unreachable!("is_robust_test with no bad progs should always return Some or Err")
-                };
-                subtask_tests.push((candidate, main_output));
+                let (main_output, _) = self.run_test(
+                    &candidate,
+                    programs.solution,
+                    programs.good_solutions,
+                    &[],
+                    programs.validators,
+                    cpp_runner,
+                    subtask_idx,
+                    gen_idx,
+                    slowest_main_run,
+                )?;
+                let params = subtask.params_from_seed(gen_idx, test_seed);
+                subtask_tests.push((candidate, main_output, TestSource::Generator { gen_idx, seed: test_seed, params }));
                 found_count_progress_bar.inc(1);
                 got += 1;
             }
@@ -132,108 +368,283 @@ impl<T: ToOutput> Task<T> {
             }
         }
 
-        // Phase 3: Robust tests (failing bad solutions)
+        // Phase 3: Robust tests (failing bad solutions), until every bad solution fails on enough of them.
+        // A test is taken if it breaks any bad solution that still fails on too few tests.
+        // Generators are chosen by how often they break bad solutions, see `GeneratorBandit`.
+        // Parameterized generators often change the parameters of their last robust test instead of picking new ones
+        let mut bandit = GeneratorBandit::new(subtask.get_num_generators());
+        let mut robust_params: Vec<Option<Params>> = vec![None; subtask.get_num_generators()];
         let mut supplemental_tries = 0;
-        while robust_found_count < target_robust && supplemental_tries < self.max_tries {
+        let mut total_tries = 0;
+        while failure_counts.iter().any(|&count| count < target_robust) && supplemental_tries < self.max_tries {
             supplemental_tries += 1;
+            total_tries += 1;
             tries_progress_bar.inc(1);
-            let Some((candidate, gen_idx)) = subtask.generate_random_test() else { break };
-            let mut candidate = candidate.to_output();
+            let random_test = if rng.random_bool(EXPLORATION_RATE) {
+                subtask
+                    .generate_random_test(&mut rng)
+                    .map(|(test, gen_idx, test_seed)| (test, gen_idx, test_seed, subtask.params_from_seed(gen_idx, test_seed)))
+            } else {
+                bandit.best_generator().map(|gen_idx| {
+                    let test_seed = rng.random();
+                    let params = match &robust_params[gen_idx] {
+                        Some(params) if rng.random_bool(MUTATION_RATE) => subtask.mutate_params(gen_idx, params, &mut rng),
+                        _ => subtask.params_from_seed(gen_idx, test_seed),
+                    };
+                    (subtask.generate_test_with_params(gen_idx, test_seed, &params), gen_idx, test_seed, params)
+                })
+            };
+            let Some((mut candidate, gen_idx, test_seed, params)) = random_test else { break };
             if self.trim_whitespace {
                 candidate = trim_whitespace(&candidate);
             }
             if tried_inputs.contains(&hash_string(&candidate)) {
+                bandit.record(gen_idx, false);
                 continue;
             }
             tried_inputs.insert(hash_string(&candidate));
-
-            if let Some(main_output) = self.is_robust_test(&candidate, solution_handle, &good_solution_handles, &bad_solution_handles, cpp_runner, subtask_idx, gen_idx)? {
-                subtask_tests.push((candidate, main_output));
+            self.validate_test(&candidate, subtask_idx, programs.validators, cpp_runner, gen_idx)?;
+
+            let (main_output, failed) = self.run_test(
+                &candidate,
+                programs.solution,
+                programs.good_solutions,
+                programs.bad_solutions,
+                programs.validators,
+                cpp_runner,
+                subtask_idx,
+                gen_idx,
+                slowest_main_run,
+            )?;
+            let hit = failed.iter().any(|&bad_idx| failure_counts[bad_idx] < target_robust);
+            bandit.record(gen_idx, hit);
+            if hit {
+                for &bad_idx in &failed {
+                    if failure_counts[bad_idx] < target_robust {
+                        found_count_progress_bar.inc(1);
+                    }
+                    failure_counts[bad_idx] += 1;
+                }
+                if !params.is_empty() {
+                    debug!("Generator {} of subtask {} broke a bad solution with {params}", gen_idx + 1, subtask_idx + 1);
+                    robust_params[gen_idx] = Some(params.clone());
+                }
+                subtask_tests.push((candidate, main_output, TestSource::Generator { gen_idx, seed: test_seed, params }));
                 robust_found_count += 1;
                 supplemental_tries = 0;
-                found_count_progress_bar.inc(1);
                 tries_progress_bar.reset();
             }
         }
 
-        if robust_found_count < target_robust {
-            error!("Could not find enough robust tests for Subtask {} (found {}/{})", subtask_idx + 1, robust_found_count, target_robust);
+        for (&(sol_idx, _), &count) in programs.bad_solutions.iter().zip(&failure_counts) {
+            if count < target_robust {
+                error!(
+                    "Could not find enough robust tests for Subtask {} that {} fails (found {count}/{target_robust})",
+                    subtask_idx + 1,
+                    self.solutions[sol_idx].name
+                );
+            }
         }
         self.logger.remove(&found_count_progress_bar);
         self.logger.remove(&tries_progress_bar);
 
         // Shuffle all tests for this subtask
-        let mut rng = rand::rng();
         subtask_tests.shuffle(&mut rng);
+        let robust = RobustTestStats {
+            found: robust_found_count,
+            target: target_robust,
+            tries: total_tries,
+            failures: programs
+                .bad_solutions
+                .iter()
+                .zip(failure_counts)
+                .map(|(&(sol_idx, _), count)| (self.solutions[sol_idx].name.clone(), count))
+                .collect(),
+            generators: bandit.into_stats(),
+        };
+        for (gen_idx, generator) in robust.generators.iter().enumerate() {
+            debug!("Generator {} of subtask {} gave {}/{} robust tests", gen_idx + 1, subtask_idx + 1, generator.hits, generator.tries);
+        }
+        Ok((subtask_tests, robust))
+    }
 
-        // Write shuffled tests to disk
-        let mut subtask_files = Vec::new();
-        for (test_id_in_subtask, (input, output)) in subtask_tests.into_iter().enumerate() {
-            let input_path = self.get_input_file_path(*global_test_id, subtask_idx as i32, test_id_in_subtask as i32);
-            let output_path = self.get_output_file_path(*global_test_id, subtask_idx as i32, test_id_in_subtask as i32);
-
+    /// Writes the sample tests into `sample.01.in`, `sample.01.out`, ... Outputs that are not given are produced by the main solution,
+    /// given outputs are checked against it. Every solution has to pass every sample.
+    pub(super) fn create_samples(&self, solution_handles: &[ProgramHandle], solution_handle: ProgramHandle, cpp_runner: &mut CppRunner) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let solutions: Vec<(usize, ProgramHandle)> = solution_handles.iter().copied().enumerate().collect();
+        let mut sample_files = Vec::new();
+        for (sample_idx, (input, expected_output)) in self.samples.iter().enumerate() {
+            let mut input = input.clone();
+            if self.trim_whitespace {
+                input = trim_whitespace(&input);
+            }
+            let input_path = self.tests_path.join(format!("sample.{:02}.in", sample_idx + 1));
+            let output_path = self.tests_path.join(format!("sample.{:02}.out", sample_idx + 1));
             fs::write(&input_path, &input).map_err(|err| Error::IOError { err, file: path_str(&input_path) })?;
-            fs::write(&output_path, output).map_err(|err| Error::IOError { err, file: path_str(&output_path) })?;
 
-            subtask_files.push((input_path, output_path));
-            *global_test_id += 1;
+            let output = match self.run_fixed_test(&input, expected_output.as_deref(), solution_handle, &solutions, cpp_runner)? {
+                Ok(output) => output,
+                Err((solution_name, verdict)) => {
+                    return Err(Error::SampleFailed {
+                        sample_number: sample_idx + 1,
+                        solution_name,
+                        verdict,
+                        file: path_str(&input_path),
+                    });
+                }
+            };
+            fs::write(&output_path, &output).map_err(|err| Error::IOError { err, file: path_str(&output_path) })?;
+            sample_files.push((input_path, output_path));
         }
-        all_test_files.push(subtask_files);
-        Ok(())
+        Ok(sample_files)
     }
 
-    /// Checks if a candidate test input effectively distinguishes between the correct solution
-    /// and a set of "bad" solutions.
+    /// Runs the main solution and the given partial solutions on a test that is not generated.
+    /// The output is the expected output if it is given (the main solution has to pass it), otherwise the output of the main solution.
+    /// Returns the name of the first solution that fails with its verdict instead of the output.
+    fn run_fixed_test(
+        &self,
+        input: &str,
+        expected_output: Option<&str>,
+        solution_handle: ProgramHandle,
+        solutions: &[(usize, ProgramHandle)],
+        cpp_runner: &mut CppRunner,
+    ) -> Result<std::result::Result<String, (String, String)>> {
+        let mut programs = vec![solution_handle];
+        programs.extend(solutions.iter().map(|&(_, handle)| handle));
+        let results = cpp_runner.check_programs(input, &programs, self.time_limit, self.memory_limit)?;
+
+        let RunResult::Ok(_, _, main_output) = &results[0] else {
+            return Ok(Err(("Main solution".to_owned(), results[0].to_display_string())));
+        };
+        let mut output = if let Some(expected_output) = expected_output {
+            let verdict = self.check_output(cpp_runner, input, expected_output, main_output)?;
+            if !verdict.is_accepted() {
+                return Ok(Err(("Main solution".to_owned(), verdict_text(&verdict))));
+            }
+            expected_output.to_owned()
+        } else {
+            main_output.trim().to_owned() + "\n"
+        };
+        if self.trim_whitespace {
+            output = trim_whitespace(&output);
+        }
+
+        for (&(solution_idx, _), result) in solutions.iter().zip(&results[1..]) {
+            let failure = match result {
+                RunResult::Ok(_, _, program_output) => match self.check_output(cpp_runner, input, &output, program_output)? {
+                    CheckerVerdict::Accepted => None,
+                    verdict => Some(verdict_text(&verdict)),
+                },
+                result => Some(result.to_display_string()),
+            };
+            if let Some(verdict) = failure {
+                return Ok(Err((format!("Partial solution {} ({})", solution_idx + 1, self.solutions[solution_idx].name), verdict)));
+            }
+        }
+        Ok(Ok(output))
+    }
+
+    /// Runs the validators of the subtask on a test input exactly as it will be written to the test file.
+    pub(crate) fn run_validators(&self, input: &str, subtask_idx: usize, validator_handles: &[ProgramHandle], runner: &CppRunner) -> Result<std::result::Result<(), String>> {
+        let mut validation = self.subtasks[subtask_idx].validate(input);
+        for &validator in validator_handles {
+            if validation.is_err() {
+                break;
+            }
+            validation = runner.run_validator(validator, input)?;
+        }
+        Ok(validation)
+    }
+
+    /// Validates a generated test, an invalid input is saved to `invalid_test.in` in the problem directory.
+    fn validate_test(&self, input: &str, subtask_idx: usize, validator_handles: &[ProgramHandle], runner: &CppRunner, gen_idx: usize) -> Result<()> {
+        let Err(message) = self.run_validators(input, subtask_idx, validator_handles, runner)? else {
+            return Ok(());
+        };
+
+        let write_path = self.problem_path.join("invalid_test.in");
+        fs::write(&write_path, input).map_err(|err| Error::IOError { err, file: path_str(&write_path) })?;
+        Err(Error::InvalidTest {
+            subtask_number: subtask_idx + 1,
+            gen_id: gen_idx + 1,
+            message,
+            file: path_str(&write_path),
+        })
+    }
+
+    /// Runs the solutions on a candidate test input to see which "bad" solutions it distinguishes from the correct solution.
     ///
-    /// A test is considered robust if:
-    /// 1. All "good" solutions (including main) produce the same valid response.
-    /// 2. Every "bad" solution either TLEs, crashes, or produces a different output.
-    fn is_robust_test(
+    /// All "good" solutions (including main) have to produce a valid response, otherwise an error is returned.
+    /// Returns the output of the main solution and the positions in `bad_progs` of the "bad" solutions
+    /// that fail the test (they TLE, MLE, crash, or produce a wrong output). The "bad" solutions get TLE if they run longer
+    /// than `Task::search_time_limit` of `slowest_main_run` with the multiplier of their language, `slowest_main_run` is updated with the run of the main solution.
+    #[allow(clippy::too_many_lines)]
+    fn run_test(
         &self,
         input: &str,
         main_prog: ProgramHandle,
         good_progs: &[(usize, ProgramHandle)],
-        bad_progs: &[ProgramHandle],
+        bad_progs: &[(usize, ProgramHandle)],
+        validator_handles: &[ProgramHandle],
         runner: &mut CppRunner,
         subtask_idx: usize,
         gen_idx: usize,
-    ) -> Result<Option<String>> {
+        slowest_main_run: &mut i32,
+    ) -> Result<(String, Vec<usize>)> {
         let mut all_progs = vec![main_prog];
         for &(_, handle) in good_progs {
             all_progs.push(handle);
         }
-        all_progs.extend_from_slice(bad_progs);
+        all_progs.extend(bad_progs.iter().map(|&(_, handle)| handle));
 
         // Run all solutions in parallel
-        let results = runner.check_programs(input, &all_progs, self.time_limit)?;
+        let results = runner.check_programs(input, &all_progs, self.time_limit, self.memory_limit)?;
 
-        let write_bad_test = || -> Result<()> {
+        let write_bad_test = |runner: &mut CppRunner, failing_prog: Option<ProgramHandle>, verdict: Verdict| -> Result<()> {
             let write_path = self.problem_path.join("failing_test.in");
             fs::write(write_path.clone(), input).map_err(move |err| Error::IOError { file: path_str(&write_path), err })?;
-            Ok(())
+            self.write_minimized_test(input, subtask_idx, validator_handles, main_prog, failing_prog, verdict, runner)
         };
 
         // Correct (Main) Solution Result
         let mut correct_output = match &results[0] {
-            RunResult::Ok(_, output) => output.trim().to_owned() + "\n",
+            RunResult::Ok(time, _, output) => {
+                *slowest_main_run = (*slowest_main_run).max(*time);
+                output.trim().to_owned() + "\n"
+            }
             RunResult::TimedOut => {
-                write_bad_test()?;
+                write_bad_test(runner, None, Verdict::TimedOut)?;
                 return Err(Error::SolutionTimedOut {
                     test_path: "generation phase".to_owned(),
                     gen_id: gen_idx + 1,
                 });
             }
+            RunResult::MemoryLimitExceeded => {
+                write_bad_test(runner, None, Verdict::MemoryLimitExceeded)?;
+                return Err(Error::SolutionMemoryLimitExceeded {
+                    test_path: "generation phase".to_owned(),
+                    gen_id: gen_idx + 1,
+                });
+            }
             RunResult::Crashed => {
-                write_bad_test()?;
+                write_bad_test(runner, None, Verdict::Crashed)?;
                 return Err(Error::SolutionCrash {
                     test_path: "generation phase".to_owned(),
                     gen_id: gen_idx + 1,
                 });
             }
+            RunResult::WrongAnswer => {
+                write_bad_test(runner, None, Verdict::WrongAnswer)?;
+                return Err(SolutionFailed {
+                    test_path: "generation phase".to_owned(),
+                    gen_id: gen_idx + 1,
+                });
+            }
         };
 
-        if !(self.checker)(input, &correct_output, &correct_output) {
-            write_bad_test()?;
+        if !self.check_output(runner, input, &correct_output, &correct_output)?.is_accepted() {
+            write_bad_test(runner, None, Verdict::WrongAnswer)?;
             return Err(SolutionFailed {
                 test_path: "generation phase".to_owned(),
                 gen_id: gen_idx + 1,
@@ -245,58 +656,136 @@ impl<T: ToOutput> Task<T> {
         }
 
         // Ensure all other "good" solutions pass and match main output
-        for (i, &(sol_idx, _)) in good_progs.iter().enumerate() {
-            match &results[i + 1] {
-                RunResult::Ok(_, output) if (self.checker)(input, &correct_output, output) => {}
-                result => {
-                    let write_path = self.problem_path.join("failing_test.in");
-                    let official_output_write_path = self.problem_path.join("failing_test_correct_output.out");
-                    let wrong_output_write_path = self.problem_path.join("failing_test_wrong_output.out");
-                    fs::write(official_output_write_path.clone(), correct_output).map_err(move |err| Error::IOError {
-                        file: path_str(&official_output_write_path),
-                        err,
-                    })?;
-
-                    if let RunResult::Ok(_, output) = &results[i + 1] {
-                        fs::write(wrong_output_write_path.clone(), output).map_err(move |err| Error::IOError {
-                            file: path_str(&wrong_output_write_path),
-                            err,
-                        })?;
-                    } else if wrong_output_write_path.is_file() {
-                        fs::remove_file(wrong_output_write_path.clone()).map_err(move |err| Error::IOError {
-                            file: path_str(&wrong_output_write_path),
-                            err,
-                        })?;
-                    }
+        for (i, &(sol_idx, handle)) in good_progs.iter().enumerate() {
+            let result = &results[i + 1];
+            let failure = match result {
+                RunResult::Ok(_, _, output) => match self.check_output(runner, input, &correct_output, output)? {
+                    CheckerVerdict::Accepted => None,
+                    verdict => Some((Verdict::from_checker(&verdict), verdict_text(&verdict))),
+                },
+                result => Some((Verdict::from(result), result.to_display_string())),
+            };
+            let Some((verdict, verdict_text)) = failure else { continue };
 
-                    fs::write(write_path.clone(), input).map_err(move |err| Error::IOError { file: path_str(&write_path), err })?;
-                    return Err(Error::PartialSolutionFailsSubtask {
-                        partial_number: sol_idx + 1,
-                        subtask_number: subtask_idx + 1,
-                        subtask_name: self.subtasks[subtask_idx].name.clone(),
-                        partial_name: self.solutions[sol_idx].name.clone(),
-                        verdict: if matches!(result, RunResult::Ok(_, _)) { "WA".to_owned() } else { result.to_display_string() },
-                        gen_id: gen_idx + 1,
-                    });
-                }
+            let write_path = self.problem_path.join("failing_test.in");
+            let official_output_write_path = self.problem_path.join("failing_test_correct_output.out");
+            let wrong_output_write_path = self.problem_path.join("failing_test_wrong_output.out");
+            fs::write(official_output_write_path.clone(), correct_output).map_err(move |err| Error::IOError {
+                file: path_str(&official_output_write_path),
+                err,
+            })?;
+
+            if let RunResult::Ok(_, _, output) = result {
+                fs::write(wrong_output_write_path.clone(), output).map_err(move |err| Error::IOError {
+                    file: path_str(&wrong_output_write_path),
+                    err,
+                })?;
+            } else if wrong_output_write_path.is_file() {
+                fs::remove_file(wrong_output_write_path.clone()).map_err(move |err| Error::IOError {
+                    file: path_str(&wrong_output_write_path),
+                    err,
+                })?;
             }
-        }
 
-        if bad_progs.is_empty() {
-            return Ok(Some(correct_output));
+            fs::write(write_path.clone(), input).map_err(move |err| Error::IOError { file: path_str(&write_path), err })?;
+            self.write_minimized_test(input, subtask_idx, validator_handles, main_prog, Some(handle), verdict, runner)?;
+            return Err(Error::PartialSolutionFailsSubtask {
+                partial_number: sol_idx + 1,
+                subtask_number: subtask_idx + 1,
+                subtask_name: self.subtasks[subtask_idx].name.clone(),
+                partial_name: self.solutions[sol_idx].name.clone(),
+                verdict: verdict_text,
+                gen_id: gen_idx + 1,
+            });
         }
 
-        // Run Bad Solutions to ensure they fail
+        // Check which Bad Solutions fail
         let bad_results_start = 1 + good_progs.len();
-        for res in &results[bad_results_start..] {
-            match res {
-                RunResult::Ok(_, output) if (self.checker)(input, &correct_output, output) => {
-                    // A bad solution passed this test! This test is not robust enough.
-                    return Ok(None);
-                }
-                _ => {} // Bad solution failed as expected (TLE, Crash, or WA)
+        let time_limit = self.search_time_limit(*slowest_main_run);
+        let mut failed = Vec::new();
+        for (bad_idx, (&(sol_idx, handle), res)) in bad_progs.iter().zip(&results[bad_results_start..]).enumerate() {
+            let verdict = match res {
+                RunResult::Ok(time, _, _) if *time > runner.get_time_limit(handle, time_limit) => Verdict::TimedOut,
+                RunResult::Ok(_, _, output) => Verdict::from_checker(&self.check_output(runner, input, &correct_output, output)?),
+                res => Verdict::from(res),
+            };
+            if verdict != Verdict::Ok {
+                failed.push(bad_idx);
+            }
+            // A failing bad solution has to fail with one of its expected verdicts
+            if !self.solutions[sol_idx].is_expected_verdict(subtask_idx, verdict) {
+                write_bad_test(runner, Some(handle), verdict)?;
+                return Err(Error::PartialSolutionUnexpectedVerdict {
+                    subtask_number: subtask_idx + 1,
+                    partial_number: sol_idx + 1,
+                    partial_name: self.solutions[sol_idx].name.clone(),
+                    subtask_name: self.subtasks[subtask_idx].name.clone(),
+                    verdict,
+                    expected: self.solutions[sol_idx].expected_verdicts_text(subtask_idx),
+                    test: format!("a test of generator {}, saved to failing_test.in", gen_idx + 1),
+                });
             }
         }
-        Ok(Some(correct_output))
+        Ok((correct_output, failed))
+    }
+
+    /// Minimizes a test that a solution fails (the main solution if `failing_prog` is `None`) with `shrink::minimize`
+    /// and saves it to `failing_test_min.in`. Smaller inputs are only taken if they pass the validators
+    /// and the solution gets the same verdict on them. Tests that exceed the time limit are not minimized,
+    /// because every try would take the whole time limit.
+    fn write_minimized_test(
+        &self,
+        input: &str,
+        subtask_idx: usize,
+        validator_handles: &[ProgramHandle],
+        main_prog: ProgramHandle,
+        failing_prog: Option<ProgramHandle>,
+        verdict: Verdict,
+        runner: &mut CppRunner,
+    ) -> Result<()> {
+        let write_path = self.problem_path.join("failing_test_min.in");
+        if write_path.is_file() {
+            fs::remove_file(&write_path).map_err(|err| Error::IOError { err, file: path_str(&write_path) })?;
+        }
+        if self.max_minimize_tries == 0 || verdict == Verdict::TimedOut {
+            return Ok(());
+        }
+
+        info!("Minimizing the failing test");
+        let minimized = shrink::minimize(input, self.max_minimize_tries, |candidate| {
+            if self.run_validators(candidate, subtask_idx, validator_handles, runner)?.is_err() {
+                return Ok(false);
+            }
+            Ok(self.failing_verdict(candidate, main_prog, failing_prog, runner)? == Some(verdict))
+        })?;
+        info!("Minimized the failing test from {} to {} bytes, it is saved to {}", input.len(), minimized.len(), path_str(&write_path));
+        fs::write(&write_path, minimized).map_err(|err| Error::IOError { err, file: path_str(&write_path) })
+    }
+
+    /// Verdict of a solution on a test, judged like in `run_test`: the main solution if `failing_prog` is `None`,
+    /// otherwise the given solution against the output of the main solution. `None` if the main solution fails
+    /// the test when another solution is judged.
+    fn failing_verdict(&self, input: &str, main_prog: ProgramHandle, failing_prog: Option<ProgramHandle>, runner: &mut CppRunner) -> Result<Option<Verdict>> {
+        let programs: Vec<ProgramHandle> = std::iter::once(main_prog).chain(failing_prog).collect();
+        let results = runner.check_programs(input, &programs, self.time_limit, self.memory_limit)?;
+        let mut correct_output = match &results[0] {
+            RunResult::Ok(_, _, output) => output.trim().to_owned() + "\n",
+            result => return Ok(failing_prog.is_none().then(|| Verdict::from(result))),
+        };
+        let main_verdict = Verdict::from_checker(&self.check_output(runner, input, &correct_output, &correct_output)?);
+        let Some(result) = results.get(1) else {
+            return Ok(Some(main_verdict));
+        };
+        if main_verdict != Verdict::Ok {
+            return Ok(None);
+        }
+
+        if self.trim_whitespace {
+            correct_output = trim_whitespace(&correct_output);
+        }
+        Ok(Some(match result {
+            RunResult::Ok(_, _, output) => Verdict::from_checker(&self.check_output(runner, input, &correct_output, output)?),
+            result => Verdict::from(result),
+        }))
     }
 }
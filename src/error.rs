@@ -1,3 +1,4 @@
+use crate::Verdict;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -17,18 +18,49 @@ pub enum Error {
     )]
     CompilerNotFound,
 
+    #[error("Program {tool} is not found. Make sure to install it first. If it is already installed, specify its path with the {env_var} environment variable.")]
+    ToolNotFound { tool: String, env_var: String },
+
     #[error("Compiler error: {stderr}\n{stdout}")]
     CompilerError { stderr: String, stdout: String },
 
     #[error("Solution timed out on test {test_path} (generator {gen_id})")]
     SolutionTimedOut { test_path: String, gen_id: usize },
 
+    #[error("Solution exceeded memory limit on test {test_path} (generator {gen_id})")]
+    SolutionMemoryLimitExceeded { test_path: String, gen_id: usize },
+
     #[error("Solution crashed on test {test_path} (generator {gen_id})")]
     SolutionCrash { test_path: String, gen_id: usize },
 
     #[error("Solution produces wrong answer on {test_path} (generator {gen_id})")]
     SolutionFailed { test_path: String, gen_id: usize },
 
+    #[error("Invalid test in subtask {subtask_number} (generator {gen_id}): {message}. The test is saved to {file}")]
+    InvalidTest { subtask_number: usize, gen_id: usize, message: String, file: String },
+
+    #[error("Invalid test {file} in subtask {subtask_number}: {message}")]
+    InvalidManualTest { subtask_number: usize, message: String, file: String },
+
+    #[error("{solution_name} does not pass test {file} of subtask {subtask_number} ({verdict})")]
+    ManualTestFailed {
+        subtask_number: usize,
+        solution_name: String,
+        verdict: String,
+        file: String,
+    },
+
+    #[error("Subtask {subtask_number} can only depend on earlier subtasks, not on subtask {dependency_number}")]
+    InvalidDependency { subtask_number: usize, dependency_number: usize },
+
+    #[error("Partial solution {partial_number} ({partial_name}) should pass subtask {subtask_number}, but not subtask {dependency_number}, which is included in it")]
+    PartialSolutionDependency {
+        partial_number: usize,
+        partial_name: String,
+        subtask_number: usize,
+        dependency_number: usize,
+    },
+
     #[error("Partial solution {partial_number} ({partial_name}) passes extra subtask {subtask_number} ({subtask_name}) (generator {gen_id})")]
     PartialSolutionPassesExtraSubtask {
         subtask_number: usize,
@@ -48,8 +80,79 @@ pub enum Error {
         gen_id: usize,
     },
 
+    #[error("Partial solution {partial_number} ({partial_name}) scores {score:.3} on subtask {subtask_number} ({subtask_name}), expected {expected}.")]
+    PartialSolutionScoreOutOfRange {
+        subtask_number: usize,
+        partial_number: usize,
+        partial_name: String,
+        subtask_name: String,
+        score: f64,
+        expected: String,
+    },
+
+    #[error("Partial solution {partial_number} ({partial_name}) gets {} on subtask {subtask_number} ({subtask_name}) on {test}, expected {expected}.", .verdict.short_name())]
+    PartialSolutionUnexpectedVerdict {
+        subtask_number: usize,
+        partial_number: usize,
+        partial_name: String,
+        subtask_name: String,
+        verdict: Verdict,
+        expected: String,
+        test: String,
+    },
+
+    #[error("Partial solution {partial_number} ({partial_name}) passes subtask {subtask_number} ({subtask_name}) with the time limit {time_limit}ms, but it should fail it.")]
+    PartialSolutionPassesSubtask {
+        subtask_number: usize,
+        partial_number: usize,
+        partial_name: String,
+        subtask_name: String,
+        time_limit: i32,
+    },
+
+    #[error("{solution_name} does not pass sample {sample_number} ({verdict}). The sample is saved to {file}")]
+    SampleFailed {
+        sample_number: usize,
+        solution_name: String,
+        verdict: String,
+        file: String,
+    },
+
+    #[error("Checker failed with exit code {code:?}: {message}")]
+    CheckerFailed { code: Option<i32>, message: String },
+
+    #[error("Validator failed: {message}")]
+    ValidatorFailed { message: String },
+
+    #[error("Interactor failed: {message}")]
+    InteractorFailed { message: String },
+
     #[error("Missing solution")]
     MissingSolution {},
+
+    #[error("Tests were not generated yet, {file} does not exist. Generate the tests first.")]
+    MissingTests { file: String },
+
+    #[error("Invalid line in {file}: {line}")]
+    InvalidLayout { file: String, line: String },
+
+    #[error("The {format} format needs a memory limit, set one with `with_memory_limit` to export the task.")]
+    MissingMemoryLimit { format: String },
+
+    #[error("Invalid arguments: {message}. Run with --help to see the usage.")]
+    InvalidArguments { message: String },
+
+    #[error("The task has {expected} subtasks, but tests of {found} subtasks were given")]
+    WrongSubtaskCount { expected: usize, found: usize },
+
+    #[error("Unsupported source file extension: {file}")]
+    UnsupportedLanguage { file: String },
+
+    #[error("Invalid parameter \"{name}\": {reason}")]
+    InvalidParameter { name: String, reason: &'static str },
+
+    #[error("Unknown parameter {name}, it is not declared in the space of the generator")]
+    UnknownParameter { name: String },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
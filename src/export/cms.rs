@@ -0,0 +1,153 @@
+use crate::export::{Package, adapted_checker_source, write_file, yaml_string};
+#[cfg(unix)]
+use crate::runner::gcc::Gcc;
+use crate::subtask::ScoreAggregation;
+use crate::task::path_str;
+use crate::{Error, Result};
+use log::warn;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Main function that makes a testlib checker speak the CMS checker protocol. CMS runs
+/// `checker <input> <correct output> <contestant output>` and reads the score from stdout and the message from stderr.
+pub const CMS_CHECKER_MAIN: &str = "
+int main(int argc, char* argv[]) {
+    if (argc < 4) {
+        std::fprintf(stderr, \"usage: checker <input> <correct output> <contestant output>\\n\");
+        return 1;
+    }
+    std::string message;
+    int code = ezcp_run_checker(argv[1], argv[3], argv[2], message);
+    double score = 0.0;
+    if (code == 0) {
+        score = 1.0;
+    } else if (code == 7) {
+        score = ezcp_parse_points(message);
+    } else if (code != 1 && code != 2) {
+        std::fprintf(stderr, \"checker failed with exit code %d: %s\\n\", code, message.c_str());
+        return 1;
+    }
+    std::printf(\"%g\\n\", score);
+    std::fprintf(stderr, \"%s\\n\", message.empty() ? (score >= 1.0 ? \"Output is correct\" : \"Output isn't correct\") : message.c_str());
+    return 0;
+}
+";
+
+/// Writes a CMS task directory in the format of `cmsImportTask`: `task.yaml`, `input/input0.txt`,
+/// `output/output0.txt`, ... (samples first, as public test cases), `gen/GEN` with the subtasks, `check/checker` (with its source) and `sol/*`.
+pub fn export(package: &Package, package_path: &Path) -> Result<()> {
+    package.check_memory_limit("CMS")?;
+    if package_path.exists() {
+        fs::remove_dir_all(package_path).map_err(|err| Error::IOError { err, file: path_str(package_path) })?;
+    }
+    if package.interactor_source.is_some() {
+        warn!("Interactors can not be exported to CMS, the task has to be changed to the Communication type by hand.");
+    }
+    if package.subtasks.iter().any(|subtask| subtask.aggregation == ScoreAggregation::Sum) {
+        warn!("CMS subtasks take the minimum score of their tests, partial scores are not summed.");
+    }
+
+    let tests = package.samples.iter().chain(package.subtasks.iter().flat_map(|subtask| &subtask.tests));
+    for (test_id, (input_file, output_file)) in tests.enumerate() {
+        for (source, destination) in [(input_file, format!("input/input{test_id}.txt")), (output_file, format!("output/output{test_id}.txt"))] {
+            let destination = package_path.join(destination);
+            let contents = fs::read_to_string(source).map_err(|err| Error::IOError { err, file: path_str(source) })?;
+            write_file(&destination, &contents)?;
+        }
+    }
+
+    if let Some(checker_source) = &package.checker_source {
+        let source_path = package_path.join("check").join("checker.cpp");
+        write_file(&source_path, &adapted_checker_source(CMS_CHECKER_MAIN, checker_source))?;
+        // headers included by the checker, like testlib.h, are compiled together with it
+        for header in &package.checker_headers {
+            let destination = package_path.join("check").join(header.file_name().unwrap_or_default());
+            fs::copy(header, &destination).map_err(|err| Error::IOError { err, file: path_str(header) })?;
+        }
+        // the adapter uses POSIX calls, like the judges that run CMS
+        #[cfg(unix)]
+        Gcc::new()?.compile(&source_path, Some(&package_path.join("check").join("checker")))?;
+        #[cfg(not(unix))]
+        warn!("The CMS checker can only be compiled on unix, compile check/checker.cpp into check/checker by hand.");
+    }
+
+    let solution_names = package.solution_file_names();
+    for (solution, name) in package.solutions.iter().zip(&solution_names) {
+        write_file(&package_path.join("sol").join(format!("{name}.{}", solution.language.extension())), &solution.source)?;
+    }
+
+    write_file(&package_path.join("gen").join("GEN"), &gen_file(package))?;
+    write_file(&package_path.join("task.yaml"), &task_yaml(package))
+}
+
+/// Points and number of tests of the CMS subtasks. The samples make up the first subtask with no points.
+fn subtasks(package: &Package) -> Vec<(i32, usize)> {
+    let samples = (!package.samples.is_empty()).then_some((0, package.samples.len()));
+    samples.into_iter().chain(package.subtasks.iter().map(|subtask| (subtask.points, subtask.tests.len()))).collect()
+}
+
+/// Generates `gen/GEN`, which lists the subtasks and the number of tests in each of them.
+pub fn gen_file(package: &Package) -> String {
+    let mut gen_file = String::new();
+    let mut test_id = 0;
+    for (points, num_tests) in subtasks(package) {
+        writeln!(gen_file, "#ST: {points}").ok();
+        for _ in 0..num_tests {
+            writeln!(gen_file, "#COPY: input/input{test_id}.txt").ok();
+            test_id += 1;
+        }
+    }
+    gen_file
+}
+
+/// Generates `task.yaml` of the task.
+pub fn task_yaml(package: &Package) -> String {
+    let mut yaml = String::new();
+    writeln!(yaml, "name: {}", package.short_name()).ok();
+    writeln!(yaml, "title: {}", yaml_string(&package.name)).ok();
+    writeln!(yaml, "time_limit: {}", f64::from(package.time_limit) / 1000.0).ok();
+    writeln!(yaml, "memory_limit: {}", package.memory_limit).ok();
+    writeln!(yaml, "n_input: {}", package.num_tests()).ok();
+    writeln!(yaml, "infile: \"\"").ok();
+    writeln!(yaml, "outfile: \"\"").ok();
+    writeln!(yaml, "token_mode: disabled").ok();
+    if !package.samples.is_empty() {
+        let public_testcases: Vec<String> = (0..package.samples.len()).map(|test_id| test_id.to_string()).collect();
+        writeln!(yaml, "public_testcases: {}", public_testcases.join(", ")).ok();
+    }
+    writeln!(yaml, "score_type: GroupMin").ok();
+    writeln!(yaml, "score_type_parameters: [{}]", score_type_parameters(package).join(", ")).ok();
+    yaml
+}
+
+/// Parameters of the `GroupMin` score type: points and number of tests of each subtask. If subtasks include
+/// tests of other subtasks, the tests of every subtask are given with a regex of their codenames (`000`, `001`, ...) instead,
+/// because CMS does not allow mixing both forms.
+fn score_type_parameters(package: &Package) -> Vec<String> {
+    if package.subtasks.iter().all(|subtask| subtask.dependencies.is_empty()) {
+        return subtasks(package).iter().map(|(points, num_tests)| format!("[{points}, {num_tests}]")).collect();
+    }
+
+    // ids of the tests of every CMS subtask, the samples are the first one if there are any
+    let mut test_ids = Vec::new();
+    let mut test_id = 0;
+    for (_, num_tests) in subtasks(package) {
+        test_ids.push((test_id..test_id + num_tests).collect::<Vec<_>>());
+        test_id += num_tests;
+    }
+    let offset = usize::from(!package.samples.is_empty());
+    let mut parameters = Vec::new();
+    for (group_idx, (points, _)) in subtasks(package).into_iter().enumerate() {
+        let mut ids = test_ids[group_idx].clone();
+        if let Some(subtask) = group_idx.checked_sub(offset).map(|subtask_idx| &package.subtasks[subtask_idx]) {
+            for dependency in &subtask.dependencies {
+                ids.extend(&test_ids[dependency + offset]);
+            }
+        }
+        ids.sort_unstable();
+        let codenames: Vec<String> = ids.iter().map(|id| format!("{id:03}")).collect();
+        parameters.push(format!("[{points}, \"^({})$\"]", codenames.join("|")));
+    }
+    parameters
+}
@@ -0,0 +1,139 @@
+use crate::archiver::archive_named_files;
+use crate::export::{Package, adapted_checker_source, write_file, yaml_string};
+use crate::partial_solution::round_points;
+use crate::subtask::ScoreAggregation;
+use crate::{Result, Verdict};
+use indicatif::MultiProgress;
+use log::warn;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Main function that makes a testlib checker an output validator. Kattis runs
+/// `validator <input> <answer> <feedback dir> < team output` and expects exit code 42 for accepted and 43 for wrong answer.
+/// Partial scores of the checker are accepted only if they are full.
+pub const KATTIS_VALIDATOR_MAIN: &str = "
+#include <fstream>
+#include <iostream>
+
+int main(int argc, char* argv[]) {
+    if (argc < 4) {
+        std::fprintf(stderr, \"usage: validator <input> <answer> <feedback dir> < team output\\n\");
+        return 1;
+    }
+    std::string feedback_dir = argv[3];
+    std::string output_path = feedback_dir + \"/team_output\";
+    {
+        std::ofstream output(output_path);
+        output << std::cin.rdbuf();
+    }
+    std::string message;
+    int code = ezcp_run_checker(argv[1], &output_path[0], argv[2], message);
+    if (code == 7) {
+        code = ezcp_parse_points(message) >= 1.0 ? 0 : 1;
+    }
+    if (code != 0 && code != 1 && code != 2) {
+        std::fprintf(stderr, \"checker failed with exit code %d: %s\\n\", code, message.c_str());
+        return 1;
+    }
+    std::ofstream(feedback_dir + \"/judgemessage.txt\") << message << std::endl;
+    return code == 0 ? 42 : 43;
+}
+";
+
+/// Writes a Kattis problem package (`kattis.zip`), which `DOMjudge` imports: `problem.yaml`, `.timelimit`,
+/// `data/sample/01.in`, `data/sample/01.ans`, ..., `data/secret/subtask1/01.in`, `data/secret/subtask1/01.ans`, ... with `testdata.yaml` scoring for every subtask,
+/// `output_validators/checker/checker.cpp` and `submissions/accepted/*` and `submissions/wrong_answer/*`.
+///
+/// * `staging_path` - Directory where generated files are written before they are archived.
+pub fn export(package: &Package, staging_path: &Path, package_path: &Path, logger: &MultiProgress) -> Result<()> {
+    if package.interactor_source.is_some() {
+        warn!("Interactors can not be exported to Kattis packages, the interactive validator has to be added by hand.");
+    }
+
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+    let add_source = |files: &mut Vec<(PathBuf, String)>, name: String, source: &str| -> Result<()> {
+        let path = staging_path.join(&name);
+        write_file(&path, source)?;
+        files.push((path, name));
+        Ok(())
+    };
+
+    for (sample_idx, (input_file, output_file)) in package.samples.iter().enumerate() {
+        files.push((input_file.clone(), format!("data/sample/{:02}.in", sample_idx + 1)));
+        files.push((output_file.clone(), format!("data/sample/{:02}.ans", sample_idx + 1)));
+    }
+    for (subtask_idx, subtask) in package.subtasks.iter().enumerate() {
+        let dir = format!("data/secret/subtask{}", subtask_idx + 1);
+        // test groups can not share tests, so tests of included subtasks are copied into the group
+        let tests: Vec<&(PathBuf, PathBuf)> = subtask
+            .tests
+            .iter()
+            .chain(subtask.dependencies.iter().flat_map(|dependency| &package.subtasks[*dependency].tests))
+            .collect();
+        for (test_idx, (input_file, output_file)) in tests.iter().enumerate() {
+            files.push((input_file.clone(), format!("{dir}/{:02}.in", test_idx + 1)));
+            files.push((output_file.clone(), format!("{dir}/{:02}.ans", test_idx + 1)));
+        }
+        add_source(&mut files, format!("{dir}/testdata.yaml"), &subtask_testdata_yaml(subtask.points, subtask.aggregation, tests.len()))?;
+    }
+    let total_points: i32 = package.subtasks.iter().map(|subtask| subtask.points).sum();
+    add_source(
+        &mut files,
+        "data/secret/testdata.yaml".to_owned(),
+        &format!("on_reject: continue\ngrader_flags: sum\nrange: 0 {total_points}\n"),
+    )?;
+
+    if let Some(checker_source) = &package.checker_source {
+        add_source(
+            &mut files,
+            "output_validators/checker/checker.cpp".to_owned(),
+            &adapted_checker_source(KATTIS_VALIDATOR_MAIN, checker_source),
+        )?;
+        // headers included by the checker, like testlib.h, are compiled together with it
+        for header in &package.checker_headers {
+            files.push((header.clone(), format!("output_validators/checker/{}", header.file_name().unwrap_or_default().to_string_lossy())));
+        }
+    }
+
+    let solution_names = package.solution_file_names();
+    for (solution, name) in package.solutions.iter().zip(&solution_names) {
+        let verdict = if (0..package.subtasks.len()).all(|subtask_idx| solution.passes_subtasks.contains(&subtask_idx)) {
+            "accepted"
+        } else {
+            match solution.rejected_verdict(package.subtasks.len()) {
+                Some(Verdict::TimedOut) => "time_limit_exceeded",
+                Some(Verdict::Crashed) => "run_time_error",
+                _ => "wrong_answer",
+            }
+        };
+        add_source(&mut files, format!("submissions/{verdict}/{name}.{}", solution.language.extension()), &solution.source)?;
+    }
+
+    add_source(&mut files, ".timelimit".to_owned(), &format!("{}\n", f64::from(package.time_limit) / 1000.0))?;
+    add_source(&mut files, "problem.yaml".to_owned(), &problem_yaml(package))?;
+
+    archive_named_files(&files, package_path, logger)
+}
+
+/// Generates `testdata.yaml` of a subtask. Tests of a subtask that takes the minimum are worth all its points,
+/// otherwise the points are split between them.
+pub fn subtask_testdata_yaml(points: i32, aggregation: ScoreAggregation, num_tests: usize) -> String {
+    match aggregation {
+        ScoreAggregation::Min => format!("on_reject: break\ngrader_flags: min\naccept_score: {points}\nrange: 0 {points}\n"),
+        ScoreAggregation::Sum => format!(
+            "on_reject: continue\ngrader_flags: sum\naccept_score: {}\nrange: 0 {points}\n",
+            round_points(f64::from(points) / num_tests.max(1) as f64)
+        ),
+    }
+}
+
+/// Generates `problem.yaml` of the package.
+pub fn problem_yaml(package: &Package) -> String {
+    let mut yaml = String::new();
+    writeln!(yaml, "name: {}", yaml_string(&package.name)).ok();
+    writeln!(yaml, "type: scoring").ok();
+    writeln!(yaml, "validation: {}", if package.checker_source.is_some() { "custom" } else { "default" }).ok();
+    writeln!(yaml, "limits:").ok();
+    writeln!(yaml, "    memory: {}", package.memory_limit).ok();
+    yaml
+}
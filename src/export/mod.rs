@@ -0,0 +1,350 @@
+//! Exporters of generated tests into packages for judging systems.
+mod cms;
+mod kattis;
+mod polygon;
+
+use crate::runner::language::Language;
+use crate::solution::read_source_file;
+use crate::subtask::ScoreAggregation;
+use crate::task::{TaskPrograms, TestFiles, path_str};
+use crate::{Error, Result, Task, ToOutput, Verdict};
+use log::warn;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Format in which the generated tests are packaged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// A flat zip archive of input and output files (`tests.zip` by default).
+    #[default]
+    Zip,
+    /// A Codeforces Polygon package (`polygon.zip`) with `problem.xml`,
+    /// tests with their groups and points, solutions tagged by expected verdict and the checker.
+    Polygon,
+    /// A CMS task directory (`cms/`) with `task.yaml`, `input/`, `output/`, the checker in `check/`
+    /// and the solutions in `sol/`, ready for `cmsImportTask`.
+    Cms,
+    /// A Kattis problem package (`kattis.zip`) for `DOMjudge` with `problem.yaml`, the tests in `data/secret`
+    /// with a folder and `testdata.yaml` scoring per subtask, the output validator and the submissions.
+    Kattis,
+}
+
+/// C++ checker that compares the output with the answer token by token (up to whitespace),
+/// like the default checker of `Task`. It follows the testlib exit codes, but does not need testlib.
+pub const TOKEN_CHECKER: &str = r#"#include <fstream>
+#include <iostream>
+#include <string>
+
+int main(int argc, char* argv[]) {
+    if (argc < 4) {
+        std::cerr << "usage: check <input> <output> <answer>";
+        return 3;
+    }
+    std::ifstream output(argv[2]), answer(argv[3]);
+    std::string expected, found;
+    for (int token = 1;; token++) {
+        bool has_expected = static_cast<bool>(answer >> expected);
+        bool has_found = static_cast<bool>(output >> found);
+        if (!has_expected && !has_found) {
+            return 0;
+        }
+        if (!has_expected) {
+            std::cerr << "extra token " << token << ": " << found;
+            return 1;
+        }
+        if (!has_found) {
+            std::cerr << "output ended before token " << token << ", expected " << expected;
+            return 1;
+        }
+        if (expected != found) {
+            std::cerr << "token " << token << " differs: expected " << expected << ", found " << found;
+            return 1;
+        }
+    }
+}
+"#;
+
+/// Runs a testlib checker in a child process and returns its exit code (-1 if it crashed) with what it wrote to stderr.
+/// Judging systems that call checkers differently get their own `main`, which calls `ezcp_run_checker`,
+/// and the checker source is appended after it with its `main` renamed.
+const CHECKER_RUNNER: &str = "#include <cstdio>
+#include <cstdlib>
+#include <string>
+#include <sys/wait.h>
+#include <unistd.h>
+
+int ezcp_checker_main(int argc, char* argv[]);
+
+static std::string ezcp_trim(const std::string& text) {
+    std::size_t start = text.find_first_not_of(\" \\t\\r\\n\");
+    std::size_t end = text.find_last_not_of(\" \\t\\r\\n\");
+    return start == std::string::npos ? std::string() : text.substr(start, end - start + 1);
+}
+
+static int ezcp_run_checker(char* input, char* output, char* answer, std::string& message) {
+    int pipe_fds[2];
+    if (pipe(pipe_fds) != 0) {
+        return -1;
+    }
+    pid_t pid = fork();
+    if (pid < 0) {
+        return -1;
+    }
+    if (pid == 0) {
+        close(pipe_fds[0]);
+        dup2(pipe_fds[1], 2);
+        char name[] = \"checker\";
+        char* args[] = {name, input, output, answer, nullptr};
+        std::exit(ezcp_checker_main(4, args));
+    }
+    close(pipe_fds[1]);
+    char buffer[4096];
+    ssize_t length;
+    while ((length = read(pipe_fds[0], buffer, sizeof buffer)) > 0) {
+        message.append(buffer, length);
+    }
+    close(pipe_fds[0]);
+    int status = 0;
+    waitpid(pid, &status, 0);
+    message = ezcp_trim(message);
+    return WIFEXITED(status) ? WEXITSTATUS(status) : -1;
+}
+
+// testlib reports partial scores as \"points <score> <message>\", the score is removed from the message.
+// Like in the runner, the score is the fraction of the points of the test in [0, 1].
+static double ezcp_parse_points(std::string& message) {
+    std::string rest = message.compare(0, 6, \"points\") == 0 ? message.substr(6) : message;
+    char* end = nullptr;
+    double score = std::strtod(rest.c_str(), &end);
+    message = ezcp_trim(end);
+    return score;
+}
+";
+
+/// Source of a checker program for a judging system that does not use the testlib protocol:
+/// `CHECKER_RUNNER`, the given `main` of the judging system and the testlib checker with its `main` renamed.
+fn adapted_checker_source(main: &str, checker_source: &str) -> String {
+    format!("{CHECKER_RUNNER}{main}\n#define main ezcp_checker_main\n{checker_source}")
+}
+
+/// A solution as it is put into a package.
+pub struct PackageSolution {
+    pub name: String,
+    pub source: String,
+    pub language: Language,
+    /// The main solution passes all subtasks.
+    pub is_main: bool,
+    pub passes_subtasks: HashSet<usize>,
+    /// Verdicts the solution fails some subtasks with, see `Solution::with_expected_verdicts`.
+    pub expected_verdicts: HashMap<usize, BTreeSet<Verdict>>,
+}
+
+impl PackageSolution {
+    /// Returns the verdict the solution fails with, if it is the only expected verdict on every subtask it does not pass.
+    pub fn rejected_verdict(&self, num_subtasks: usize) -> Option<Verdict> {
+        let mut verdicts = (0..num_subtasks).filter(|subtask_idx| !self.passes_subtasks.contains(subtask_idx)).map(|subtask_idx| {
+            self.expected_verdicts
+                .get(&subtask_idx)
+                .and_then(|verdicts| if verdicts.len() == 1 { verdicts.first().copied() } else { None })
+        });
+        let verdict = verdicts.next()??;
+        verdicts.all(|other| other == Some(verdict)).then_some(verdict)
+    }
+}
+
+/// A subtask with the files of its tests.
+pub struct PackageSubtask {
+    pub points: i32,
+    pub aggregation: ScoreAggregation,
+    /// Input and output files of the tests.
+    pub tests: Vec<(PathBuf, PathBuf)>,
+    /// Indices of the earlier subtasks whose tests are included in this one (also through other subtasks).
+    pub dependencies: Vec<usize>,
+}
+
+/// Everything an exporter needs to know about a task after the tests are generated.
+pub struct Package {
+    pub name: String,
+    /// Time limit in milliseconds
+    pub time_limit: i32,
+    /// Memory limit in megabytes
+    pub memory_limit: i32,
+    /// Input and output files of the sample tests, which are exported separately from the subtasks.
+    pub samples: Vec<(PathBuf, PathBuf)>,
+    pub subtasks: Vec<PackageSubtask>,
+    /// The main solution comes first.
+    pub solutions: Vec<PackageSolution>,
+    /// Source of the C++ checker, `TOKEN_CHECKER` is used if there is none.
+    pub checker_source: Option<String>,
+    /// Header files (like `testlib.h`) in the directory of a checker file, which the checker may include.
+    /// They are put next to the checker source in packages that compile the checker.
+    pub checker_headers: Vec<PathBuf>,
+    pub interactor_source: Option<String>,
+}
+
+impl Package {
+    /// Source of the checker to be put into the package.
+    pub fn checker_source(&self) -> &str {
+        self.checker_source.as_deref().unwrap_or(TOKEN_CHECKER)
+    }
+
+    /// Number of tests over all subtasks, including the samples.
+    pub fn num_tests(&self) -> usize {
+        self.samples.len() + self.subtasks.iter().map(|subtask| subtask.tests.len()).sum::<usize>()
+    }
+
+    /// Returns an error if the task has no memory limit (0 means unlimited), because `format` can not express that.
+    pub fn check_memory_limit(&self, format: &str) -> Result<()> {
+        if self.memory_limit > 0 {
+            Ok(())
+        } else {
+            Err(Error::MissingMemoryLimit { format: format.to_owned() })
+        }
+    }
+
+    /// Short lowercase name without spaces, used for file names.
+    pub fn short_name(&self) -> String {
+        slug(&self.name)
+    }
+
+    /// File names (without extension) of the solutions, unique within the package.
+    pub fn solution_file_names(&self) -> Vec<String> {
+        let mut used = HashSet::new();
+        let mut names = Vec::new();
+        for (i, solution) in self.solutions.iter().enumerate() {
+            let mut name = if solution.is_main { "main".to_owned() } else { slug(&solution.name) };
+            if name.is_empty() || !used.insert(name.clone()) {
+                name = format!("{name}-{}", i + 1);
+                used.insert(name.clone());
+            }
+            names.push(name);
+        }
+        names
+    }
+}
+
+/// Lowercase name with everything except letters and digits replaced by dashes.
+fn slug(name: &str) -> String {
+    let mut result = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            result.push(c.to_ascii_lowercase());
+        } else if !result.is_empty() && !result.ends_with('-') {
+            result.push('-');
+        }
+    }
+    result.trim_end_matches('-').to_owned()
+}
+
+/// Escapes text for XML attributes and elements.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+/// Quotes text as a YAML string.
+fn yaml_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Header files (`.h` and `.hpp`) in the directory of the checker file, sorted by name.
+fn checker_headers(checker_file: &Path) -> Result<Vec<PathBuf>> {
+    let checker_dir = checker_file.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."));
+    let entries = fs::read_dir(checker_dir).map_err(|err| Error::IOError { err, file: path_str(checker_dir) })?;
+    let mut headers = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|err| Error::IOError { err, file: path_str(checker_dir) })?.path();
+        if path.is_file() && path.extension().is_some_and(|extension| extension == "h" || extension == "hpp") {
+            headers.push(path);
+        }
+    }
+    headers.sort();
+    Ok(headers)
+}
+
+/// Writes a text file into the package staging directory.
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| Error::IOError { err, file: path_str(parent) })?;
+    }
+    fs::write(path, contents).map_err(|err| Error::IOError { err, file: path_str(path) })
+}
+
+impl<T: ToOutput> Task<T> {
+    /// Collects the generated tests, the solutions and the checker into a `Package`.
+    pub(crate) fn build_package(
+        &self,
+        sample_files: &[(PathBuf, PathBuf)],
+        test_files: &[Vec<(PathBuf, PathBuf)>],
+        main_source: &str,
+        solution_sources: &[String],
+        time_limit: i32,
+    ) -> Result<Package> {
+        let checker_source = if let Some(checker_file) = &self.checker_file {
+            Some(read_source_file(checker_file)?)
+        } else {
+            self.checker_source.clone()
+        };
+        if checker_source.is_none() && (self.checker.is_some() || self.scoring_checker.is_some()) && self.interactor_source.is_none() {
+            warn!("Checkers written in Rust can not be exported, the package compares outputs token by token.");
+        }
+
+        let mut solutions = vec![PackageSolution {
+            name: "main".to_owned(),
+            source: main_source.to_owned(),
+            language: self.solution_language,
+            is_main: true,
+            passes_subtasks: (0..self.subtasks.len()).collect(),
+            expected_verdicts: HashMap::new(),
+        }];
+        for (solution, source) in self.solutions.iter().zip(solution_sources) {
+            solutions.push(PackageSolution {
+                name: solution.name.clone(),
+                source: source.clone(),
+                language: solution.language,
+                is_main: false,
+                passes_subtasks: solution.passes_subtasks.clone(),
+                expected_verdicts: solution.expected_verdicts.clone(),
+            });
+        }
+
+        Ok(Package {
+            name: self.name.clone(),
+            time_limit,
+            memory_limit: self.memory_limit,
+            samples: sample_files.to_vec(),
+            subtasks: self
+                .subtasks
+                .iter()
+                .zip(test_files)
+                .enumerate()
+                .map(|(subtask_idx, (subtask, tests))| PackageSubtask {
+                    points: subtask.points,
+                    aggregation: subtask.aggregation,
+                    tests: tests.clone(),
+                    dependencies: self.included_subtasks(subtask_idx).into_iter().filter(|&idx| idx != subtask_idx).collect(),
+                })
+                .collect(),
+            solutions,
+            checker_source,
+            checker_headers: self.checker_file.as_deref().map(checker_headers).transpose()?.unwrap_or_default(),
+            interactor_source: self.interactor_source.clone(),
+        })
+    }
+
+    /// Writes the tests in the given format: the tests archive for `ExportFormat::Zip`,
+    /// otherwise the package for the judging system in the problem directory.
+    pub(crate) fn export_package(&self, format: ExportFormat, programs: &TaskPrograms, test_files: &TestFiles) -> Result<()> {
+        let build_package = || self.build_package(&test_files.samples, &test_files.subtasks, &programs.solution_source, &programs.solution_sources, programs.time_limit);
+        let staging_path = self.build_folder_path.join("package");
+        if staging_path.exists() {
+            fs::remove_dir_all(&staging_path).map_err(|err| Error::IOError { err, file: path_str(&staging_path) })?;
+        }
+        match format {
+            ExportFormat::Zip => self.archive_tests(programs, test_files),
+            ExportFormat::Polygon => polygon::export(&build_package()?, &staging_path, &self.problem_path.join("polygon.zip"), &self.logger),
+            ExportFormat::Cms => cms::export(&build_package()?, &self.problem_path.join("cms")),
+            ExportFormat::Kattis => kattis::export(&build_package()?, &staging_path, &self.problem_path.join("kattis.zip"), &self.logger),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::archiver::archive_named_files;
+use crate::export::{Package, write_file, xml_escape};
+use crate::partial_solution::round_points;
+use crate::runner::language::Language;
+use crate::subtask::ScoreAggregation;
+use crate::{Result, Verdict};
+use indicatif::MultiProgress;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Source type of a language in Polygon.
+const fn source_type(language: Language) -> &'static str {
+    match language {
+        Language::Cpp => "cpp.g++17",
+        Language::C => "c.gcc",
+        Language::Rust => "rust",
+        Language::Python => "python.3",
+    }
+}
+
+/// Writes a Polygon package: `problem.xml`, `tests/01`, `tests/01.a`, ... (samples first, in group 0), `files/check.cpp`,
+/// `files/interactor.cpp` for interactive tasks and `solutions/*`.
+///
+/// * `staging_path` - Directory where generated files are written before they are archived.
+pub fn export(package: &Package, staging_path: &Path, package_path: &Path, logger: &MultiProgress) -> Result<()> {
+    package.check_memory_limit("Polygon")?;
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+
+    let mut test_id = 0;
+    for (input_file, output_file) in package.samples.iter().chain(package.subtasks.iter().flat_map(|subtask| &subtask.tests)) {
+        test_id += 1;
+        files.push((input_file.clone(), format!("tests/{test_id:02}")));
+        files.push((output_file.clone(), format!("tests/{test_id:02}.a")));
+    }
+
+    let mut add_source = |name: String, source: &str| -> Result<()> {
+        let path = staging_path.join(&name);
+        write_file(&path, source)?;
+        files.push((path, name));
+        Ok(())
+    };
+
+    add_source("files/check.cpp".to_owned(), package.checker_source())?;
+    if let Some(interactor_source) = &package.interactor_source {
+        add_source("files/interactor.cpp".to_owned(), interactor_source)?;
+    }
+    let solution_names = package.solution_file_names();
+    for (solution, name) in package.solutions.iter().zip(&solution_names) {
+        add_source(format!("solutions/{name}.{}", solution.language.extension()), &solution.source)?;
+    }
+    add_source("problem.xml".to_owned(), &problem_xml(package, &solution_names))?;
+
+    archive_named_files(&files, package_path, logger)
+}
+
+/// Generates `problem.xml` of the package.
+pub fn problem_xml(package: &Package, solution_names: &[String]) -> String {
+    let mut xml = String::new();
+    writeln!(xml, r#"<?xml version="1.0" encoding="utf-8" standalone="no"?>"#).ok();
+    writeln!(xml, r#"<problem revision="1" short-name="{}">"#, xml_escape(&package.short_name())).ok();
+    writeln!(xml, "    <names>").ok();
+    writeln!(xml, r#"        <name language="english" value="{}"/>"#, xml_escape(&package.name)).ok();
+    writeln!(xml, "    </names>").ok();
+
+    writeln!(xml, r#"    <judging input-file="" output-file="">"#).ok();
+    writeln!(xml, r#"        <testset name="tests">"#).ok();
+    writeln!(xml, "            <time-limit>{}</time-limit>", package.time_limit).ok();
+    writeln!(xml, "            <memory-limit>{}</memory-limit>", i64::from(package.memory_limit) * 1024 * 1024).ok();
+    writeln!(xml, "            <test-count>{}</test-count>", package.num_tests()).ok();
+    writeln!(xml, "            <input-path-pattern>tests/%02d</input-path-pattern>").ok();
+    writeln!(xml, "            <answer-path-pattern>tests/%02d.a</answer-path-pattern>").ok();
+    writeln!(xml, "            <tests>").ok();
+    for _ in &package.samples {
+        writeln!(xml, r#"                <test method="manual" sample="true" group="0"/>"#).ok();
+    }
+    for (subtask_idx, subtask) in package.subtasks.iter().enumerate() {
+        for _ in &subtask.tests {
+            match subtask.aggregation {
+                ScoreAggregation::Min => writeln!(xml, r#"                <test method="manual" group="{}"/>"#, subtask_idx + 1),
+                ScoreAggregation::Sum => writeln!(
+                    xml,
+                    r#"                <test method="manual" group="{}" points="{}"/>"#,
+                    subtask_idx + 1,
+                    round_points(f64::from(subtask.points) / subtask.tests.len() as f64)
+                ),
+            }
+            .ok();
+        }
+    }
+    writeln!(xml, "            </tests>").ok();
+    writeln!(xml, "            <groups>").ok();
+    if !package.samples.is_empty() {
+        writeln!(xml, r#"                <group feedback-policy="complete" name="0" points="0" points-policy="complete-group"/>"#).ok();
+    }
+    for (subtask_idx, subtask) in package.subtasks.iter().enumerate() {
+        let points_policy = match subtask.aggregation {
+            ScoreAggregation::Min => "complete-group",
+            ScoreAggregation::Sum => "each-test",
+        };
+        let group = format!(
+            r#"                <group feedback-policy="complete" name="{}" points="{}" points-policy="{points_policy}""#,
+            subtask_idx + 1,
+            subtask.points
+        );
+        if subtask.dependencies.is_empty() {
+            writeln!(xml, "{group}/>").ok();
+        } else {
+            writeln!(xml, "{group}>").ok();
+            writeln!(xml, "                    <dependencies>").ok();
+            for dependency in &subtask.dependencies {
+                writeln!(xml, r#"                        <dependency group="{}"/>"#, dependency + 1).ok();
+            }
+            writeln!(xml, "                    </dependencies>").ok();
+            writeln!(xml, "                </group>").ok();
+        }
+    }
+    writeln!(xml, "            </groups>").ok();
+    writeln!(xml, "        </testset>").ok();
+    writeln!(xml, "    </judging>").ok();
+
+    writeln!(xml, "    <assets>").ok();
+    writeln!(xml, r#"        <checker type="testlib">"#).ok();
+    writeln!(xml, r#"            <source path="files/check.cpp" type="{}"/>"#, source_type(Language::Cpp)).ok();
+    writeln!(xml, "        </checker>").ok();
+    if package.interactor_source.is_some() {
+        writeln!(xml, "        <interactor>").ok();
+        writeln!(xml, r#"            <source path="files/interactor.cpp" type="{}"/>"#, source_type(Language::Cpp)).ok();
+        writeln!(xml, "        </interactor>").ok();
+    }
+    writeln!(xml, "        <solutions>").ok();
+    for (solution, name) in package.solutions.iter().zip(solution_names) {
+        let tag = if solution.is_main {
+            "main"
+        } else if (0..package.subtasks.len()).all(|subtask_idx| solution.passes_subtasks.contains(&subtask_idx)) {
+            "accepted"
+        } else {
+            match solution.rejected_verdict(package.subtasks.len()) {
+                Some(Verdict::WrongAnswer) => "wrong-answer",
+                Some(Verdict::TimedOut) => "time-limit-exceeded",
+                Some(Verdict::MemoryLimitExceeded) => "memory-limit-exceeded",
+                _ => "rejected",
+            }
+        };
+        writeln!(xml, r#"            <solution tag="{tag}">"#).ok();
+        writeln!(
+            xml,
+            r#"                <source path="solutions/{}.{}" type="{}"/>"#,
+            xml_escape(name),
+            solution.language.extension(),
+            source_type(solution.language)
+        )
+        .ok();
+        writeln!(xml, "            </solution>").ok();
+    }
+    writeln!(xml, "        </solutions>").ok();
+    writeln!(xml, "    </assets>").ok();
+    writeln!(xml, "</problem>").ok();
+    xml
+}
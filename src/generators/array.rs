@@ -1,5 +1,6 @@
-use rand::RngExt;
+use crate::GeneratorRng;
 use rand::prelude::ThreadRng;
+use rand::{Rng, RngExt};
 use std::fmt::Write;
 
 /// This function converts an array of integers to a string.
@@ -27,15 +28,22 @@ pub fn array_to_string(array: &Vec<i32>, include_count: bool) -> String {
 /// The array will have a length between `min_n` and `max_n` (inclusive).
 /// The generator function will be called to generate each element in the array.
 pub fn array_generator_custom<F: Fn(&mut ThreadRng) -> i32>(min_n: i32, max_n: i32, generator: F) -> impl Fn() -> String {
-    move || {
-        let mut rng = rand::rng();
-        let n = rng.random_range(min_n..=max_n);
-        let mut array = Vec::new();
-        for _ in 0..n {
-            array.push(generator(&mut rng));
-        }
-        array_to_string(&array, true)
+    move || random_array(&mut rand::rng(), min_n, max_n, &generator)
+}
+
+/// Same as `array_generator_custom`, but the array is generated with the seeded generator of the test,
+/// so it is used with `Subtask::with_seeded_test`.
+pub fn array_generator_custom_with_rng<F: Fn(&mut GeneratorRng) -> i32>(min_n: i32, max_n: i32, generator: F) -> impl Fn(&mut GeneratorRng) -> String {
+    move |rng| random_array(rng, min_n, max_n, &generator)
+}
+
+fn random_array<R: Rng>(rng: &mut R, min_n: i32, max_n: i32, generator: impl Fn(&mut R) -> i32) -> String {
+    let n = rng.random_range(min_n..=max_n);
+    let mut array = Vec::new();
+    for _ in 0..n {
+        array.push(generator(rng));
     }
+    array_to_string(&array, true)
 }
 
 /// This function returns a function that generates an array of integers.
@@ -45,3 +53,9 @@ pub fn array_generator_custom<F: Fn(&mut ThreadRng) -> i32>(min_n: i32, max_n: i
 pub fn array_generator(min_n: i32, max_n: i32, min_x: i32, max_x: i32) -> impl Fn() -> String {
     array_generator_custom(min_n, max_n, move |rng| rng.random_range(min_x..=max_x))
 }
+
+/// Same as `array_generator`, but the array is generated with the seeded generator of the test,
+/// so it is used with `Subtask::with_seeded_test`.
+pub fn array_generator_with_rng(min_n: i32, max_n: i32, min_x: i32, max_x: i32) -> impl Fn(&mut GeneratorRng) -> String {
+    array_generator_custom_with_rng(min_n, max_n, move |rng| rng.random_range(min_x..=max_x))
+}
@@ -1,6 +1,6 @@
-use crate::ToOutput;
-use rand::RngExt;
+use crate::{GeneratorRng, ToOutput};
 use rand::prelude::SliceRandom;
+use rand::{Rng, RngExt};
 use std::collections::HashSet;
 use std::fmt::Write;
 
@@ -42,8 +42,13 @@ impl Graph {
     /// The edges are chosen randomly.
     #[must_use]
     pub fn new_random(n: i32, m: i32) -> Self {
+        Self::new_random_with_rng(n, m, &mut rand::rng())
+    }
+
+    /// Same as `new_random`, but the graph is generated with `rng`.
+    #[must_use]
+    pub fn new_random_with_rng<R: Rng>(n: i32, m: i32, rng: &mut R) -> Self {
         let mut result = Self::new_empty(n);
-        let mut rng = rand::rng();
         while result.get_num_edges() < m {
             let u = rng.random_range(0..n);
             let v = rng.random_range(0..n);
@@ -55,11 +60,16 @@ impl Graph {
     /// This function create a new random path (that is also a tree)
     #[must_use]
     pub fn new_random_path(n: i32) -> Self {
+        Self::new_random_path_with_rng(n, &mut rand::rng())
+    }
+
+    /// Same as `new_random_path`, but the graph is generated with `rng`.
+    #[must_use]
+    pub fn new_random_path_with_rng<R: Rng>(n: i32, rng: &mut R) -> Self {
         let mut result = Self::new_empty(n);
         result.is_tree = true;
-        let mut rng = rand::rng();
         let mut nodes = (0..n).collect::<Vec<_>>();
-        nodes.shuffle(&mut rng);
+        nodes.shuffle(rng);
         for i in 1..n {
             let u = nodes[(i - 1) as usize];
             let v = nodes[i as usize];
@@ -72,11 +82,16 @@ impl Graph {
     /// The edges are chosen randomly.
     #[must_use]
     pub fn new_random_tree(n: i32) -> Self {
+        Self::new_random_tree_with_rng(n, &mut rand::rng())
+    }
+
+    /// Same as `new_random_tree`, but the graph is generated with `rng`.
+    #[must_use]
+    pub fn new_random_tree_with_rng<R: Rng>(n: i32, rng: &mut R) -> Self {
         let mut result = Self::new_empty(n);
         result.is_tree = true;
-        let mut rng = rand::rng();
         let mut nodes = (0..n).collect::<Vec<_>>();
-        nodes.shuffle(&mut rng);
+        nodes.shuffle(rng);
         for i in 1..n {
             let u = nodes[i as usize];
             let v = nodes[rng.random_range(0..i) as usize];
@@ -88,11 +103,16 @@ impl Graph {
     /// this creates a random tree that has O(n) depth
     #[must_use]
     pub fn new_random_deep_tree(n: i32) -> Self {
+        Self::new_random_deep_tree_with_rng(n, &mut rand::rng())
+    }
+
+    /// Same as `new_random_deep_tree`, but the graph is generated with `rng`.
+    #[must_use]
+    pub fn new_random_deep_tree_with_rng<R: Rng>(n: i32, rng: &mut R) -> Self {
         let mut result = Self::new_empty(n);
         result.is_tree = true;
-        let mut rng = rand::rng();
         let mut nodes = (0..n).collect::<Vec<_>>();
-        nodes.shuffle(&mut rng);
+        nodes.shuffle(rng);
         let mut last_on_chain = nodes[0];
         for i in 1..n {
             let u = nodes[i as usize];
@@ -113,9 +133,14 @@ impl Graph {
     /// If m <= n - 1, the graph will be a tree.
     #[must_use]
     pub fn new_random_connected(n: i32, m: i32) -> Self {
-        let mut result = Self::new_random_tree(n);
+        Self::new_random_connected_with_rng(n, m, &mut rand::rng())
+    }
+
+    /// Same as `new_random_connected`, but the graph is generated with `rng`.
+    #[must_use]
+    pub fn new_random_connected_with_rng<R: Rng>(n: i32, m: i32, rng: &mut R) -> Self {
+        let mut result = Self::new_random_tree_with_rng(n, rng);
         result.is_tree = false;
-        let mut rng = rand::rng();
         while result.get_num_edges() < m {
             let u = rng.random_range(0..n);
             let v = rng.random_range(0..n);
@@ -128,10 +153,15 @@ impl Graph {
     /// The edges are chosen randomly and the graph is guaranteed to be bipartite.
     #[must_use]
     pub fn new_random_bipartite(n: i32, m: i32) -> Self {
+        Self::new_random_bipartite_with_rng(n, m, &mut rand::rng())
+    }
+
+    /// Same as `new_random_bipartite`, but the graph is generated with `rng`.
+    #[must_use]
+    pub fn new_random_bipartite_with_rng<R: Rng>(n: i32, m: i32, rng: &mut R) -> Self {
         let mut result = Self::new_empty(n);
-        let mut rng = rand::rng();
         let mut nodes = (0..n).collect::<Vec<_>>();
-        nodes.shuffle(&mut rng);
+        nodes.shuffle(rng);
         let size1 = loop {
             let size1 = rng.random_range(1..n);
             if size1 * (n - size1) >= m {
@@ -266,6 +296,17 @@ impl ToOutput for Graph {
     /// The nodes are 1-indexed.
     /// The edges will be randomly shuffled and pair may be swapped.
     fn to_output(self) -> String {
+        self.write_edges(&mut rand::rng())
+    }
+
+    /// Same as `to_output`, but the edges are shuffled and swapped with `rng`.
+    fn to_output_with_rng(self, rng: &mut GeneratorRng) -> String {
+        self.write_edges(rng)
+    }
+}
+
+impl Graph {
+    fn write_edges<R: Rng>(self, rng: &mut R) -> String {
         if self.is_tree {
             assert!(self.is_tree());
         }
@@ -276,8 +317,9 @@ impl ToOutput for Graph {
             result += &format!("{} {}\n", self.get_num_nodes(), self.get_num_edges());
         }
         let mut edges = self.edges_iter().collect::<Vec<_>>();
-        let mut rng = rand::rng();
-        edges.shuffle(&mut rng);
+        // edges are sorted first, because the iteration order of a HashSet differs between runs
+        edges.sort_unstable();
+        edges.shuffle(rng);
         for (u, v) in edges {
             if rng.random_bool(0.5) {
                 writeln!(result, "{} {}", u + 1, v + 1).ok();
@@ -1,5 +1,5 @@
 mod array;
 mod graph;
 
-pub use array::{array_generator, array_generator_custom, array_to_string};
+pub use array::{array_generator, array_generator_custom, array_generator_custom_with_rng, array_generator_with_rng, array_to_string};
 pub use graph::Graph;
@@ -0,0 +1,144 @@
+use crate::partial_solution::round_points;
+use crate::report::{SolutionReport, TaskReport, TestReport, Verdict};
+use crate::task::{file_name, path_str};
+use std::path::Path;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 20px; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 2px 8px; text-align: center; white-space: nowrap; }
+th.test, td.test { text-align: left; }
+tr.subtask th { background: #e8e8e8; }
+tr.total th { background: #d0d0d0; }
+td.ok { background: #c8f0c8; }
+td.partial { background: #f8f0a8; }
+td.failed { background: #f4b8b8; }
+small { color: #555; }
+";
+
+impl TaskReport {
+    /// Returns the slowest run with the `OK` verdict of the main solution and the partial solutions with full points, with the name of its solution.
+    /// Other partial solutions are skipped, they are often meant to be too slow.
+    #[must_use]
+    pub fn slowest_correct_run(&self) -> Option<(&str, &TestReport)> {
+        self.solutions
+            .iter()
+            .enumerate()
+            .filter(|(solution_idx, solution)| *solution_idx == 0 || solution.points >= f64::from(solution.max_points))
+            .map(|(_, solution)| solution)
+            .flat_map(|solution| solution.subtasks.iter().flat_map(|subtask| &subtask.tests).map(move |test| (solution.name.as_str(), test)))
+            .filter(|(_, test)| test.verdict == Verdict::Ok)
+            .max_by_key(|(_, test)| test.time)
+    }
+
+    /// Returns the report as a self-contained HTML page with a matrix of the verdicts and times of all solutions on all tests.
+    /// Links to the test files are relative to `base_path`, the directory where the page is saved.
+    #[must_use]
+    pub fn to_html(&self, base_path: &Path) -> String {
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+            html_escape(&self.name)
+        );
+        html += &format!(
+            "<p>Seed: {}<br>Time limit: {} ms<br>Memory limit: {} MB<br>Tests size: {:.2} MB</p>\n",
+            self.seed,
+            self.time_limit,
+            self.memory_limit,
+            self.tests_size as f64 / 1_000_000.0
+        );
+        if let Some((solution_name, test)) = self.slowest_correct_run()
+            && let Some(time) = test.time
+            && self.time_limit > 0
+        {
+            html += &format!(
+                "<p>Slowest correct run: {time} ms ({} on {}), {} ms below the time limit ({:.1}% of it)</p>\n",
+                html_escape(solution_name),
+                html_escape(&file_name(&test.input_file)),
+                self.time_limit - time,
+                f64::from(time) * 100.0 / f64::from(self.time_limit)
+            );
+        }
+
+        html += "<table>\n<tr><th class=\"test\">Test</th>";
+        for solution in &self.solutions {
+            html += &format!("<th>{}</th>", html_escape(&solution.name));
+        }
+        html += "</tr>\n";
+
+        for (subtask_idx, subtask) in self.subtasks.iter().enumerate() {
+            html += &format!("<tr class=\"subtask\"><th class=\"test\">Subtask {}: {}</th>", subtask_idx + 1, html_escape(&subtask.name));
+            for solution in &self.solutions {
+                let subtask_report = &solution.subtasks[subtask_idx];
+                html += &format!("<th>{}/{}</th>", round_points(subtask_report.points), subtask_report.max_points);
+            }
+            html += "</tr>\n";
+
+            for (test_idx, test) in subtask.tests.iter().enumerate() {
+                html += &format!(
+                    "<tr><td class=\"test\"><a href=\"{}\">{}</a> <a href=\"{}\"><small>out</small></a></td>",
+                    html_escape(&link(&test.input_file, base_path)),
+                    html_escape(&file_name(&test.input_file)),
+                    html_escape(&link(&test.output_file, base_path))
+                );
+                for solution in &self.solutions {
+                    html += &test_cell(solution, subtask_idx, test_idx);
+                }
+                html += "</tr>\n";
+            }
+        }
+
+        html += "<tr class=\"total\"><th class=\"test\">Total</th>";
+        for solution in &self.solutions {
+            html += &format!("<th>{}/{}</th>", round_points(solution.points), solution.max_points);
+        }
+        html += "</tr>\n</table>\n";
+        html += &self.robust_tests_html();
+        html += "</body>\n</html>\n";
+        html
+    }
+
+    /// Table with the number of robust tests of every subtask and the hits of its generators.
+    fn robust_tests_html(&self) -> String {
+        let mut html = "<h2>Robust tests</h2>\n<table>\n<tr><th class=\"test\">Subtask</th><th>Found</th><th>Tries</th><th class=\"test\">Generators (robust tests / tries)</th></tr>\n".to_owned();
+        for (subtask_idx, subtask) in self.subtasks.iter().enumerate() {
+            html += &format!("<tr><td class=\"test\">Subtask {}: {}</td>", subtask_idx + 1, html_escape(&subtask.name));
+            if let Some(robust) = &subtask.robust {
+                let generators: Vec<String> = robust
+                    .generators
+                    .iter()
+                    .enumerate()
+                    .map(|(gen_idx, generator)| format!("{}: {}/{}", gen_idx + 1, generator.hits, generator.tries))
+                    .collect();
+                html += &format!("<td>{}</td><td>{}</td><td class=\"test\">{}</td>", robust.found, robust.tries, generators.join(", "));
+            } else {
+                html += "<td colspan=\"3\">cached</td>";
+            }
+            html += "</tr>\n";
+        }
+        html += "</table>\n";
+        html
+    }
+}
+
+/// Cell of the matrix with the verdict and the time of a solution on a test.
+fn test_cell(solution: &SolutionReport, subtask_idx: usize, test_idx: usize) -> String {
+    let Some(test) = solution.subtasks[subtask_idx].tests.get(test_idx) else {
+        return "<td></td>".to_owned();
+    };
+    let class = match test.verdict {
+        Verdict::Ok => "ok",
+        Verdict::PartiallyCorrect => "partial",
+        _ => "failed",
+    };
+    let time = test.time.map_or_else(String::new, |time| format!(" <small>{time} ms</small>"));
+    format!("<td class=\"{class}\" title=\"{}\">{}{time}</td>", html_escape(&test.message), test.verdict.short_name())
+}
+
+/// Path of a file relative to the directory of the page, or the whole path if it is not inside of it.
+fn link(path: &Path, base_path: &Path) -> String {
+    path_str(path.strip_prefix(base_path).unwrap_or(path)).replace('\\', "/")
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
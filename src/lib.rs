@@ -1,20 +1,36 @@
+// lets `derive(ToOutput)` refer to `::ezcp` inside this crate as well
+extern crate self as ezcp;
+
 mod archiver;
+mod bandit;
+pub mod cli;
 mod create_tests;
 mod error;
+mod export;
 mod generators;
+mod html_report;
 mod logger_format;
+mod params;
 mod partial_solution;
+mod report;
 mod runner;
+mod shrink;
 mod solution;
 mod subtask;
 mod task;
 mod test;
+mod test_cache;
 mod tests;
 mod to_output;
 
 pub use error::{Error, Result};
-pub use generators::{Graph, array_generator, array_generator_custom, array_to_string};
+pub use export::ExportFormat;
+pub use generators::{Graph, array_generator, array_generator_custom, array_generator_custom_with_rng, array_generator_with_rng, array_to_string};
+pub use params::{ParamSpace, Params};
+pub use report::{GeneratedTest, GeneratorStats, RobustTestStats, SolutionReport, SubtaskReport, SubtaskTestsReport, TaskReport, TestReport, Verdict};
+pub use runner::language::Language;
 pub use solution::Solution;
-pub use subtask::Subtask;
+pub use subtask::{ScoreAggregation, Subtask};
 pub use task::Task;
+pub use test::GeneratorRng;
 pub use to_output::ToOutput;
@@ -0,0 +1,121 @@
+use crate::test::GeneratorRng;
+use crate::{Error, Result};
+use rand::RngExt;
+use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// Share of the robust test search that changes the parameters of the last robust test of a parameterized generator,
+/// instead of picking them from the whole space.
+pub const MUTATION_RATE: f64 = 0.5;
+
+/// Named integer ranges of the parameters of a parameterized generator, see `Subtask::with_param_test`.
+///
+/// Parameters that are not integers can be scaled, for example a density in percent.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ParamSpace {
+    ranges: Vec<(String, RangeInclusive<i64>)>,
+}
+
+impl ParamSpace {
+    /// Creates a space without parameters.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Adds a parameter with the range of its values.
+    /// The name can not be empty and can not contain whitespace or `=`, because parameters are written as `name=value`.
+    pub fn with_param(mut self, name: &str, range: RangeInclusive<i64>) -> Result<Self> {
+        let reason = if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '=') {
+            Some("the name can not be empty or contain whitespace or '='")
+        } else if range.is_empty() {
+            Some("the range is empty")
+        } else if self.ranges.iter().any(|(other, _)| other == name) {
+            Some("it is declared twice")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(Error::InvalidParameter { name: name.to_owned(), reason });
+        }
+        self.ranges.push((name.to_owned(), range));
+        Ok(self)
+    }
+
+    /// Picks every parameter uniformly from its range.
+    pub(crate) fn sample(&self, rng: &mut GeneratorRng) -> Params {
+        Params {
+            values: self.ranges.iter().map(|(name, range)| (name.clone(), rng.random_range(range.clone()))).collect(),
+        }
+    }
+
+    /// Moves one random parameter by up to a tenth of its range, or picks it again from the whole range.
+    /// Repeated mutations of parameters that broke a bad solution search around them (hill climbing).
+    pub(crate) fn mutate(&self, params: &Params, rng: &mut GeneratorRng) -> Params {
+        let mut params = params.clone();
+        if self.ranges.is_empty() {
+            return params;
+        }
+        let param_idx = rng.random_range(0..self.ranges.len());
+        let range = &self.ranges[param_idx].1;
+        let value = &mut params.values[param_idx].1;
+        *value = if rng.random_bool(0.2) {
+            rng.random_range(range.clone())
+        } else {
+            let step = i64::try_from(range.end().abs_diff(*range.start()) / 10).unwrap_or(i64::MAX).max(1);
+            value.saturating_add(rng.random_range(-step..=step)).clamp(*range.start(), *range.end())
+        };
+        params
+    }
+
+    /// Whether the values are the parameters of this space and within their ranges.
+    pub(crate) fn contains(&self, params: &Params) -> bool {
+        self.ranges.len() == params.values.len() && self.ranges.iter().zip(&params.values).all(|((name, range), (other, value))| name == other && range.contains(value))
+    }
+}
+
+/// Values of the parameters of a generated test, passed to the closure of `Subtask::with_param_test`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Params {
+    values: Vec<(String, i64)>,
+}
+
+impl Params {
+    /// Returns the value of a parameter, or an error if the parameter is not declared in the space of the generator.
+    pub fn get(&self, name: &str) -> Result<i64> {
+        self.values
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|&(_, value)| value)
+            .ok_or_else(|| Error::UnknownParameter { name: name.to_owned() })
+    }
+
+    /// Names and values of the parameters, in the order they were declared in.
+    #[must_use]
+    pub fn values(&self) -> &[(String, i64)] {
+        &self.values
+    }
+
+    /// Whether the generator has no parameters.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Parses parameters written with `Display`.
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        let values = text
+            .split_whitespace()
+            .map(|param| param.split_once('=').and_then(|(name, value)| Some((name.to_owned(), value.parse().ok()?))))
+            .collect::<Option<_>>()?;
+        Some(Self { values })
+    }
+}
+
+/// Writes the parameters as `name=value` separated by spaces.
+impl Display for Params {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let params: Vec<String> = self.values.iter().map(|(name, value)| format!("{name}={value}")).collect();
+        write!(f, "{}", params.join(" "))
+    }
+}
@@ -1,49 +1,112 @@
+use crate::report::{SolutionReport, SubtaskReport, TestReport, Verdict};
 use crate::runner::cpp_runner::{CppRunner, ProgramHandle};
 use crate::runner::exec_runner::RunResult;
+use crate::task::path_str;
 use crate::{Error, Result, Task, ToOutput};
-use console::style;
-use std::collections::{BTreeMap, HashSet};
-use std::fmt::Display;
+use log::debug;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
-enum TestResult {
-    Ok = 0,
-    TimedOut = 1,
-    Crashed = 2,
-    WrongAnswer = 3,
-}
-
-impl Display for TestResult {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let val = match self {
-            Self::Ok => style("OK").green().bright().bold(),
-            Self::TimedOut => style("TLE").red().bright().bold(),
-            Self::Crashed => style("RTE").red().bright().bold(),
-            Self::WrongAnswer => style("WA").red().bright().bold(),
-        };
-        write!(f, "{val}")
+impl<T: ToOutput> Task<T> {
+    /// This function takes an executable file and a list of test files.
+    /// It runs the executable on each test file, compares the output with the expected output and logs the results.
+    ///
+    /// * `solution_idx` - Index of the partial solution whose expected scores are verified, `None` for the main solution.
+    /// * `reports` - The report of the solution is added to them, also if it fails the expectations.
+    pub(crate) fn run_partial_solution(
+        &self,
+        test_files: &[Vec<(PathBuf, PathBuf)>],
+        cpp_runner: &mut CppRunner,
+        program_handle: ProgramHandle,
+        solution_idx: Option<usize>,
+        lines_of_code: usize,
+        time_limit: i32,
+        reports: &mut Vec<SolutionReport>,
+    ) -> Result<()> {
+        let name = solution_idx.map_or_else(|| "main".to_owned(), |solution_idx| self.solutions[solution_idx].name.clone());
+        reports.push(self.judge_program(&name, test_files, cpp_runner, program_handle, time_limit)?);
+        self.log_solution_report(&reports[reports.len() - 1], solution_idx, lines_of_code, time_limit)
     }
-}
 
-impl TestResult {
-    pub const fn from(result: &RunResult) -> Self {
-        match result {
-            RunResult::Ok(_, _) => Self::Ok,
-            RunResult::TimedOut => Self::TimedOut,
-            RunResult::Crashed => Self::Crashed,
+    /// Logs the points and the results of every subtask of a solution judged with `time_limit`.
+    /// For a partial solution (`solution_idx`), it also checks that the results are the expected ones.
+    pub(crate) fn log_solution_report(&self, report: &SolutionReport, solution_idx: Option<usize>, lines_of_code: usize, time_limit: i32) -> Result<()> {
+        let mut results_text = String::new();
+        for (subtask_id, subtask_report) in report.subtasks.iter().enumerate() {
+            results_text += "\n";
+            results_text += &format!("- Subtask {}: ", subtask_id + 1);
+            for (result, count) in &subtask_report.verdicts {
+                results_text += &format!("{result} ({count}) ");
+            }
+
+            if let Some(max_time) = subtask_report.max_time {
+                results_text += &format!("{max_time}ms ");
+            }
+
+            if let Some(max_memory) = subtask_report.max_memory {
+                results_text += &format!("{:.1}MB", max_memory as f32 / 1024.0);
+            }
+
+            if subtask_report.score > 0.0 && subtask_report.score < 1.0 {
+                results_text += &format!(" {}/{} points", round_points(subtask_report.points), subtask_report.max_points);
+            }
+
+            if let Some(solution_idx) = solution_idx
+                && let Some(test_report) = subtask_report.tests.iter().find(|test| !self.solutions[solution_idx].is_expected_verdict(subtask_id, test.verdict))
+            {
+                self.log_result(&format!("Results: {results_text}"))?;
+                return Err(Error::PartialSolutionUnexpectedVerdict {
+                    subtask_number: subtask_id + 1,
+                    partial_number: solution_idx + 1,
+                    partial_name: self.solutions[solution_idx].name.clone(),
+                    subtask_name: subtask_report.name.clone(),
+                    verdict: test_report.verdict,
+                    expected: self.solutions[solution_idx].expected_verdicts_text(subtask_id),
+                    test: path_str(&test_report.input_file),
+                });
+            }
+
+            if let Some(solution_idx) = solution_idx
+                && let Some(expected) = self.solutions[solution_idx].expected_scores.get(&subtask_id)
+                && !expected.contains(&subtask_report.score)
+            {
+                self.log_result(&format!("Results: {results_text}"))?;
+                return Err(Error::PartialSolutionScoreOutOfRange {
+                    subtask_number: subtask_id + 1,
+                    partial_number: solution_idx + 1,
+                    partial_name: self.solutions[solution_idx].name.clone(),
+                    subtask_name: subtask_report.name.clone(),
+                    score: subtask_report.score,
+                    expected: format!("{}..={}", expected.start(), expected.end()),
+                });
+            }
+
+            // the robust tests were searched for with a time limit that can be lower than the calibrated one
+            if let Some(solution_idx) = solution_idx
+                && self.auto_time_limit.is_some()
+                && self.solutions[solution_idx].should_fail(subtask_id)
+                && subtask_report.passed()
+            {
+                self.log_result(&format!("Results: {results_text}"))?;
+                return Err(Error::PartialSolutionPassesSubtask {
+                    subtask_number: subtask_id + 1,
+                    partial_number: solution_idx + 1,
+                    partial_name: self.solutions[solution_idx].name.clone(),
+                    subtask_name: subtask_report.name.clone(),
+                    time_limit,
+                });
+            }
         }
+
+        self.log_result(&format!("Points {}/{}", round_points(report.points), report.max_points))?;
+        self.log_result(&format!("Lines of code: {lines_of_code}"))?;
+        self.log_result(&format!("Results: {results_text}"))
     }
-}
 
-impl<T: ToOutput> Task<T> {
-    /// This function takes an executable file and a list of test files.
-    /// It runs the executable on each test file and compares the output with the expected output.
-    /// It returns a set of subtasks that passed.
-    pub(crate) fn run_partial_solution(&self, test_files: &Vec<Vec<(PathBuf, PathBuf)>>, cpp_runner: &mut CppRunner, program_handle: ProgramHandle, lines_of_code: usize) -> Result<HashSet<usize>> {
+    /// Runs a program on the tests of every subtask with the given time limit and judges its outputs with the checker of the task.
+    pub(crate) fn judge_program(&self, name: &str, test_files: &[Vec<(PathBuf, PathBuf)>], cpp_runner: &mut CppRunner, program_handle: ProgramHandle, time_limit: i32) -> Result<SolutionReport> {
         cpp_runner.clear_tasks();
         let mut test_handles = Vec::new();
-        let mut passed_subtasks = HashSet::new();
 
         for subtask_tests in test_files {
             let mut test_handles_element = Vec::new();
@@ -52,7 +115,7 @@ impl<T: ToOutput> Task<T> {
                     err,
                     file: input_file.to_str().unwrap_or("???").to_owned(),
                 })?;
-                let handle = cpp_runner.add_task(program_handle, input_data, self.time_limit);
+                let handle = cpp_runner.add_task(program_handle, input_data, time_limit, self.memory_limit);
 
                 test_handles_element.push((handle, input_file.clone(), output_file.clone()));
             }
@@ -61,14 +124,9 @@ impl<T: ToOutput> Task<T> {
 
         cpp_runner.run_tasks(Some(&self.logger), false)?;
 
-        let mut got_points = 0;
-        let mut total_points = 0;
-
-        let mut results_text = String::new();
-        for (subtask_id, subtask_test_handles) in test_handles.iter().enumerate() {
-            let mut max_time = Some(0);
-            // count, which result was returned by how many tests
-            let mut results = BTreeMap::new();
+        let mut test_reports = Vec::new();
+        for subtask_test_handles in &test_handles {
+            let mut subtask_test_reports = Vec::new();
             for (handle, input_file, output_file) in subtask_test_handles {
                 let input_data = std::fs::read_to_string(input_file).map_err(|err| Error::IOError {
                     err,
@@ -76,53 +134,87 @@ impl<T: ToOutput> Task<T> {
                 })?;
 
                 let run_result = cpp_runner.get_result(*handle);
-                let mut test_result = TestResult::from(&run_result);
-
-                match run_result {
-                    RunResult::Ok(time, program_output) => {
-                        if max_time.is_some() {
-                            max_time = Some(i32::max(max_time.unwrap(), time));
-                        }
-
-                        let correct_output = std::fs::read_to_string(output_file).map_err(|err| Error::IOError {
-                            err,
-                            file: output_file.to_str().unwrap_or("???").to_owned(),
-                        })?;
-                        if !(self.checker)(&input_data, &correct_output, &program_output) {
-                            test_result = TestResult::WrongAnswer;
-                        }
-                    }
-                    RunResult::TimedOut | RunResult::Crashed => {
-                        max_time = None;
+                let mut test_report = TestReport {
+                    input_file: input_file.clone(),
+                    output_file: output_file.clone(),
+                    verdict: Verdict::from(&run_result),
+                    score: 0.0,
+                    time: None,
+                    memory: None,
+                    message: String::new(),
+                    generator: None,
+                };
+
+                if let RunResult::Ok(time, memory, program_output) = run_result {
+                    test_report.time = Some(time);
+                    test_report.memory = Some(memory);
+
+                    let correct_output = std::fs::read_to_string(output_file).map_err(|err| Error::IOError {
+                        err,
+                        file: output_file.to_str().unwrap_or("???").to_owned(),
+                    })?;
+                    let verdict = self.check_output(cpp_runner, &input_data, &correct_output, &program_output)?;
+                    if !verdict.is_accepted() {
+                        debug!("{} on {}: {}", verdict.to_display_string(), path_str(input_file), verdict.message());
                     }
+                    test_report.score = verdict.score();
+                    verdict.message().clone_into(&mut test_report.message);
+                    test_report.verdict = Verdict::from_checker(&verdict);
                 }
-
-                // increment the count for the result
-                // keys are strings, because enum has time in the Ok variant
-                results.entry(test_result).and_modify(|count| *count += 1).or_insert(1);
+                subtask_test_reports.push(test_report);
             }
+            test_reports.push(subtask_test_reports);
+        }
 
-            results_text += "\n";
-            results_text += &format!("- Subtask {}: ", subtask_id + 1);
-            for (result, count) in &results {
-                results_text += &format!("{result} ({count}) ");
-            }
+        let mut report = SolutionReport {
+            name: name.to_owned(),
+            points: 0.0,
+            max_points: 0,
+            subtasks: Vec::new(),
+        };
+        for (subtask_id, subtask) in self.subtasks.iter().enumerate() {
+            let mut max_time = Some(0);
+            let mut max_memory = Some(0);
+            // count, which result was returned by how many tests
+            let mut verdicts = BTreeMap::new();
+            let mut scores = Vec::new();
+            // tests of included subtasks count as tests of this subtask
+            for included_id in self.included_subtasks(subtask_id) {
+                for test_report in &test_reports[included_id] {
+                    if let (Some(time), Some(memory)) = (test_report.time, test_report.memory) {
+                        max_time = max_time.map(|max_time| i32::max(max_time, time));
+                        max_memory = max_memory.map(|max_memory| i32::max(max_memory, memory));
+                    } else {
+                        max_time = None;
+                        max_memory = None;
+                    }
 
-            if let Some(max_time) = max_time {
-                results_text += &format!("{max_time}ms");
+                    // increment the count for the result
+                    verdicts.entry(test_report.verdict).and_modify(|count| *count += 1).or_insert(1);
+                    scores.push(test_report.score);
+                }
             }
 
-            if results.len() == 1 && results.contains_key(&TestResult::Ok) {
-                passed_subtasks.insert(subtask_id);
-                got_points += self.subtasks[subtask_id].points;
-            }
-            total_points += self.subtasks[subtask_id].points;
+            let score = subtask.aggregation.aggregate(&scores);
+            let points = score * f64::from(subtask.points);
+            report.points += points;
+            report.max_points += subtask.points;
+            report.subtasks.push(SubtaskReport {
+                name: subtask.name.clone(),
+                score,
+                points,
+                max_points: subtask.points,
+                verdicts,
+                max_time,
+                max_memory,
+                tests: test_reports[subtask_id].clone(),
+            });
         }
-
-        self.log_result(&format!("Points {got_points}/{total_points}"))?;
-        self.log_result(&format!("Lines of code: {lines_of_code}"))?;
-        self.log_result(&format!("Results: {results_text}"))?;
-
-        Ok(passed_subtasks)
+        Ok(report)
     }
 }
+
+/// Rounds points to two decimals, so whole points are printed without decimals.
+pub fn round_points(points: f64) -> f64 {
+    (points * 100.0).round() / 100.0
+}
@@ -0,0 +1,360 @@
+use crate::params::Params;
+use crate::runner::checker::CheckerVerdict;
+use crate::runner::exec_runner::RunResult;
+use crate::task::path_str;
+use console::style;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+/// Verdict of a solution on a test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Verdict {
+    Ok = 0,
+    TimedOut = 1,
+    MemoryLimitExceeded = 2,
+    Crashed = 3,
+    WrongAnswer = 4,
+    PresentationError = 5,
+    PartiallyCorrect = 6,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let val = match self {
+            Self::Ok => style(self.short_name()).green().bright().bold(),
+            Self::PartiallyCorrect => style(self.short_name()).yellow().bright().bold(),
+            _ => style(self.short_name()).red().bright().bold(),
+        };
+        write!(f, "{val}")
+    }
+}
+
+impl Verdict {
+    pub(crate) const fn from(result: &RunResult) -> Self {
+        match result {
+            RunResult::Ok(_, _, _) => Self::Ok,
+            RunResult::TimedOut => Self::TimedOut,
+            RunResult::MemoryLimitExceeded => Self::MemoryLimitExceeded,
+            RunResult::Crashed => Self::Crashed,
+            RunResult::WrongAnswer => Self::WrongAnswer,
+        }
+    }
+
+    pub(crate) const fn from_checker(verdict: &CheckerVerdict) -> Self {
+        match verdict {
+            CheckerVerdict::Accepted => Self::Ok,
+            CheckerVerdict::WrongAnswer(_) => Self::WrongAnswer,
+            CheckerVerdict::PresentationError(_) => Self::PresentationError,
+            CheckerVerdict::PartiallyCorrect(_, _) => Self::PartiallyCorrect,
+        }
+    }
+
+    /// Abbreviation of the verdict, like `OK`, `TLE` or `WA`.
+    #[must_use]
+    pub const fn short_name(self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::TimedOut => "TLE",
+            Self::MemoryLimitExceeded => "MLE",
+            Self::Crashed => "RTE",
+            Self::WrongAnswer => "WA",
+            Self::PresentationError => "PE",
+            Self::PartiallyCorrect => "PC",
+        }
+    }
+}
+
+/// Result of a solution on one test.
+#[derive(Clone, Debug)]
+pub struct TestReport {
+    pub input_file: PathBuf,
+    pub output_file: PathBuf,
+    pub verdict: Verdict,
+    /// Score of the test as a fraction in `[0, 1]`
+    pub score: f64,
+    /// Running time in milliseconds, `None` if the solution did not finish
+    pub time: Option<i32>,
+    /// Peak memory in kilobytes, `None` if the solution did not finish
+    pub memory: Option<i32>,
+    /// Message of the checker
+    pub message: String,
+    /// Index of the generator of the test in its subtask, `None` for hand-written tests or if it is not known
+    pub generator: Option<usize>,
+}
+
+/// Result of a solution on a subtask. The score counts the tests of the included subtasks
+/// (`Subtask::with_dependency`), but `tests` holds only the tests of the subtask itself.
+#[derive(Clone, Debug)]
+pub struct SubtaskReport {
+    pub name: String,
+    /// Score of the subtask as a fraction of its points
+    pub score: f64,
+    pub points: f64,
+    pub max_points: i32,
+    /// How many tests got each verdict
+    pub verdicts: BTreeMap<Verdict, usize>,
+    /// Longest running time in milliseconds, `None` if the solution did not finish on some test
+    pub max_time: Option<i32>,
+    /// Largest peak memory in kilobytes, `None` if the solution did not finish on some test
+    pub max_memory: Option<i32>,
+    pub tests: Vec<TestReport>,
+}
+
+impl SubtaskReport {
+    /// Returns `true` if the solution got `OK` on every test.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.verdicts.keys().all(|&verdict| verdict == Verdict::Ok)
+    }
+}
+
+/// Result of a solution on all tests of the task.
+#[derive(Clone, Debug)]
+pub struct SolutionReport {
+    pub name: String,
+    pub points: f64,
+    pub max_points: i32,
+    pub subtasks: Vec<SubtaskReport>,
+}
+
+/// Statistics of the search for robust tests of a subtask, the tests that break the bad solutions.
+#[derive(Clone, Debug)]
+pub struct RobustTestStats {
+    /// Number of robust tests that were found
+    pub found: usize,
+    /// Number of robust tests that every bad solution has to fail
+    pub target: usize,
+    /// Number of random tests that were tried
+    pub tries: usize,
+    /// Name of every bad solution with the number of robust tests it fails
+    pub failures: Vec<(String, usize)>,
+    /// How often every generator was tried and how often it gave a robust test
+    pub generators: Vec<GeneratorStats>,
+}
+
+/// Tries of a generator in the search for robust tests.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GeneratorStats {
+    /// Number of tests of the generator that were tried
+    pub tries: usize,
+    /// Number of tests that were taken, because they broke a bad solution that still needed failures
+    pub hits: usize,
+}
+
+impl GeneratorStats {
+    /// Share of the tries that were taken as robust tests, 0 if the generator was not tried.
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        if self.tries == 0 { 0.0 } else { self.hits as f64 / self.tries as f64 }
+    }
+}
+
+impl RobustTestStats {
+    /// Returns `true` if every bad solution fails on enough robust tests.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failures.iter().all(|&(_, count)| count >= self.target)
+    }
+}
+
+/// A saved test and where it comes from.
+#[derive(Clone, Debug)]
+pub struct GeneratedTest {
+    pub input_file: PathBuf,
+    pub output_file: PathBuf,
+    /// Index of the generator in the subtask, `None` for hand-written tests
+    pub generator: Option<usize>,
+    /// Seed of the generator, `None` for hand-written tests
+    pub seed: Option<u64>,
+    /// Values of the parameters of a parameterized generator, empty for other tests
+    pub params: Params,
+    /// Original file of a hand-written test
+    pub source_file: Option<PathBuf>,
+    /// Size of the input and output in bytes
+    pub size: u64,
+}
+
+/// Tests that were created for a subtask.
+#[derive(Clone, Debug)]
+pub struct SubtaskTestsReport {
+    pub name: String,
+    pub tests: Vec<GeneratedTest>,
+    /// Size of all inputs and outputs in bytes
+    pub size: u64,
+    /// `None` if the tests were taken from the test cache
+    pub robust: Option<RobustTestStats>,
+}
+
+/// Report of a run of the task: the created tests and the results of every solution on them.
+/// It is also written into `report.json` and `report.html` next to `results.txt`.
+#[derive(Clone, Debug)]
+pub struct TaskReport {
+    pub name: String,
+    pub seed: u64,
+    /// Time limit in milliseconds
+    pub time_limit: i32,
+    /// Memory limit in megabytes
+    pub memory_limit: i32,
+    /// Number of sample tests
+    pub samples: usize,
+    /// Size of all test files in bytes
+    pub tests_size: u64,
+    pub subtasks: Vec<SubtaskTestsReport>,
+    /// The main solution first, then the partial solutions
+    pub solutions: Vec<SolutionReport>,
+}
+
+impl TaskReport {
+    /// Sets the generator of every test in the solution reports from the created tests.
+    pub(crate) fn set_test_generators(&mut self) {
+        let generators: HashMap<&PathBuf, Option<usize>> = self.subtasks.iter().flat_map(|subtask| &subtask.tests).map(|test| (&test.input_file, test.generator)).collect();
+        for solution in &mut self.solutions {
+            for test in solution.subtasks.iter_mut().flat_map(|subtask| &mut subtask.tests) {
+                test.generator = generators.get(&test.input_file).copied().flatten();
+            }
+        }
+    }
+
+    /// Returns the report as a JSON document.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let subtasks: Vec<String> = self.subtasks.iter().map(SubtaskTestsReport::to_json).collect();
+        let solutions: Vec<String> = self.solutions.iter().map(SolutionReport::to_json).collect();
+        format!(
+            "{{\n  \"name\": {},\n  \"seed\": {},\n  \"time_limit\": {},\n  \"memory_limit\": {},\n  \"samples\": {},\n  \"tests_size\": {},\n  \"subtasks\": [\n    {}\n  ],\n  \"solutions\": [\n    {}\n  ]\n}}\n",
+            json_string(&self.name),
+            self.seed,
+            self.time_limit,
+            self.memory_limit,
+            self.samples,
+            self.tests_size,
+            subtasks.join(",\n    "),
+            solutions.join(",\n    "),
+        )
+    }
+}
+
+impl SubtaskTestsReport {
+    fn to_json(&self) -> String {
+        let tests: Vec<String> = self
+            .tests
+            .iter()
+            .map(|test| {
+                format!(
+                    "{{\"input_file\": {}, \"output_file\": {}, \"generator\": {}, \"seed\": {}, \"params\": {{{}}}, \"source_file\": {}, \"size\": {}}}",
+                    json_path(&test.input_file),
+                    json_path(&test.output_file),
+                    json_option(test.generator),
+                    json_option(test.seed),
+                    test.params
+                        .values()
+                        .iter()
+                        .map(|(name, value)| format!("{}: {value}", json_string(name)))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    test.source_file.as_deref().map_or_else(|| "null".to_owned(), json_path),
+                    test.size,
+                )
+            })
+            .collect();
+        let robust = self.robust.as_ref().map_or_else(
+            || "null".to_owned(),
+            |robust| {
+                let failures: Vec<String> = robust.failures.iter().map(|(name, count)| format!("{}: {count}", json_string(name))).collect();
+                let generators: Vec<String> = robust
+                    .generators
+                    .iter()
+                    .map(|generator| format!("{{\"tries\": {}, \"hits\": {}, \"hit_rate\": {}}}", generator.tries, generator.hits, generator.hit_rate()))
+                    .collect();
+                format!(
+                    "{{\"found\": {}, \"target\": {}, \"tries\": {}, \"failures\": {{{}}}, \"generators\": [{}]}}",
+                    robust.found,
+                    robust.target,
+                    robust.tries,
+                    failures.join(", "),
+                    generators.join(", ")
+                )
+            },
+        );
+        format!(
+            "{{\"name\": {}, \"num_tests\": {}, \"size\": {}, \"robust\": {robust}, \"tests\": [{}]}}",
+            json_string(&self.name),
+            self.tests.len(),
+            self.size,
+            tests.join(", ")
+        )
+    }
+}
+
+impl SolutionReport {
+    fn to_json(&self) -> String {
+        let subtasks: Vec<String> = self.subtasks.iter().map(SubtaskReport::to_json).collect();
+        format!(
+            "{{\"name\": {}, \"points\": {}, \"max_points\": {}, \"subtasks\": [{}]}}",
+            json_string(&self.name),
+            self.points,
+            self.max_points,
+            subtasks.join(", ")
+        )
+    }
+}
+
+impl SubtaskReport {
+    fn to_json(&self) -> String {
+        let verdicts: Vec<String> = self.verdicts.iter().map(|(verdict, count)| format!("{}: {count}", json_string(verdict.short_name()))).collect();
+        let tests: Vec<String> = self.tests.iter().map(TestReport::to_json).collect();
+        format!(
+            "{{\"name\": {}, \"score\": {}, \"points\": {}, \"max_points\": {}, \"verdicts\": {{{}}}, \"max_time\": {}, \"max_memory\": {}, \"tests\": [{}]}}",
+            json_string(&self.name),
+            self.score,
+            self.points,
+            self.max_points,
+            verdicts.join(", "),
+            json_option(self.max_time),
+            json_option(self.max_memory),
+            tests.join(", ")
+        )
+    }
+}
+
+impl TestReport {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"input_file\": {}, \"verdict\": {}, \"score\": {}, \"time\": {}, \"memory\": {}, \"message\": {}, \"generator\": {}}}",
+            json_path(&self.input_file),
+            json_string(self.verdict.short_name()),
+            self.score,
+            json_option(self.time),
+            json_option(self.memory),
+            json_string(&self.message),
+            json_option(self.generator),
+        )
+    }
+}
+
+/// Quotes and escapes a string for JSON.
+fn json_string(text: &str) -> String {
+    let mut result = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            '\r' => result += "\\r",
+            '\t' => result += "\\t",
+            c if (c as u32) < 0x20 => result += &format!("\\u{:04x}", c as u32),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn json_path(path: &Path) -> String {
+    json_string(&path_str(path))
+}
+
+fn json_option<V: Display>(value: Option<V>) -> String {
+    value.map_or_else(|| "null".to_owned(), |value| value.to_string())
+}
@@ -0,0 +1,167 @@
+use crate::{Error, Result};
+use log::trace;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+use tempfile::NamedTempFile;
+
+/// Exit codes of testlib checkers.
+const EXIT_OK: i32 = 0;
+const EXIT_WRONG_ANSWER: i32 = 1;
+const EXIT_PRESENTATION_ERROR: i32 = 2;
+const EXIT_POINTS: i32 = 7;
+
+/// Time after which a checker that has not finished is killed and counted as failed.
+pub const CHECKER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The verdict of a checker on one program output.
+#[derive(Clone, PartialEq, Debug)]
+pub enum CheckerVerdict {
+    /// The output is correct.
+    Accepted,
+    /// The output is wrong, with the message of the checker.
+    WrongAnswer(String),
+    /// The output is malformed, with the message of the checker.
+    PresentationError(String),
+    /// The output gets a fraction of the points (strictly between 0 and 1), with the message of the checker.
+    PartiallyCorrect(f64, String),
+}
+
+impl CheckerVerdict {
+    /// Creates a verdict from a score in `[0, 1]`.
+    /// Score 1 is accepted and score 0 is a wrong answer.
+    pub fn from_score(score: f64, message: String) -> Result<Self> {
+        if !(0.0..=1.0).contains(&score) {
+            return Err(Error::CheckerFailed {
+                code: None,
+                message: format!("score {score} is not in range [0, 1]"),
+            });
+        }
+        Ok(if score >= 1.0 {
+            Self::Accepted
+        } else if score <= 0.0 {
+            Self::WrongAnswer(message)
+        } else {
+            Self::PartiallyCorrect(score, message)
+        })
+    }
+
+    #[must_use]
+    pub const fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted)
+    }
+
+    /// Message of the checker, empty for accepted outputs.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Accepted => "",
+            Self::WrongAnswer(message) | Self::PresentationError(message) | Self::PartiallyCorrect(_, message) => message,
+        }
+    }
+
+    /// Fraction of the points the output gets.
+    #[must_use]
+    pub const fn score(&self) -> f64 {
+        match self {
+            Self::Accepted => 1.0,
+            Self::WrongAnswer(_) | Self::PresentationError(_) => 0.0,
+            Self::PartiallyCorrect(score, _) => *score,
+        }
+    }
+
+    pub fn to_display_string(&self) -> String {
+        match self {
+            Self::Accepted => "OK".to_owned(),
+            Self::WrongAnswer(_) => "WA".to_owned(),
+            Self::PresentationError(_) => "PE".to_owned(),
+            Self::PartiallyCorrect(score, _) => format!("PC {score:.2}"),
+        }
+    }
+}
+
+/// Runs a compiled checker as `checker <input> <output> <answer>` (testlib convention).
+///
+/// Exit code 0 means the output is accepted, 1 is a wrong answer and 2 is a presentation error.
+/// Exit code 7 gives partial points (testlib `quitp`): stderr then starts with the score,
+/// which has to be a fraction in `[0, 1]`, optionally prefixed with `points`. Absolute points are not
+/// supported, because the checker does not know the points of the test, and values above 1 fail the checker.
+/// Any other exit code (testlib uses 3 for FAIL) means that the checker itself failed,
+/// and so does running for longer than `timeout`.
+/// The message written to stderr by the checker is attached to the verdict.
+pub fn run_checker(checker_file: &Path, input: &str, output: &str, answer: &str, timeout: Duration) -> Result<CheckerVerdict> {
+    let io_error = |err| Error::IOError {
+        err,
+        file: checker_file.to_string_lossy().into_owned(),
+    };
+    let write_temp_file = |contents: &str| -> Result<NamedTempFile> {
+        let mut file = NamedTempFile::new().map_err(|err| Error::IOError { err, file: String::new() })?;
+        file.write_all(contents.as_bytes()).map_err(|err| Error::IOError {
+            err,
+            file: file.path().to_string_lossy().into_owned(),
+        })?;
+        Ok(file)
+    };
+    let input_file = write_temp_file(input)?;
+    let output_file = write_temp_file(output)?;
+    let answer_file = write_temp_file(answer)?;
+    // stderr goes to a file, so that a long message can not block the checker while it is waited for
+    let stderr_file = write_temp_file("")?;
+
+    let mut command = Command::new(checker_file);
+    command
+        .arg(input_file.path())
+        .arg(output_file.path())
+        .arg(answer_file.path())
+        .stdout(Stdio::null())
+        .stderr(stderr_file.reopen().map_err(io_error)?);
+    trace!("Running checker: {command:?}");
+    let mut process = command.spawn().map_err(io_error)?;
+
+    let Some(status) = wait_with_timeout(&mut process, timeout).map_err(io_error)? else {
+        return Err(Error::CheckerFailed {
+            code: None,
+            message: format!("the checker did not finish in {} ms", timeout.as_millis()),
+        });
+    };
+
+    let message = std::fs::read_to_string(stderr_file.path()).map_err(io_error)?.trim().to_owned();
+    trace!("Checker finished with return code {:?}: {message}", status.code());
+    match status.code() {
+        Some(EXIT_OK) => Ok(CheckerVerdict::Accepted),
+        Some(EXIT_WRONG_ANSWER) => Ok(CheckerVerdict::WrongAnswer(message)),
+        Some(EXIT_PRESENTATION_ERROR) => Ok(CheckerVerdict::PresentationError(message)),
+        Some(EXIT_POINTS) => parse_points(&message),
+        code => Err(Error::CheckerFailed { code, message }),
+    }
+}
+
+/// Waits for a process to finish. If it runs longer than `timeout`, it is killed and `None` is returned.
+pub fn wait_with_timeout(process: &mut Child, timeout: Duration) -> std::io::Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = process.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            process.kill().ok();
+            process.wait().ok();
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+/// Parses the stderr of a checker that exited with `EXIT_POINTS`: `[points] <score> [message]`.
+fn parse_points(message: &str) -> Result<CheckerVerdict> {
+    let message = message.strip_prefix("points").unwrap_or(message).trim_start();
+    let (score, message) = message.split_once(char::is_whitespace).unwrap_or((message, ""));
+    let Ok(score) = score.parse::<f64>() else {
+        return Err(Error::CheckerFailed {
+            code: Some(EXIT_POINTS),
+            message: format!("expected a score, got \"{score}\""),
+        });
+    };
+    CheckerVerdict::from_score(score, message.trim().to_owned())
+}
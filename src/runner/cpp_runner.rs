@@ -1,10 +1,14 @@
 use crate::Error::IOError;
 use crate::Result;
-use crate::runner::exec_runner::{RunResult, run_solution};
+use crate::runner::checker::{CHECKER_TIMEOUT, CheckerVerdict, run_checker};
+use crate::runner::exec_runner::{RunResult, run_interactive, run_solution};
 use crate::runner::gcc::{Gcc, GccOptimization, GccStandard};
+use crate::runner::language::Language;
+use crate::runner::validator::{VALIDATOR_TIMEOUT, run_validator};
 use indicatif::{MultiProgress, ProgressBar};
 use log::trace;
 use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::thread::spawn;
@@ -14,16 +18,32 @@ fn path_str(p: &Path) -> String {
     p.to_string_lossy().into_owned()
 }
 
-/// A unique handle for a compiled C++ program.
+/// A unique handle for a compiled program in any supported language.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct ProgramHandle {
     pub(crate) id: usize,
 }
 
+/// A prepared program: the command that runs it and its language.
+struct Program {
+    /// Executable for compiled languages, interpreter for interpreted ones
+    command: PathBuf,
+    /// Arguments passed to the command
+    args: Vec<OsString>,
+    /// Source file the program was built from
+    source_file: PathBuf,
+    language: Language,
+    /// Hash of the language and the source code
+    hash: u64,
+    /// Interactor the program talks to, if it runs interactively
+    interactor: Option<ProgramHandle>,
+}
+
 struct Task {
     program: ProgramHandle,
     input: String,
-    time_limit: i32, // in milliseconds
+    time_limit: i32,   // in milliseconds
+    memory_limit: i32, // in megabytes
     result: Option<RunResult>,
 }
 
@@ -33,7 +53,7 @@ pub struct TaskHandle {
     pub(crate) id: usize,
 }
 
-/// Orchestrates the compilation and parallel execution of C++ solutions.
+/// Orchestrates the compilation and parallel execution of solutions.
 ///
 /// `CppRunner` manages a build folder, handles program deduplication via hashing,
 /// and provides an asynchronous task-based API for running binaries with time limits.
@@ -44,14 +64,18 @@ pub struct CppRunner {
     build_folder: PathBuf,
     /// Handle to the internal timer utility
     timer: ProgramHandle,
-    /// Map from program ID to executable path
-    programs: Vec<PathBuf>,
+    /// Map from program ID to the prepared program
+    programs: Vec<Program>,
     /// List of registered execution tasks
     tasks: Vec<Task>,
     /// Map from source code hash to program handle for deduplication
     hash_to_handle: HashMap<u64, ProgramHandle>,
     /// Files that should be preserved in the build folder
     necessary_files: HashSet<PathBuf>,
+    /// Factors by which the time limit is multiplied for each language
+    time_limit_multipliers: HashMap<Language, f32>,
+    /// Checker program that judges the outputs, if the task uses one
+    checker: Option<ProgramHandle>,
 }
 
 impl CppRunner {
@@ -79,6 +103,8 @@ impl CppRunner {
             tasks: Vec::new(),
             hash_to_handle: HashMap::new(),
             necessary_files: HashSet::new(),
+            time_limit_multipliers: HashMap::new(),
+            checker: None,
         };
 
         trace!("Building timer program");
@@ -92,10 +118,34 @@ impl CppRunner {
     ///
     /// If the same source has already been added, the existing handle is returned.
     pub fn add_program(&mut self, source_code: &str) -> Result<ProgramHandle> {
-        trace!("Adding program with source code: {source_code}");
+        self.add_program_lang(source_code, Language::Cpp)
+    }
+
+    /// Prepares a source string in the given language and returns a handle to the program.
+    ///
+    /// If the same source has already been added, the existing handle is returned.
+    pub fn add_program_lang(&mut self, source_code: &str, language: Language) -> Result<ProgramHandle> {
+        self.add_program_inner(source_code, language, None)
+    }
+
+    /// Prepares a source file in the given language and returns a handle to the program.
+    /// `source_code` is the contents of the file, which the caller has already read.
+    ///
+    /// The file is compiled directly, so compiler errors refer to its real name and lines.
+    /// It is only rebuilt when its contents change.
+    pub fn add_program_file(&mut self, source_file: &Path, source_code: &str, language: Language) -> Result<ProgramHandle> {
+        self.add_program_inner(source_code, language, Some(source_file))
+    }
+
+    /// Shared implementation of `add_program_lang` and `add_program_file`.
+    ///
+    /// * `original_file` - File the source was read from. If set, it is compiled instead of the copy in the build folder.
+    fn add_program_inner(&mut self, source_code: &str, language: Language, original_file: Option<&Path>) -> Result<ProgramHandle> {
+        trace!("Adding {} program with source code: {source_code}", language.as_str());
         let handle = ProgramHandle { id: self.programs.len() };
         let hash = {
             let mut s = DefaultHasher::new();
+            language.hash(&mut s);
             source_code.hash(&mut s);
             s.finish()
         };
@@ -106,38 +156,125 @@ impl CppRunner {
             return Ok(*existing_handle);
         }
 
-        self.hash_to_handle.insert(hash, handle);
-        let source_file = self.build_folder.join(format!("p{hash}.cpp"));
-        let executable_file = Gcc::transform_output_file(&source_file, None)?;
+        let source_file = self.build_folder.join(format!("p{hash}.{}", language.extension()));
+        let executable_file = if language.is_compiled() {
+            Gcc::transform_output_file(&source_file, None)?
+        } else {
+            source_file.clone()
+        };
+        let compiled_file = original_file.map_or_else(|| source_file.clone(), Path::to_path_buf);
 
-        self.necessary_files.insert(source_file.clone());
         self.necessary_files.insert(executable_file.clone());
 
-        if !source_file.exists() {
-            std::fs::write(&source_file, source_code).map_err(|err| IOError { err, file: path_str(&source_file) })?;
+        if original_file.is_none() || !language.is_compiled() {
+            self.necessary_files.insert(source_file.clone());
+
+            if !source_file.exists() {
+                std::fs::write(&source_file, source_code).map_err(|err| IOError { err, file: path_str(&source_file) })?;
+
+                if !language.is_compiled() {
+                    // interpreted sources are only checked once, remove them if the check fails
+                    if let Err(err) = language.compile(&self.gcc, &compiled_file, &executable_file) {
+                        std::fs::remove_file(&source_file).map_err(|err| IOError { err, file: path_str(&source_file) })?;
+                        return Err(err);
+                    }
+                }
+            }
         }
 
-        if !executable_file.exists() {
+        if language.is_compiled() && !executable_file.exists() {
             trace!("Compiling: {}", executable_file.to_string_lossy());
-            self.gcc.compile(&source_file, Some(&executable_file))?;
+            language.compile(&self.gcc, &compiled_file, &executable_file)?;
         }
 
-        self.programs.push(executable_file);
+        let (command, args) = language.run_command(&executable_file)?;
+        self.hash_to_handle.insert(hash, handle);
+        self.programs.push(Program {
+            command,
+            args,
+            source_file: compiled_file,
+            language,
+            hash,
+            interactor: None,
+        });
         Ok(handle)
     }
 
+    /// Overrides the factor by which the time limit is multiplied for programs in `language`.
+    pub fn set_time_limit_multiplier(&mut self, language: Language, multiplier: f32) {
+        self.time_limit_multipliers.insert(language, multiplier);
+    }
+
+    /// Makes the program run interactively against the given interactor, or normally if it is `None`.
+    /// See `run_interactive` for the protocol between the interactor and the runner.
+    pub fn set_interactor(&mut self, program: ProgramHandle, interactor: Option<ProgramHandle>) {
+        self.programs[program.id].interactor = interactor;
+    }
+
+    /// Sets the checker program used by `run_checker`.
+    pub const fn set_checker(&mut self, checker: Option<ProgramHandle>) {
+        self.checker = checker;
+    }
+
+    /// Judges a program output with the checker program.
+    ///
+    /// Returns `None` if no checker is set.
+    pub fn run_checker(&self, input: &str, output: &str, answer: &str) -> Result<Option<CheckerVerdict>> {
+        self.checker
+            .map(|checker| run_checker(&self.programs[checker.id].command, input, output, answer, CHECKER_TIMEOUT))
+            .transpose()
+    }
+
+    /// Checks a test input with a validator program.
+    ///
+    /// Returns `Ok(Err(message))` if the validator rejects the input.
+    pub fn run_validator(&self, validator: ProgramHandle, input: &str) -> Result<Result<(), String>> {
+        run_validator(&self.programs[validator.id].command, input, VALIDATOR_TIMEOUT)
+    }
+
+    /// Returns the path of the executable (or the interpreter) of a program.
+    #[must_use]
+    pub fn get_executable_path(&self, program: ProgramHandle) -> &Path {
+        &self.programs[program.id].command
+    }
+
+    /// Returns the path of the source file a program was built from.
+    #[must_use]
+    pub fn get_source_path(&self, program: ProgramHandle) -> &Path {
+        &self.programs[program.id].source_file
+    }
+
+    /// Returns the hash of the language and the source code of a program, it is the same in every run.
+    #[must_use]
+    pub fn get_program_hash(&self, program: ProgramHandle) -> u64 {
+        self.programs[program.id].hash
+    }
+
+    fn get_time_limit_multiplier(&self, language: Language) -> f32 {
+        self.time_limit_multipliers.get(&language).copied().unwrap_or_else(|| language.default_time_limit_multiplier())
+    }
+
+    /// Returns the time limit that a program runs with for the given time limit of the task,
+    /// multiplied by the time limit multiplier of its language.
+    #[must_use]
+    pub fn get_time_limit(&self, program: ProgramHandle, time_limit: i32) -> i32 {
+        (time_limit as f32 * self.get_time_limit_multiplier(self.programs[program.id].language)).round() as i32
+    }
+
     /// Registers a new execution task.
     ///
     /// * `program` - Handle to the executable to run.
     /// * `input` - Data to be sent to stdin.
-    /// * `time_limit` - Maximum CPU time in seconds.
-    pub fn add_task(&mut self, program: ProgramHandle, input: String, time_limit: i32) -> TaskHandle {
-        trace!("Adding task for program id: {}, time limit: {}", program.id, time_limit);
+    /// * `time_limit` - Maximum CPU time in milliseconds.
+    /// * `memory_limit` - Maximum memory in megabytes (0 means unlimited).
+    pub fn add_task(&mut self, program: ProgramHandle, input: String, time_limit: i32, memory_limit: i32) -> TaskHandle {
+        trace!("Adding task for program id: {}, time limit: {}, memory limit: {}", program.id, time_limit, memory_limit);
         let handle = TaskHandle { id: self.tasks.len() };
         self.tasks.push(Task {
             program,
             input,
             time_limit,
+            memory_limit,
             result: None,
         });
         handle
@@ -160,11 +297,11 @@ impl CppRunner {
     /// Runs multiple programs against a single input sequentially or in parallel.
     ///
     /// This is a convenience method that manages task creation and result collection.
-    pub fn check_programs(&mut self, input: &str, programs: &[ProgramHandle], time_limit: i32) -> Result<Vec<RunResult>> {
+    pub fn check_programs(&mut self, input: &str, programs: &[ProgramHandle], time_limit: i32, memory_limit: i32) -> Result<Vec<RunResult>> {
         self.clear_tasks();
         let mut handles = Vec::new();
         for &program in programs {
-            handles.push(self.add_task(program, input.to_owned(), time_limit));
+            handles.push(self.add_task(program, input.to_owned(), time_limit, memory_limit));
         }
         self.run_tasks(None, false)?;
         let mut results = Vec::new();
@@ -202,7 +339,7 @@ impl CppRunner {
             self.clean_build_folder()?;
         }
 
-        let timer_path = self.programs[self.timer.id].clone();
+        let timer_path = self.programs[self.timer.id].command.clone();
 
         let num_threads = num_cpus::get().min(4);
         let mut threads = Vec::new();
@@ -213,10 +350,13 @@ impl CppRunner {
 
         loop {
             while threads.len() < num_threads && it < self.tasks.len() {
-                let program_handle = &self.tasks[it].program;
-                let executable_file = self.programs[program_handle.id].clone();
+                let program = &self.programs[self.tasks[it].program.id];
+                let executable_file = program.command.clone();
+                let args = program.args.clone();
+                let interactor_path = program.interactor.map(|interactor| self.programs[interactor.id].command.clone());
                 let input_data = self.tasks[it].input.clone();
-                let time_limit = self.tasks[it].time_limit;
+                let time_limit = self.get_time_limit(self.tasks[it].program, self.tasks[it].time_limit);
+                let memory_limit = self.tasks[it].memory_limit;
 
                 it += 1;
                 if let Some(progress_bar) = &progress_bar {
@@ -224,7 +364,15 @@ impl CppRunner {
                 }
 
                 let timer_path = timer_path.clone();
-                threads.push((spawn(move || run_solution(&executable_file, &input_data, time_limit, &timer_path)), it - 1));
+                threads.push((
+                    spawn(move || {
+                        interactor_path.map_or_else(
+                            || run_solution(&executable_file, &args, &input_data, time_limit, memory_limit, &timer_path),
+                            |interactor_path| run_interactive(&executable_file, &args, &interactor_path, &input_data, time_limit, memory_limit, &timer_path),
+                        )
+                    }),
+                    it - 1,
+                ));
             }
 
             let mut threads_upd = Vec::new();
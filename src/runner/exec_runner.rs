@@ -1,41 +1,93 @@
 use crate::Error;
 use crate::Result;
 use log::trace;
+use std::ffi::OsString;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+use tempfile::NamedTempFile;
 
 /// The result of running a compiled program.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum RunResult {
-    /// Program finished successfully: (`elapsed_time_ms`, stdout)
-    Ok(i32, String),
+    /// Program finished successfully: (`elapsed_time_ms`, `peak_memory_kb`, stdout)
+    Ok(i32, i32, String),
     /// Program exceeded the time limit.
     TimedOut,
+    /// Program exceeded the memory limit.
+    MemoryLimitExceeded,
     /// Program crashed or returned a non-zero exit code.
     Crashed,
+    /// The interactor rejected the program.
+    WrongAnswer,
 }
 
 impl RunResult {
     pub fn to_display_string(&self) -> String {
         match self {
-            Self::Ok(_, _) => "OK".to_owned(),
+            Self::Ok(_, _, _) => "OK".to_owned(),
             Self::TimedOut => "TLE".to_owned(),
+            Self::MemoryLimitExceeded => "MLE".to_owned(),
             Self::Crashed => "RTE".to_owned(),
+            Self::WrongAnswer => "WA".to_owned(),
         }
     }
 }
 
+/// Builds the command that runs `executable_file` under the timer utility.
+fn timer_command(executable_file: &PathBuf, args: &[OsString], time_limit: i32, memory_limit: i32, timer_path: &Path) -> Command {
+    let mut command = Command::new(timer_path);
+    command.arg(executable_file);
+    command.arg(format!("{time_limit}"));
+    command.arg(format!("{memory_limit}"));
+    command.args(args);
+    command
+}
+
+/// Interprets the exit status and stderr of the timer utility.
+/// Returns the elapsed time and peak memory if the solution finished successfully,
+/// otherwise the failed result.
+pub fn timer_verdict(status: ExitStatus, stderr: &[u8]) -> std::result::Result<(i32, i32), RunResult> {
+    if status.code() == Some(175) {
+        trace!("Solution timed out with signal 175");
+        return Err(RunResult::TimedOut);
+    }
+
+    if status.code() == Some(176) {
+        trace!("Solution exceeded memory limit with signal 176");
+        return Err(RunResult::MemoryLimitExceeded);
+    }
+
+    if !status.success() || status.code() != Some(0) {
+        trace!("Solution crashed with return code: {:?}", status.code());
+        return Err(RunResult::Crashed);
+    }
+
+    // capture stderr from solution process
+    let stderr_str = String::from_utf8_lossy(stderr);
+    // parse output from timer command: the last line is "<time_ms> <memory_kb>"
+    let last_line = stderr_str.trim().lines().last().unwrap_or_default();
+    let mut values = last_line.split_whitespace().map(str::parse::<i32>);
+    let (Some(Ok(elapsed_time_ms)), Some(Ok(peak_memory_kb)), None) = (values.next(), values.next(), values.next()) else {
+        trace!("Unexpected last line of the timer output: {last_line}");
+        return Err(RunResult::Crashed);
+    };
+    trace!("Elapsed time from timer: {elapsed_time_ms} ms, peak memory: {peak_memory_kb} KB");
+
+    Ok((elapsed_time_ms, peak_memory_kb))
+}
+
 /// Spawns the timer utility to execute and monitor a solution.
 ///
-/// * `executable_file` - Path to the compiled C++ binary.
+/// * `executable_file` - Path to the compiled binary or the interpreter.
+/// * `args` - Arguments passed to the executable (e.g. the script for interpreted languages).
 /// * `input_data` - Input to be sent via stdin.
-/// * `time_limit` - Maximum CPU time in seconds.
+/// * `time_limit` - Maximum CPU time in milliseconds.
+/// * `memory_limit` - Maximum resident memory in megabytes (0 means unlimited).
 /// * `timer_path` - Path to the pre-compiled `timer` utility.
-pub fn run_solution(executable_file: &PathBuf, input_data: &str, time_limit: i32, timer_path: &Path) -> Result<RunResult> {
-    let mut solution_process = Command::new(timer_path);
-    solution_process.arg(executable_file);
-    solution_process.arg(format!("{time_limit}"));
+pub fn run_solution(executable_file: &PathBuf, args: &[OsString], input_data: &str, time_limit: i32, memory_limit: i32, timer_path: &Path) -> Result<RunResult> {
+    let mut solution_process = timer_command(executable_file, args, time_limit, memory_limit, timer_path);
 
     trace!("Running command: {solution_process:?}");
     // spawn the solution process
@@ -56,28 +108,113 @@ pub fn run_solution(executable_file: &PathBuf, input_data: &str, time_limit: i32
     drop(solution_process.stdin.take());
 
     let output_result = solution_process.wait_with_output().map_err(|err| Error::IOError { err, file: String::new() })?;
-    let return_code = output_result.status;
 
-    if return_code.code() == Some(175) {
-        trace!("Solution timed out with signal 175");
-        return Ok(RunResult::TimedOut);
-    }
+    let (elapsed_time_ms, peak_memory_kb) = match timer_verdict(output_result.status, &output_result.stderr) {
+        Ok(usage) => usage,
+        Err(result) => return Ok(result),
+    };
 
-    if !return_code.success() || return_code.code() != Some(0) {
-        trace!("Solution crashed with return code: {:?}", return_code.code());
-        return Ok(RunResult::Crashed);
-    }
+    let output = String::from_utf8_lossy(&output_result.stdout).into_owned();
+
+    Ok(RunResult::Ok(elapsed_time_ms, peak_memory_kb, output))
+}
+
+/// Runs a solution interactively: its stdin and stdout are connected to an interactor.
+///
+/// The interactor is started as `interactor <input_file> <output_file>`, where `input_file`
+/// contains the test input. It decides the verdict with its exit code (0 means accepted) and
+/// whatever it writes into `output_file` becomes the output of the run.
+/// Time and memory limits only apply to the solution.
+///
+/// * `interactor_file` - Path to the compiled interactor.
+/// * other arguments are the same as in `run_solution`.
+pub fn run_interactive(executable_file: &PathBuf, args: &[OsString], interactor_file: &Path, input_data: &str, time_limit: i32, memory_limit: i32, timer_path: &Path) -> Result<RunResult> {
+    let io_error = |err| Error::IOError { err, file: String::new() };
+    let mut input_file = NamedTempFile::new().map_err(io_error)?;
+    input_file.write_all(input_data.as_bytes()).map_err(|err| Error::IOError {
+        err,
+        file: path_str(input_file.path()),
+    })?;
+    let output_file = NamedTempFile::new().map_err(io_error)?;
+    // stderr of the interactor goes to a file, a pipe that is not drained while waiting could fill up and block it
+    let interactor_stderr = NamedTempFile::new().map_err(io_error)?;
+
+    let mut solution_process = timer_command(executable_file, args, time_limit, memory_limit, timer_path);
+    trace!("Running command: {solution_process:?}");
+    let mut solution_process = solution_process.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn().map_err(io_error)?;
 
-    let elapsed_time_ms = {
-        // capture stderr from solution process
-        let stderr_str = String::from_utf8_lossy(&output_result.stderr);
-        // parse output from timer command (ignore trailing newlines/whitespace)
-        let trimmed = stderr_str.trim();
-        trimmed.parse::<i32>().unwrap()
+    // connect the pipes crosswise: solution stdout -> interactor stdin, interactor stdout -> solution stdin
+    let (Some(solution_stdin), Some(solution_stdout)) = (solution_process.stdin.take(), solution_process.stdout.take()) else {
+        solution_process.kill().ok();
+        solution_process.wait().ok();
+        return Err(Error::InteractorFailed {
+            message: "Could not connect the solution to the interactor".to_owned(),
+        });
+    };
+    let interactor_process = interactor_stderr.reopen().and_then(|stderr_file| {
+        // the command is dropped right after spawning, so that it does not keep the pipe ends open
+        let mut command = Command::new(interactor_file);
+        command
+            .arg(input_file.path())
+            .arg(output_file.path())
+            .stdin(Stdio::from(solution_stdout))
+            .stdout(Stdio::from(solution_stdin))
+            .stderr(Stdio::from(stderr_file));
+        trace!("Running interactor: {command:?}");
+        command.spawn()
+    });
+    let interactor_process = match interactor_process {
+        Ok(process) => process,
+        Err(err) => {
+            solution_process.kill().ok();
+            solution_process.wait().ok();
+            return Err(io_error(err));
+        }
     };
-    trace!("Elapsed time from timer: {elapsed_time_ms} ms");
 
-    let output = String::from_utf8_lossy(&output_result.stdout).into_owned();
+    let solution_output = solution_process.wait_with_output().map_err(io_error)?;
+
+    // The interactor sees EOF once the solution exits, so it should finish shortly after.
+    // If it keeps running, it is killed after a safety timeout.
+    let interactor_status = {
+        let mut interactor_process = interactor_process;
+        let deadline = Instant::now() + Duration::from_millis(time_limit.max(0) as u64 + 2000);
+        loop {
+            if let Some(status) = interactor_process.try_wait().map_err(io_error)? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                interactor_process.kill().ok();
+                interactor_process.wait().ok();
+                return Err(Error::InteractorFailed {
+                    message: "Interactor did not finish after the solution exited".to_owned(),
+                });
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    };
+    trace!("Interactor finished with return code: {:?}", interactor_status.code());
+
+    let (elapsed_time_ms, peak_memory_kb) = match timer_verdict(solution_output.status, &solution_output.stderr) {
+        Ok(usage) => usage,
+        // The solution may crash because the interactor stopped talking to it after a wrong answer
+        Err(RunResult::Crashed) if !interactor_status.success() => return Ok(RunResult::WrongAnswer),
+        Err(result) => return Ok(result),
+    };
+
+    if !interactor_status.success() {
+        trace!("Interactor rejected the solution: {}", std::fs::read_to_string(interactor_stderr.path()).unwrap_or_default());
+        return Ok(RunResult::WrongAnswer);
+    }
+
+    let output = std::fs::read_to_string(output_file.path()).map_err(|err| Error::IOError {
+        err,
+        file: path_str(output_file.path()),
+    })?;
+
+    Ok(RunResult::Ok(elapsed_time_ms, peak_memory_kb, output))
+}
 
-    Ok(RunResult::Ok(elapsed_time_ms, output))
+fn path_str(p: &Path) -> String {
+    p.to_string_lossy().into_owned()
 }
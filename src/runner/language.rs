@@ -0,0 +1,159 @@
+use crate::runner::gcc::Gcc;
+use crate::{Error, Result};
+use log::debug;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Programming languages in which solutions can be written.
+///
+/// C++ is compiled with the same `g++` that builds the internal timer,
+/// C and Rust are compiled with `gcc` and `rustc`, and Python sources are
+/// executed by the `python3` interpreter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    Cpp,
+    C,
+    Rust,
+    Python,
+}
+
+impl Language {
+    /// Detects the language from a source file extension (without the dot).
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "cpp" | "cc" | "cxx" | "c++" => Some(Self::Cpp),
+            "c" => Some(Self::C),
+            "rs" => Some(Self::Rust),
+            "py" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    /// Detects the language from the extension of a source file path.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension().and_then(|extension| extension.to_str()).and_then(Self::from_extension)
+    }
+
+    /// Extension used for source files of this language.
+    #[must_use]
+    pub const fn extension(&self) -> &str {
+        match self {
+            Self::Cpp => "cpp",
+            Self::C => "c",
+            Self::Rust => "rs",
+            Self::Python => "py",
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(&self) -> &str {
+        match self {
+            Self::Cpp => "C++",
+            Self::C => "C",
+            Self::Rust => "Rust",
+            Self::Python => "Python",
+        }
+    }
+
+    /// Factor by which the time limit is multiplied for solutions in this language.
+    /// It can be overridden with `Task::with_time_limit_multiplier`.
+    #[must_use]
+    pub const fn default_time_limit_multiplier(&self) -> f32 {
+        match self {
+            Self::Cpp | Self::C | Self::Rust => 1.0,
+            Self::Python => 3.0,
+        }
+    }
+
+    /// Returns `true` if sources of this language are compiled into a native executable.
+    #[must_use]
+    pub const fn is_compiled(&self) -> bool {
+        !matches!(self, Self::Python)
+    }
+
+    /// Compiles `source_file` into `executable_file`.
+    /// For interpreted languages it only checks the syntax of the source.
+    pub(crate) fn compile(self, gcc: &Gcc, source_file: &Path, executable_file: &PathBuf) -> Result<()> {
+        let mut command = match self {
+            Self::Cpp => {
+                gcc.compile(source_file, Some(executable_file))?;
+                return Ok(());
+            }
+            Self::C => {
+                let mut command = Command::new(find_tool("CC_PATH", &["gcc", "cc"])?);
+                command.arg("-std=c11").arg("-O2");
+                #[cfg(windows)]
+                {
+                    command.arg("-static");
+                }
+                #[cfg(target_os = "macos")]
+                {
+                    command.arg("-Wl,-stack_size,0x20000000");
+                }
+                command.arg(source_file).arg("-o").arg(executable_file).arg("-lm");
+                command
+            }
+            Self::Rust => {
+                let mut command = Command::new(find_tool("RUSTC_PATH", &["rustc"])?);
+                command.arg("--edition=2021").arg("-O").arg(source_file).arg("-o").arg(executable_file);
+                command
+            }
+            Self::Python => {
+                let mut command = Command::new(find_tool("PYTHON_PATH", &["python3", "python"])?);
+                // compile without writing bytecode files into the build folder
+                command.arg("-c").arg("import sys; compile(open(sys.argv[1]).read(), sys.argv[1], 'exec')").arg(source_file);
+                command
+            }
+        };
+
+        debug!("Running command: {command:?}");
+        let process = command.output().map_err(|err| Error::IOError { err, file: String::new() })?;
+
+        if !process.status.success() {
+            return Err(Error::CompilerError {
+                stderr: String::from_utf8_lossy(&process.stderr).to_string(),
+                stdout: String::from_utf8_lossy(&process.stdout).to_string(),
+            });
+        }
+
+        if self.is_compiled() && !executable_file.exists() {
+            return Err(Error::CompilerError {
+                stderr: "Output file was not created".to_owned(),
+                stdout: String::new(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the command and its arguments that execute a prepared program.
+    ///
+    /// * `file` - The executable for compiled languages or the source file for interpreted ones.
+    pub(crate) fn run_command(self, file: &Path) -> Result<(PathBuf, Vec<OsString>)> {
+        match self {
+            Self::Cpp | Self::C | Self::Rust => Ok((file.to_owned(), Vec::new())),
+            Self::Python => Ok((find_tool("PYTHON_PATH", &["python3", "python"])?, vec![file.as_os_str().to_owned()])),
+        }
+    }
+}
+
+/// Locates a compiler or interpreter, preferring the path in the given environment variable.
+fn find_tool(env_var: &str, candidates: &[&str]) -> Result<PathBuf> {
+    if let Ok(path) = std::env::var(env_var) {
+        return Ok(PathBuf::from(path));
+    }
+
+    for candidate in candidates {
+        if let Ok(path) = which::which(candidate) {
+            return Ok(path);
+        }
+    }
+
+    Err(Error::ToolNotFound {
+        tool: candidates.first().copied().unwrap_or_default().to_owned(),
+        env_var: env_var.to_owned(),
+    })
+}
@@ -1,4 +1,7 @@
 //! Execution engines for running solutions and verifying outcomes.
+pub mod checker;
 pub mod cpp_runner;
 pub mod exec_runner;
 pub mod gcc;
+pub mod language;
+pub mod validator;
@@ -3,7 +3,10 @@
 #include <iostream>
 #include <string>
 #include <thread>
+#include <vector>
 #include <windows.h>
+#define PSAPI_VERSION 2
+#include <psapi.h>
 
 using namespace std;
 
@@ -23,9 +26,18 @@ long long get_rusage(HANDLE process) {
   return user_ms + sys_ms;
 }
 
+long long get_peak_memory_kb(HANDLE process) {
+  PROCESS_MEMORY_COUNTERS counters;
+  if (!GetProcessMemoryInfo(process, &counters, sizeof(counters))) {
+    return 0;
+  }
+  return (long long)counters.PeakWorkingSetSize / 1024;
+}
+
 int main(int argc, const char *argv[]) {
   string command = argv[1];
   int timeout_ms = stoi(argv[2]);
+  long long memory_limit_kb = argc > 3 ? stoll(argv[3]) * 1024 : 0;
 
   // Use STARTUPINFOW for wide characters
   STARTUPINFOW si = {sizeof(si)};
@@ -34,9 +46,13 @@ int main(int argc, const char *argv[]) {
 
   // Convert command to wide string for Windows (needed for CreateProcessW)
   wstring wcommand(command.begin(), command.end());
-  // Quote the command path in case it contains spaces and build a mutable
-  // buffer
+  // Quote the command path and its arguments in case they contain spaces and
+  // build a mutable buffer
   wstring quoted = L"\"" + wcommand + L"\"";
+  for (int i = 4; i < argc; i++) {
+    string arg = argv[i];
+    quoted += L" \"" + wstring(arg.begin(), arg.end()) + L"\"";
+  }
   // CreateProcessW requires a modifiable buffer for the command line
   vector<wchar_t> cmdline(quoted.begin(), quoted.end());
   cmdline.push_back(L'\0');
@@ -53,9 +69,19 @@ int main(int argc, const char *argv[]) {
 
   while (elapsed < timeout_ms) {
     waitResult = WaitForSingleObject(pi.hProcess, wait_time_ms);
+    long long peak_memory_kb = get_peak_memory_kb(pi.hProcess);
+    if (memory_limit_kb != 0 && peak_memory_kb > memory_limit_kb) {
+      TerminateProcess(pi.hProcess, 1);
+      WaitForSingleObject(pi.hProcess, INFINITE);
+      CloseHandle(pi.hProcess);
+      CloseHandle(pi.hThread);
+      return 176; // Memory limit exceeded
+    }
+
     if (waitResult == WAIT_OBJECT_0) {
       long long elapsed_time = get_rusage(pi.hProcess);
-      cerr << elapsed_time << endl; // Output the elapsed time
+      // Output the elapsed time and peak memory
+      cerr << elapsed_time << " " << peak_memory_kb << endl;
 
       DWORD exitCode;
       GetExitCodeProcess(pi.hProcess, &exitCode);
@@ -79,6 +105,7 @@ int main(int argc, const char *argv[]) {
 
 #else
 #include <chrono>
+#include <cstdio>
 #include <iostream>
 #include <signal.h>
 #include <sys/resource.h>
@@ -86,6 +113,7 @@ int main(int argc, const char *argv[]) {
 #include <time.h>
 #include <thread>
 #include <unistd.h>
+#include <vector>
 using namespace std;
 
 long long get_wall_time_ms() {
@@ -94,12 +122,56 @@ long long get_wall_time_ms() {
   return 1LL * ts.tv_sec * 1000 + ts.tv_nsec / 1000000;
 }
 
-// Runs `command` with a CPU-time limit of `time_limit_ms` milliseconds.
+// Current resident set size of a running process in kilobytes.
+// Only available on Linux, elsewhere the peak is taken from rusage on exit.
+long long get_current_memory_kb(pid_t pid) {
+#ifdef __linux__
+  char path[64];
+  snprintf(path, sizeof(path), "/proc/%d/statm", (int)pid);
+  FILE *file = fopen(path, "r");
+  if (file == nullptr) {
+    return 0;
+  }
+  long long size = 0, resident = 0;
+  int read = fscanf(file, "%lld %lld", &size, &resident);
+  fclose(file);
+  if (read != 2) {
+    return 0;
+  }
+  return resident * (sysconf(_SC_PAGESIZE) / 1024);
+#else
+  (void)pid;
+  return 0;
+#endif
+}
+
+// CPU time (user and system) from rusage in milliseconds.
+long long get_cpu_time_ms(const struct rusage &usage) {
+  return 1LL * (usage.ru_utime.tv_sec + usage.ru_stime.tv_sec) * 1000 +
+         (usage.ru_utime.tv_usec + usage.ru_stime.tv_usec) / 1000;
+}
+
+// Peak resident set size from rusage in kilobytes.
+long long get_peak_memory_kb(const struct rusage &usage) {
+#ifdef __APPLE__
+  return usage.ru_maxrss / 1024; // bytes on macOS
+#else
+  return usage.ru_maxrss; // kilobytes on Linux
+#endif
+}
+
+// Runs `command` with arguments `args`, a CPU-time limit of `time_limit_ms` milliseconds and a
+// memory limit of `memory_limit_kb` kilobytes (0 means no memory limit).
+// Peak resident memory of the command is stored into `peak_memory_kb`.
 // Returns:
 //   175           – TLE (CPU limit exceeded or wall-time safety-kill)
+//   176           – MLE (peak resident memory exceeded the limit)
 //   0             – exited successfully
 //   positive int  – exit code or signal number for crashes
-int run_command_with_timeout(const string &command, int time_limit_ms) {
+int run_command_with_limits(const string &command, const vector<string> &args,
+                            int time_limit_ms,
+                            long long memory_limit_kb,
+                            long long &peak_memory_kb) {
   pid_t pid = fork();
 
   if (pid < 0) {
@@ -110,33 +182,39 @@ int run_command_with_timeout(const string &command, int time_limit_ms) {
   if (pid == 0) {
     // Child: set a CPU-time limit before exec so TLE is determined by actual
     // CPU consumption, not wall time. This is unaffected by scheduler delays
-    // or parallel test load.
+    // or parallel test load. The limit is in whole seconds, so it only stops
+    // the program, the exact limit is checked from rusage when it exits.
     int limit_s = (time_limit_ms + 999) / 1000; // round up to whole seconds
     struct rlimit rl;
     rl.rlim_cur = (rlim_t)limit_s;       // soft limit  -> SIGXCPU
     rl.rlim_max = (rlim_t)(limit_s + 5); // hard limit  -> SIGKILL
     setrlimit(RLIMIT_CPU, &rl);
 
-    // Set stack and memory limits to unlimited (common in CP)
-    // Set stack and memory limits as high as possible
+    // Set stack limit as high as possible (common in CP). The stack counts
+    // towards the resident memory, so it is still covered by the memory limit.
     struct rlimit rl_stack;
     if (getrlimit(RLIMIT_STACK, &rl_stack) != -1) {
         rl_stack.rlim_cur = rl_stack.rlim_max;
         setrlimit(RLIMIT_STACK, &rl_stack);
     }
 
-    struct rlimit rl_as;
-    if (getrlimit(RLIMIT_AS, &rl_as) != -1) {
-        rl_as.rlim_cur = rl_as.rlim_max;
-        setrlimit(RLIMIT_AS, &rl_as);
-    }
+    // The address space is deliberately not limited: a failed allocation
+    // would show up as a crash instead of a memory limit verdict. Memory is
+    // enforced by watching the resident set size of the process instead.
 
-    execl(command.c_str(), command.c_str(), (char *)nullptr);
+    vector<char *> exec_args;
+    exec_args.push_back((char *)command.c_str());
+    for (const string &arg : args) {
+      exec_args.push_back((char *)arg.c_str());
+    }
+    exec_args.push_back(nullptr);
+    execv(command.c_str(), exec_args.data());
     _exit(127);
   }
 
   int status;
-  bool killed_by_us = false;
+  struct rusage usage;
+  peak_memory_kb = 0;
   // Safety-net wall-time kill: 2x the CPU limit + 2 s. This only fires when
   // the process is somehow not consuming CPU (e.g., sleeping in a syscall
   // forever). Normal programs and infinite loops are caught by RLIMIT_CPU.
@@ -145,9 +223,17 @@ int run_command_with_timeout(const string &command, int time_limit_ms) {
   long long wall_deadline = get_wall_time_ms() + (long long)time_limit_ms * 2 + 2000;
 
   while (true) {
-    pid_t result = waitpid(pid, &status, WNOHANG);
+    pid_t result = wait4(pid, &status, WNOHANG, &usage);
     if (result == pid) {
+      peak_memory_kb = max(peak_memory_kb, get_peak_memory_kb(usage));
+      if (memory_limit_kb != 0 && peak_memory_kb > memory_limit_kb) {
+        return 176;
+      }
       if (WIFEXITED(status)) {
+        // RLIMIT_CPU is rounded up to whole seconds, this is the exact limit
+        if (get_cpu_time_ms(usage) > time_limit_ms) {
+          return 175;
+        }
         return WEXITSTATUS(status);
       } else if (WIFSIGNALED(status)) {
         int sig = WTERMSIG(status);
@@ -161,9 +247,15 @@ int run_command_with_timeout(const string &command, int time_limit_ms) {
       }
     }
 
+    peak_memory_kb = max(peak_memory_kb, get_current_memory_kb(pid));
+    if (memory_limit_kb != 0 && peak_memory_kb > memory_limit_kb) {
+      kill(pid, SIGKILL);
+      waitpid(pid, &status, 0);
+      return 176;
+    }
+
     if (get_wall_time_ms() >= wall_deadline) {
       kill(pid, SIGKILL);
-      killed_by_us = true;
       waitpid(pid, &status, 0);
       return 175;
     }
@@ -175,16 +267,20 @@ int run_command_with_timeout(const string &command, int time_limit_ms) {
 int main(int argc, const char *argv[]) {
   string command = argv[1];
   int time_limit_ms = stoi(argv[2]);
+  long long memory_limit_kb = argc > 3 ? stoll(argv[3]) * 1024 : 0;
+  vector<string> args(argv + min(argc, 4), argv + argc);
 
+  long long peak_memory_kb = 0;
   long long start = get_wall_time_ms();
-  int exit_status = run_command_with_timeout(command, time_limit_ms);
+  int exit_status = run_command_with_limits(command, args, time_limit_ms, memory_limit_kb, peak_memory_kb);
   long long end = get_wall_time_ms();
 
-  if (exit_status == 175) {
-    return 175;
+  if (exit_status == 175 || exit_status == 176) {
+    return exit_status;
   }
 
-  cerr << end - start;
+  // Start on a new line in case the solution wrote to stderr itself
+  cerr << endl << end - start << " " << peak_memory_kb;
   return exit_status;
 }
 
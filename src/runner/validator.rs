@@ -0,0 +1,50 @@
+use crate::runner::checker::wait_with_timeout;
+use crate::{Error, Result};
+use log::trace;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use tempfile::NamedTempFile;
+
+/// Time after which a validator that has not finished is killed and counted as failed.
+pub const VALIDATOR_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs a compiled input validator (testlib convention): the test input is given on stdin.
+///
+/// Returns `Ok(Err(message))` if the validator rejects the input (non-zero exit code),
+/// where the message is what the validator wrote to stderr.
+/// A validator that runs longer than `timeout` is killed and `Error::ValidatorFailed` is returned.
+pub fn run_validator(validator_file: &Path, input: &str, timeout: Duration) -> Result<Result<(), String>> {
+    let io_error = |err| Error::IOError {
+        err,
+        file: validator_file.to_string_lossy().into_owned(),
+    };
+    let temp_file_error = |err| Error::IOError { err, file: String::new() };
+
+    // stdin and stderr are files, so that the validator can not block on a full pipe while it is waited for
+    let mut input_file = NamedTempFile::new().map_err(temp_file_error)?;
+    input_file.write_all(input.as_bytes()).map_err(temp_file_error)?;
+    let stderr_file = NamedTempFile::new().map_err(temp_file_error)?;
+
+    let mut command = Command::new(validator_file);
+    command
+        .stdin(input_file.reopen().map_err(io_error)?)
+        .stdout(Stdio::null())
+        .stderr(stderr_file.reopen().map_err(io_error)?);
+    trace!("Running validator: {command:?}");
+    let mut process = command.spawn().map_err(io_error)?;
+
+    let Some(status) = wait_with_timeout(&mut process, timeout).map_err(io_error)? else {
+        return Err(Error::ValidatorFailed {
+            message: format!("the validator did not finish in {} ms", timeout.as_millis()),
+        });
+    };
+
+    if status.success() {
+        return Ok(Ok(()));
+    }
+    let message = std::fs::read_to_string(stderr_file.path()).map_err(io_error)?.trim().to_owned();
+    trace!("Validator finished with return code {:?}: {message}", status.code());
+    Ok(Err(if message.is_empty() { format!("validator exited with code {:?}", status.code()) } else { message }))
+}
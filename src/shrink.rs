@@ -0,0 +1,103 @@
+use crate::Result;
+
+/// Minimizes a test input while `reproduces` returns true for it, by deleting lines, deleting tokens
+/// of every line and halving numbers (delta debugging on the text). At most `max_tries` smaller inputs are tried.
+/// The input is expected to reproduce the failure, it is returned unchanged if nothing smaller does.
+pub fn minimize<F: FnMut(&str) -> Result<bool>>(input: &str, max_tries: usize, reproduces: F) -> Result<String> {
+    let mut minimizer = Minimizer {
+        lines: input.lines().map(|line| line.split_whitespace().map(str::to_owned).collect()).collect(),
+        tries_left: max_tries,
+        reduced: false,
+        reproduces,
+    };
+
+    minimizer.delete_lines()?;
+    for line_idx in 0..minimizer.lines.len() {
+        minimizer.delete_tokens(line_idx)?;
+    }
+    for line_idx in 0..minimizer.lines.len() {
+        for token_idx in 0..minimizer.lines[line_idx].len() {
+            minimizer.halve_number(line_idx, token_idx)?;
+        }
+    }
+    // the lines are joined with normalized whitespace, which only reproduces if some smaller input did
+    Ok(if minimizer.reduced { join_lines(&minimizer.lines) } else { input.to_owned() })
+}
+
+fn join_lines(lines: &[Vec<String>]) -> String {
+    lines.iter().map(|tokens| tokens.join(" ") + "\n").collect()
+}
+
+struct Minimizer<F: FnMut(&str) -> Result<bool>> {
+    /// Tokens of every line of the smallest input that reproduces the failure
+    lines: Vec<Vec<String>>,
+    tries_left: usize,
+    /// Whether any smaller input reproduced the failure
+    reduced: bool,
+    reproduces: F,
+}
+
+impl<F: FnMut(&str) -> Result<bool>> Minimizer<F> {
+    /// Whether the lines reproduce the failure, they are taken if they do.
+    fn try_lines(&mut self, lines: Vec<Vec<String>>) -> Result<bool> {
+        if self.tries_left == 0 {
+            return Ok(false);
+        }
+        self.tries_left -= 1;
+        let reproduces = (self.reproduces)(&join_lines(&lines))?;
+        if reproduces {
+            self.lines = lines;
+            self.reduced = true;
+        }
+        Ok(reproduces)
+    }
+
+    /// Deletes chunks of lines, starting with halves of the input and going down to single lines.
+    fn delete_lines(&mut self) -> Result<()> {
+        let mut chunk = self.lines.len() / 2;
+        while chunk > 0 && self.tries_left > 0 {
+            let mut start = 0;
+            while start < self.lines.len() {
+                let mut lines = self.lines.clone();
+                lines.drain(start..(start + chunk).min(lines.len()));
+                if !self.try_lines(lines)? {
+                    start += chunk;
+                }
+            }
+            chunk /= 2;
+        }
+        Ok(())
+    }
+
+    /// Deletes chunks of tokens of a line, like `delete_lines`.
+    fn delete_tokens(&mut self, line_idx: usize) -> Result<()> {
+        let mut chunk = self.lines[line_idx].len() / 2;
+        while chunk > 0 && self.tries_left > 0 {
+            let mut start = 0;
+            while start < self.lines[line_idx].len() {
+                let mut lines = self.lines.clone();
+                let tokens = &mut lines[line_idx];
+                tokens.drain(start..(start + chunk).min(tokens.len()));
+                if !self.try_lines(lines)? {
+                    start += chunk;
+                }
+            }
+            chunk /= 2;
+        }
+        Ok(())
+    }
+
+    /// Halves a number token for as long as the failure reproduces.
+    fn halve_number(&mut self, line_idx: usize, token_idx: usize) -> Result<()> {
+        while let Ok(value) = self.lines[line_idx][token_idx].parse::<i64>()
+            && value != 0
+        {
+            let mut lines = self.lines.clone();
+            lines[line_idx][token_idx] = (value / 2).to_string();
+            if !self.try_lines(lines)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
-use std::collections::HashSet;
+use crate::{Error, Language, Result, Verdict};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 
 /// A solution implementation (correct or partial) to be tested.
 ///
@@ -8,10 +11,18 @@ use std::collections::HashSet;
 /// different solution implementations.
 pub struct Solution {
     pub name: String,
-    /// The C++ source code for the solution.
+    /// The source code for the solution.
     pub source: String,
+    /// File the source code is loaded from. If set, `source` is ignored.
+    pub source_file: Option<PathBuf>,
+    /// The language the source code is written in.
+    pub language: Language,
     /// Indices of the subtasks this solution is designed to pass.
     pub passes_subtasks: HashSet<usize>,
+    /// Expected score (fraction of the points in `[0, 1]`) for some subtasks.
+    pub expected_scores: HashMap<usize, RangeInclusive<f64>>,
+    /// Verdicts the solution may get on the tests of some subtasks, besides `OK`.
+    pub expected_verdicts: HashMap<usize, BTreeSet<Verdict>>,
 }
 
 impl Solution {
@@ -25,13 +36,98 @@ impl Solution {
         Self {
             name,
             source,
+            source_file: None,
+            language: Language::Cpp,
             passes_subtasks: passes_subtasks.iter().copied().collect(),
+            expected_scores: HashMap::new(),
+            expected_verdicts: HashMap::new(),
         }
     }
 
+    /// Creates a new `Solution` whose source code is loaded from a file.
+    /// The language is detected from the file extension, an unsupported extension is an error.
+    pub fn from_file(name: String, path: &Path, passes_subtasks: &[usize]) -> Result<Self> {
+        Ok(Self {
+            name,
+            source: String::new(),
+            source_file: Some(path.to_owned()),
+            language: detect_language(path)?,
+            passes_subtasks: passes_subtasks.iter().copied().collect(),
+            expected_scores: HashMap::new(),
+            expected_verdicts: HashMap::new(),
+        })
+    }
+
+    /// Returns the source code, reading it from `source_file` if it is set.
+    pub(crate) fn load_source(&self) -> Result<String> {
+        self.source_file.as_ref().map_or_else(|| Ok(self.source.clone()), |path| read_source_file(path))
+    }
+
+    /// Sets the language of the source code (C++ by default).
+    #[must_use]
+    pub const fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Sets the range of the score (fraction of the subtask points) the solution
+    /// is expected to get on a subtask, for tasks with partial scoring.
+    ///
+    /// A range starting at 1 means the solution passes the subtask. If the range
+    /// ends below 1, tests are searched on which the solution does not get full points.
+    #[must_use]
+    pub fn with_expected_score(mut self, subtask: usize, score: RangeInclusive<f64>) -> Self {
+        if *score.start() >= 1.0 {
+            self.passes_subtasks.insert(subtask);
+        } else {
+            self.passes_subtasks.remove(&subtask);
+        }
+        self.expected_scores.insert(subtask, score);
+        self
+    }
+
+    /// Sets the verdicts the solution is expected to fail a subtask with, for example only `Verdict::TimedOut`
+    /// for a brute force that is slow but never wrong. Other verdicts than these and `OK` are an error,
+    /// and only tests with one of these verdicts are chosen as robust tests for the solution.
+    #[must_use]
+    pub fn with_expected_verdicts(mut self, subtask: usize, verdicts: &[Verdict]) -> Self {
+        self.passes_subtasks.remove(&subtask);
+        self.expected_verdicts.insert(subtask, verdicts.iter().copied().collect());
+        self
+    }
+
+    /// Returns `true` if the verdict on a test of the subtask is `OK` or one of the expected verdicts of the subtask.
+    #[must_use]
+    pub fn is_expected_verdict(&self, subtask: usize, verdict: Verdict) -> bool {
+        verdict == Verdict::Ok || self.expected_verdicts.get(&subtask).is_none_or(|verdicts| verdicts.contains(&verdict))
+    }
+
+    /// Returns the expected verdicts of a subtask as text, like `TLE or MLE`.
+    pub(crate) fn expected_verdicts_text(&self, subtask: usize) -> String {
+        self.expected_verdicts
+            .get(&subtask)
+            .map(|verdicts| verdicts.iter().map(|verdict| verdict.short_name()).collect::<Vec<_>>().join(" or "))
+            .unwrap_or_default()
+    }
+
     /// Returns `true` if this solution is expected to fail on the specified subtask.
+    /// A solution that may or may not get full points on the subtask is not expected to fail.
     #[must_use]
     pub fn should_fail(&self, subtask: usize) -> bool {
-        !self.passes_subtasks.contains(&subtask)
+        !self.passes_subtasks.contains(&subtask) && self.expected_scores.get(&subtask).is_none_or(|score| *score.end() < 1.0)
     }
 }
+
+/// Detects the language of a source file from its extension.
+pub fn detect_language(path: &Path) -> Result<Language> {
+    Language::from_path(path).ok_or_else(|| Error::UnsupportedLanguage {
+        file: path.to_string_lossy().into_owned(),
+    })
+}
+
+pub fn read_source_file(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|err| Error::IOError {
+        err,
+        file: path.to_string_lossy().into_owned(),
+    })
+}
@@ -1,9 +1,34 @@
-use crate::test::TestGenerator;
+use crate::params::{ParamSpace, Params};
+use crate::test::{GeneratorRng, TestGenerator};
 
 use crate::to_output::ToOutput;
 use rand::RngExt;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
+/// How the scores of the tests in a subtask are combined into the points of the subtask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScoreAggregation {
+    /// The subtask gets the lowest score of its tests (all-or-nothing for accept/reject checkers).
+    #[default]
+    Min,
+    /// Every test is worth an equal share of the subtask points.
+    Sum,
+}
+
+impl ScoreAggregation {
+    /// Combines test scores (each in `[0, 1]`) into the score of the subtask.
+    pub(crate) fn aggregate(self, scores: &[f64]) -> f64 {
+        if scores.is_empty() {
+            return 1.0;
+        }
+        match self {
+            Self::Min => scores.iter().copied().fold(1.0, f64::min),
+            Self::Sum => scores.iter().sum::<f64>() / scores.len() as f64,
+        }
+    }
+}
+
 /// Represents a problem subtask with specific constraints.
 ///
 /// A subtask contains one or more test generators that produce input data
@@ -21,10 +46,23 @@ pub struct Subtask<T: ToOutput> {
     /// It may be ran many times (even 1000) to really make sure all solutions are correct.
     /// By default it is disabled, because it can take a lot of time.
     pub(crate) stress_tests: i32,
+    /// How test scores are combined into the subtask points
+    pub(crate) aggregation: ScoreAggregation,
     /// Checker is a function that is executed when a test is generated.
     /// It should panic when the test is not within constraints.
     /// By default it does nothing.
     checker: fn(&T),
+    /// Validators of the test input as it is written to the test file.
+    /// They return an error message when the input is not within constraints.
+    validators: Vec<Box<dyn Fn(&str) -> Result<(), String>>>,
+    /// Source code of C++ validator programs (testlib-style), see `with_validator_source`.
+    pub(crate) validator_sources: Vec<String>,
+    /// Files with C++ validator programs, see `with_validator_file`.
+    pub(crate) validator_files: Vec<PathBuf>,
+    /// Directories with hand-written tests, see `with_tests_from_dir`.
+    pub(crate) test_dirs: Vec<PathBuf>,
+    /// Indices of earlier subtasks whose tests are also tests of this subtask
+    pub(crate) dependencies: Vec<usize>,
 }
 
 impl<T: ToOutput> Default for Subtask<T> {
@@ -44,7 +82,13 @@ impl<T: ToOutput> Subtask<T> {
             initial_counts: Vec::new(),
             min_failures_per_solution: None,
             stress_tests: 0,
+            aggregation: ScoreAggregation::Min,
             checker: |_| {},
+            validators: Vec::new(),
+            validator_sources: Vec::new(),
+            validator_files: Vec::new(),
+            test_dirs: Vec::new(),
+            dependencies: Vec::new(),
         }
     }
 
@@ -52,14 +96,63 @@ impl<T: ToOutput> Subtask<T> {
     ///
     /// * `count` - Initial number of tests to generate from this generator.
     /// * `function` - A closure that returns a generated input string.
+    ///
+    /// The closure does not get the seed of the task, use `with_seeded_test` for reproducible tests.
     #[must_use]
-    pub fn with_test<F: Fn() -> T + 'static>(mut self, count: i32, function: F) -> Self {
-        let generator = Rc::new(TestGenerator::new(function));
-        self.generators.push(generator);
+    pub fn with_test<F: Fn() -> T + 'static>(self, count: i32, function: F) -> Self {
+        self.with_generator(count, TestGenerator::new(function))
+    }
+
+    /// Adds a seeded test generator to the subtask.
+    ///
+    /// * `count` - Initial number of tests to generate from this generator.
+    /// * `function` - A closure that generates a test using only the given random number generator,
+    ///   so that the same task seed always produces the same tests.
+    #[must_use]
+    pub fn with_seeded_test<F: Fn(&mut GeneratorRng) -> T + 'static>(self, count: i32, function: F) -> Self {
+        self.with_generator(count, TestGenerator::new_seeded(function))
+    }
+
+    /// Adds a parameterized test generator to the subtask.
+    ///
+    /// * `count` - Initial number of tests to generate from this generator.
+    /// * `space` - Ranges of the parameters, for example the size of the input or the maximum value.
+    /// * `function` - A closure that generates a test with the given values of the parameters,
+    ///   using only the given random number generator like in `with_seeded_test`.
+    ///
+    /// Initial tests take parameters uniformly from the space. The search for robust tests also changes
+    /// the parameters of tests that broke a bad solution, to find more tests like them.
+    /// The parameters of every test are written into `seeds.txt`.
+    #[must_use]
+    pub fn with_param_test<F: Fn(&Params, &mut GeneratorRng) -> T + 'static>(self, count: i32, space: ParamSpace, function: F) -> Self {
+        self.with_generator(count, TestGenerator::new_param(space, function))
+    }
+
+    fn with_generator(mut self, count: i32, generator: TestGenerator<T>) -> Self {
+        self.generators.push(Rc::new(generator));
         self.initial_counts.push(count as usize);
         self
     }
 
+    /// Adds hand-written tests from a directory. Every `.in` file is a test input, which is validated and deduplicated
+    /// like generated tests. If there is an `.out` or `.ans` file with the same name, the main solution has to pass it
+    /// and it is used as the output, otherwise the output of the main solution is used.
+    /// The original file names are listed in `manifest.txt` in the problem directory.
+    #[must_use]
+    pub fn with_tests_from_dir(mut self, path: &Path) -> Self {
+        self.test_dirs.push(path.to_owned());
+        self
+    }
+
+    /// Includes the tests of an earlier subtask (by index, starting at 0) in this subtask, for example
+    /// the tests of a subtask with small constraints in the subtask with full constraints.
+    /// Dependencies are transitive and the subtask is scored on its own tests together with the included ones.
+    #[must_use]
+    pub fn with_dependency(mut self, subtask_idx: usize) -> Self {
+        self.dependencies.push(subtask_idx);
+        self
+    }
+
     /// Override custom `min_failures_per_solution`
     #[must_use]
     pub const fn with_min_failures(mut self, min_failures: usize) -> Self {
@@ -67,6 +160,13 @@ impl<T: ToOutput> Subtask<T> {
         self
     }
 
+    /// Sets how the scores of the tests are combined into the subtask points.
+    #[must_use]
+    pub const fn with_aggregation(mut self, aggregation: ScoreAggregation) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
     #[must_use]
     pub const fn do_stress_test(mut self, num_tests: i32) -> Self {
         self.stress_tests = num_tests;
@@ -84,22 +184,76 @@ impl<T: ToOutput> Subtask<T> {
         self
     }
 
-    pub(crate) fn generate_test(&self, gen_idx: usize) -> T {
-        let res = self.generators[gen_idx].generate();
-        (self.checker)(&res);
-        res
+    /// Adds a validator of the test input string (after serialization and whitespace trimming).
+    /// It returns `Err` with a message when the input does not satisfy the constraints.
+    #[must_use]
+    pub fn with_validator<F: Fn(&str) -> Result<(), String> + 'static>(mut self, validator: F) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Adds a C++ validator program (testlib-style).
+    /// It reads the test input from stdin and exits with a non-zero code if it is invalid,
+    /// writing the reason to stderr.
+    #[must_use]
+    pub fn with_validator_source(mut self, source: &str) -> Self {
+        self.validator_sources.push(source.to_owned());
+        self
+    }
+
+    /// Adds a C++ validator program loaded from a file, see `with_validator_source`.
+    #[must_use]
+    pub fn with_validator_file(mut self, path: &Path) -> Self {
+        self.validator_files.push(path.to_owned());
+        self
+    }
+
+    /// Returns the generator with the given index.
+    pub(crate) fn get_generator(&self, gen_idx: usize) -> &TestGenerator<T> {
+        &self.generators[gen_idx]
+    }
+
+    /// Runs the validator closures on a test input.
+    pub(crate) fn validate(&self, input: &str) -> Result<(), String> {
+        self.validators.iter().try_for_each(|validator| validator(input))
+    }
+
+    /// Generates the input of a test with the given generator and seed.
+    /// Tests from seeded generators can be reproduced from the seed recorded in `seeds.txt`.
+    /// Parameterized generators take the parameters picked from the seed, see `generate_test_with_params`.
+    #[must_use]
+    pub fn generate_test(&self, gen_idx: usize, seed: u64) -> String {
+        self.generate_test_with_params(gen_idx, seed, &self.params_from_seed(gen_idx, seed))
+    }
+
+    /// Generates the input of a test with the given generator, seed and values of the parameters,
+    /// so tests of parameterized generators can be reproduced from the seed and parameters recorded in `seeds.txt`.
+    #[must_use]
+    pub fn generate_test_with_params(&self, gen_idx: usize, seed: u64, params: &Params) -> String {
+        self.generators[gen_idx].generate_input(seed, params, self.checker)
+    }
+
+    /// Parameters that `generate_test` uses for the seed, empty if the generator is not parameterized.
+    pub(crate) fn params_from_seed(&self, gen_idx: usize, seed: u64) -> Params {
+        self.generators[gen_idx].params_from_seed(seed)
+    }
+
+    /// Changes the parameters of a test of a parameterized generator, see `ParamSpace::mutate`.
+    pub(crate) fn mutate_params(&self, gen_idx: usize, params: &Params, rng: &mut GeneratorRng) -> Params {
+        self.generators[gen_idx].space.mutate(params, rng)
     }
 
     /// Randomly selects one of the registered generators and produces a test input.
+    /// Returns the input, the index of the generator and the seed of the test.
     ///
     /// Returns `None` if no generators are registered.
-    pub(crate) fn generate_random_test(&self) -> Option<(T, usize)> {
+    pub(crate) fn generate_random_test(&self, rng: &mut GeneratorRng) -> Option<(String, usize, u64)> {
         if self.generators.is_empty() {
             return None;
         }
 
-        let mut rng = rand::rng();
         let idx = rng.random_range(0..self.generators.len());
-        Some((self.generate_test(idx), idx))
+        let seed = rng.random();
+        Some((self.generate_test(idx, seed), idx, seed))
     }
 }
@@ -1,15 +1,21 @@
-use crate::solution::Solution;
+use crate::solution::{Solution, detect_language, read_source_file};
 use crate::subtask::Subtask;
 use crate::{Error, Result};
 
-use crate::archiver::archive_files;
+use crate::archiver::{archive_named_files, named_by_file_name};
+use crate::create_tests::{TestRecord, TestSource};
+use crate::export::ExportFormat;
 use crate::logger_format::logger_format;
-use crate::runner::cpp_runner::CppRunner;
+use crate::report::{SolutionReport, TaskReport, Verdict};
+use crate::runner::checker::CheckerVerdict;
+use crate::runner::cpp_runner::{CppRunner, ProgramHandle};
+use crate::runner::language::Language;
 use crate::to_output::ToOutput;
 use console::style;
 use indicatif::MultiProgress;
 use indicatif_log_bridge::LogWrapper;
 use log::{LevelFilter, debug, error, info, warn};
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
 use std::fs::OpenOptions;
 use std::io::Write;
@@ -18,11 +24,44 @@ use std::sync::Once;
 
 pub static LOGGER_INIT: Once = Once::new();
 
+/// Compiled programs of a task, ready to be run.
+pub struct TaskPrograms {
+    pub cpp_runner: CppRunner,
+    pub checker_handle: Option<ProgramHandle>,
+    /// Source code of the main solution, also if it is given as a file
+    pub solution_source: String,
+    pub solution_handle: ProgramHandle,
+    /// Validator programs of every subtask
+    pub validator_handles: Vec<Vec<ProgramHandle>>,
+    /// Partial solutions in the same order as `Task::solutions`
+    pub solution_handles: Vec<ProgramHandle>,
+    pub solution_sources: Vec<String>,
+    /// Time limit for judging the solutions, it differs from `Task::time_limit` after `Task::calibrate_time_limit`
+    pub time_limit: i32,
+}
+
+/// Input and output files of the samples and of the tests of every subtask.
+pub struct TestFiles {
+    pub samples: Vec<(PathBuf, PathBuf)>,
+    pub subtasks: Vec<Vec<(PathBuf, PathBuf)>>,
+}
+
 // Convert a Path to an owned String for error contexts and logs
 pub fn path_str(p: &Path) -> String {
     p.to_string_lossy().into_owned()
 }
 
+/// Time limit that is `multiplier` times `max_time`, rounded up to a positive multiple of `rounding`.
+pub fn auto_time_limit(max_time: i32, multiplier: f64, rounding: i32) -> i32 {
+    let rounding = rounding.max(1);
+    let time_limit = (f64::from(max_time) * multiplier).ceil() as i32;
+    ((time_limit + rounding - 1) / rounding).max(1) * rounding
+}
+
+pub fn file_name(p: &Path) -> String {
+    p.file_name().map_or_else(String::new, |name| name.to_string_lossy().into_owned())
+}
+
 /// This struct represents an entire task.
 /// You can add subtasks, solutions (main and partial) and set the time limit.
 /// Once you are done, you can create tests for the task.
@@ -43,8 +82,16 @@ pub struct Task<T: ToOutput> {
     pub(crate) tests_path: PathBuf,
     /// Time limit in milliseconds for solutions
     pub(crate) time_limit: i32,
+    /// Memory limit in megabytes for solutions, 0 means unlimited (1024 by default)
+    pub(crate) memory_limit: i32,
+    /// Multiplier of the slowest run of the main solution and the rounding of the time limit, see `with_auto_time_limit`
+    pub(crate) auto_time_limit: Option<(f64, i32)>,
+    /// Overrides of the per-language time limit multipliers
+    pub(crate) time_limit_multipliers: HashMap<Language, f32>,
     /// Path to the final ZIP archive containing all tests
     pub(crate) tests_archive_path: PathBuf,
+    /// Format of the output package, a plain ZIP archive of tests by default
+    pub(crate) export_format: ExportFormat,
     /// Closure to determine input file names: `(test_id, subtask_id, id_in_subtask) -> String`
     pub(crate) get_input_file_name: Box<dyn Fn(i32, i32, i32) -> String>,
     /// Closure to determine output file names: `(test_id, subtask_id, id_in_subtask) -> String`
@@ -53,19 +100,41 @@ pub struct Task<T: ToOutput> {
     pub(crate) build_folder_path: PathBuf,
     /// Registered subtasks
     pub(crate) subtasks: Vec<Subtask<T>>,
+    /// Sample tests with optional expected outputs, they come before all subtasks
+    pub(crate) samples: Vec<(String, Option<String>)>,
     /// Source code of the correct (main) solution
     pub(crate) solution_source: String,
+    /// Language of the correct (main) solution
+    pub(crate) solution_language: Language,
+    /// File the correct (main) solution is loaded from, if it is given as a file
+    pub(crate) solution_file: Option<PathBuf>,
     /// Partial solutions to be verified against subtasks
     pub(crate) solutions: Vec<Solution>,
     /// Target number of failures per "bad" solution per subtask
     pub(crate) min_failures_per_solution: usize,
     /// Maximum number of consecutive failed attempts to find a robust test
     pub(crate) max_tries: usize,
+    /// Maximum number of smaller inputs that are tried when minimizing a failing test, 0 disables minimization
+    pub(crate) max_minimize_tries: usize,
+    /// Seed for test generation, a random one is chosen if it is not set
+    pub(crate) seed: Option<u64>,
+    /// If the tests of unchanged subtasks are reused from the previous run
+    pub(crate) test_cache: bool,
     /// Test checker, used for problems with multiple different possible outputs.
-    /// By default it is a diff checker (up to whitespace).
+    /// If it is not set, a diff checker (up to whitespace) is used.
     /// The function takes 3 arguments: (`test_input`, `correct_output`, `program_output`)
     /// and returns `true` if the program output is accepted (correct), `false` if rejected.
-    pub(crate) checker: fn(&str, &str, &str) -> bool,
+    pub(crate) checker: Option<fn(&str, &str, &str) -> bool>,
+    /// Checker for partial scoring, used instead of `checker` if set.
+    /// It takes the same arguments and returns the score (fraction of the points in `[0, 1]`) and a message.
+    pub(crate) scoring_checker: Option<fn(&str, &str, &str) -> (f64, String)>,
+    /// Source code of the checker program (testlib-compatible), used instead of `checker` if set.
+    pub(crate) checker_source: Option<String>,
+    /// File with the checker program, used instead of `checker` if set.
+    pub(crate) checker_file: Option<PathBuf>,
+    /// Source code of the interactor for interactive tasks.
+    /// If it is set, the checker is not used and the interactor decides the verdict.
+    pub(crate) interactor_source: Option<String>,
     /// If you want to automatically trim whitespace from outputs
     pub(crate) trim_whitespace: bool,
 
@@ -117,18 +186,32 @@ impl<T: ToOutput> Task<T> {
             problem_path: path.to_owned(),
             tests_path: path.join("tests"),
             tests_archive_path: path.join("tests.zip"),
+            export_format: ExportFormat::Zip,
             get_input_file_name: Box::new(|test_id, subtask_id, _test_id_in_subtask| format!("test.{:02}.{:03}.in", subtask_id + 1, test_id + 1)),
             get_output_file_name: Box::new(|test_id, subtask_id, _test_id_in_subtask| format!("test.{:02}.{:03}.out", subtask_id + 1, test_id + 1)),
             build_folder_path,
             time_limit: 5000,
+            memory_limit: 1024,
+            auto_time_limit: None,
+            time_limit_multipliers: HashMap::new(),
             subtasks: Vec::new(),
+            samples: Vec::new(),
             solutions: Vec::new(),
             min_failures_per_solution: 5,
             max_tries: 100,
+            max_minimize_tries: 500,
+            seed: None,
+            test_cache: false,
             debug_level: LevelFilter::Info,
             logger: MultiProgress::new(),
             solution_source: String::new(),
-            checker: diff_checker,
+            solution_language: Language::Cpp,
+            solution_file: None,
+            checker: None,
+            scoring_checker: None,
+            checker_source: None,
+            checker_file: None,
+            interactor_source: None,
             trim_whitespace: true,
         }
     }
@@ -138,11 +221,13 @@ impl<T: ToOutput> Task<T> {
     }
 
     pub(crate) fn log_result(&self, text: &str) -> Result<()> {
+        let results_file = self.get_results_file();
         let mut file = OpenOptions::new()
+            .create(true)
             .append(true)
-            .open(self.get_results_file())
-            .unwrap();
-        writeln!(file, "{}", strip_ansi(text)).map_err(|e| Error::IOError { err: e, file: path_str(&self.get_results_file()) })?;
+            .open(&results_file)
+            .map_err(|err| Error::IOError { err, file: path_str(&results_file) })?;
+        writeln!(file, "{}", strip_ansi(text)).map_err(|err| Error::IOError { err, file: path_str(&results_file) })?;
         info!("{text}");
         Ok(())
     }
@@ -151,19 +236,108 @@ impl<T: ToOutput> Task<T> {
     ///
     /// Panics if it is called the second time.
     #[must_use]
-    pub fn with_solution_source(mut self, source: &str) -> Self {
-        assert!(self.solution_source.is_empty());
+    pub fn with_solution_source(self, source: &str) -> Self {
+        self.with_solution_source_lang(source, Language::Cpp)
+    }
+
+    /// Sets the source code and the language of the correct (main) solution.
+    ///
+    /// Panics if it is called the second time.
+    #[must_use]
+    pub fn with_solution_source_lang(mut self, source: &str, language: Language) -> Self {
+        assert!(self.solution_source.is_empty() && self.solution_file.is_none());
         self.solution_source = source.to_owned();
+        self.solution_language = language;
         self
     }
 
+    /// Sets the file with the correct (main) solution.
+    /// The language is detected from the file extension and the file is read when the task runs.
+    /// An unsupported extension is an error.
+    ///
+    /// Panics if it is called the second time.
+    pub fn with_solution_file(mut self, path: &Path) -> Result<Self> {
+        assert!(self.solution_source.is_empty() && self.solution_file.is_none());
+        self.solution_language = detect_language(path)?;
+        self.solution_file = Some(path.to_owned());
+        Ok(self)
+    }
+
     /// Sets custom checker
     #[must_use]
     pub fn with_checker(mut self, checker: fn(&str, &str, &str) -> bool) -> Self {
-        self.checker = checker;
+        self.checker = Some(checker);
+        self
+    }
+
+    /// Makes the task interactive with the given C++ interactor.
+    ///
+    /// The interactor is run as `interactor <input_file> <output_file>` with its stdin and stdout
+    /// connected to the solution. It reads the test from `input_file`, talks to the solution and
+    /// exits with code 0 if the solution is correct and with a non-zero code otherwise.
+    /// Whatever it writes into `output_file` is saved as the test output.
+    /// Time and memory limits apply only to the solution.
+    #[must_use]
+    pub fn with_interactor(mut self, source: &str) -> Self {
+        self.interactor_source = Some(source.to_owned());
+        self
+    }
+
+    /// Sets a checker for partial scoring.
+    ///
+    /// The checker takes (`test_input`, `correct_output`, `program_output`) and returns
+    /// the score of the output as a fraction of the points in `[0, 1]` together with a message.
+    /// Test scores are combined into subtask points as set by `Subtask::with_aggregation`.
+    #[must_use]
+    pub fn with_scoring_checker(mut self, checker: fn(&str, &str, &str) -> (f64, String)) -> Self {
+        self.scoring_checker = Some(checker);
+        self
+    }
+
+    /// Sets the source code of a C++ checker program.
+    ///
+    /// The checker is run as `checker input output answer`, like testlib checkers.
+    /// It exits with 0 if the output is accepted, 1 on wrong answer, 2 on presentation error
+    /// and 3 if it fails itself. Its stderr message is attached to the verdict.
+    /// Partial points are given with exit code 7 (testlib `quitp`), the value is read as the fraction
+    /// of the points of the test in `[0, 1]`, not as absolute points, so a checker for a judge that
+    /// expects absolute points has to divide them by the points of the test.
+    /// Percentages of testlib `_pc` (exit codes from 16) are not supported and count as a failed checker.
+    /// The compiled checker is also put into the tests archive.
+    #[must_use]
+    pub fn with_checker_source(mut self, source: &str) -> Self {
+        self.checker_source = Some(source.to_owned());
+        self
+    }
+
+    /// Sets the file with a C++ checker program, see `with_checker_source`.
+    /// The file is compiled in place, so a `testlib.h` next to it can be included.
+    #[must_use]
+    pub fn with_checker_file(mut self, path: &Path) -> Self {
+        self.checker_file = Some(path.to_owned());
         self
     }
 
+    /// Judges a program output.
+    /// For interactive tasks the interactor already accepted the output, so the checker is skipped.
+    pub(crate) fn check_output(&self, runner: &CppRunner, input: &str, correct_output: &str, program_output: &str) -> Result<CheckerVerdict> {
+        if self.interactor_source.is_some() {
+            return Ok(CheckerVerdict::Accepted);
+        }
+        if let Some(verdict) = runner.run_checker(input, program_output, correct_output)? {
+            return Ok(verdict);
+        }
+        if let Some(scoring_checker) = self.scoring_checker {
+            let (score, message) = scoring_checker(input, correct_output, program_output);
+            return CheckerVerdict::from_score(score, message);
+        }
+        Ok(if self.checker.unwrap_or(diff_checker)(input, correct_output, program_output) {
+            CheckerVerdict::Accepted
+        } else {
+            CheckerVerdict::WrongAnswer(String::new())
+        })
+    }
+
     /// Internal helper to get input file path.
     pub(crate) fn get_input_file_path(&self, test_id: i32, subtask_id: i32, test_id_in_subtask: i32) -> PathBuf {
         self.tests_path.join((self.get_input_file_name)(test_id, subtask_id, test_id_in_subtask))
@@ -181,6 +355,21 @@ impl<T: ToOutput> Task<T> {
         self
     }
 
+    /// Adds a sample test, which is saved as `sample.01.in`, `sample.02.in`, ... before the tests of the subtasks.
+    /// Its output is produced by the main solution and every solution (including partial ones) has to pass it.
+    #[must_use]
+    pub fn with_sample(mut self, input: T) -> Self {
+        self.samples.push((input.to_output(), None));
+        self
+    }
+
+    /// Adds a sample test with its expected output, which the main solution has to produce.
+    #[must_use]
+    pub fn with_sample_output(mut self, input: T, output: &str) -> Self {
+        self.samples.push((input.to_output(), Some(output.to_owned())));
+        self
+    }
+
     #[must_use]
     pub const fn trim_whitespace(mut self, trim_whitespace: bool) -> Self {
         self.trim_whitespace = trim_whitespace;
@@ -191,8 +380,48 @@ impl<T: ToOutput> Task<T> {
     ///
     /// * `passes_subtasks` - List of subtask indices this solution is expected to pass.
     #[must_use]
-    pub fn with_partial_solution(mut self, name: &str, source: &str, passes_subtasks: &[usize]) -> Self {
-        self.solutions.push(Solution::new(name.to_owned(), source.to_owned(), passes_subtasks));
+    pub fn with_partial_solution(self, name: &str, source: &str, passes_subtasks: &[usize]) -> Self {
+        self.with_partial_solution_lang(name, source, Language::Cpp, passes_subtasks)
+    }
+
+    /// Adds a solution (partial or incorrect) in C++ that may fail some subtasks only with the given verdicts,
+    /// see `Solution::with_expected_verdicts`.
+    ///
+    /// * `passes_subtasks` - List of subtask indices this solution is expected to pass.
+    /// * `expected_verdicts` - Subtask indices with the verdicts the solution is expected to fail them with, besides `OK`.
+    #[must_use]
+    pub fn with_partial_solution_verdicts(self, name: &str, source: &str, passes_subtasks: &[usize], expected_verdicts: &[(usize, &[Verdict])]) -> Self {
+        let solution = Solution::new(name.to_owned(), source.to_owned(), passes_subtasks);
+        self.with_partial(
+            expected_verdicts
+                .iter()
+                .fold(solution, |solution, &(subtask, verdicts)| solution.with_expected_verdicts(subtask, verdicts)),
+        )
+    }
+
+    /// Adds a solution (partial or incorrect) written in the given language.
+    ///
+    /// * `passes_subtasks` - List of subtask indices this solution is expected to pass.
+    #[must_use]
+    pub fn with_partial_solution_lang(mut self, name: &str, source: &str, language: Language, passes_subtasks: &[usize]) -> Self {
+        self.solutions.push(Solution::new(name.to_owned(), source.to_owned(), passes_subtasks).with_language(language));
+        self
+    }
+
+    /// Adds a solution (partial or incorrect) loaded from a file.
+    /// The language is detected from the file extension, an unsupported extension is an error.
+    ///
+    /// * `passes_subtasks` - List of subtask indices this solution is expected to pass.
+    pub fn with_partial_solution_file(mut self, name: &str, path: &Path, passes_subtasks: &[usize]) -> Result<Self> {
+        self.solutions.push(Solution::from_file(name.to_owned(), path, passes_subtasks)?);
+        Ok(self)
+    }
+
+    /// Adds an already built solution (partial or incorrect) to be verified,
+    /// for example one with expected scores set by `Solution::with_expected_score`.
+    #[must_use]
+    pub fn with_partial(mut self, solution: Solution) -> Self {
+        self.solutions.push(solution);
         self
     }
 
@@ -210,6 +439,40 @@ impl<T: ToOutput> Task<T> {
         self
     }
 
+    /// Sets the maximum number of smaller inputs that are tried when minimizing a failing test, 0 disables minimization.
+    /// When a solution fails on a generated test, it is saved to `failing_test.in` and the smallest input found
+    /// on which the solution fails the same way is saved to `failing_test_min.in`. The default is 500.
+    #[must_use]
+    pub const fn with_max_minimize_tries(mut self, n: usize) -> Self {
+        self.max_minimize_tries = n;
+        self
+    }
+
+    /// Sets the seed for test generation.
+    ///
+    /// With the same seed, seeded generators (`Subtask::with_seeded_test`) produce the same tests
+    /// in the same order, so the tests archive is identical. Without it, a random seed is chosen
+    /// and written to `seeds.txt` together with the seed of every test.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Enables the test cache in `build/tests_cache`, so the tests of a subtask are only generated again
+    /// if something they depend on changed: the seed, its generators, validators, hand-written tests,
+    /// the checker, the limits or the solutions that have to pass or fail it. The solutions are still run on all tests.
+    ///
+    /// The cache is useful only with a fixed seed (`with_seed`). Changes in the code of Rust closures
+    /// can not be detected, so the tests of seeded generators are generated again and validated before they
+    /// are reused. Subtasks with a generator that is not seeded (`Subtask::with_test`) and all subtasks of a task
+    /// with a checker written in Rust (`with_checker`, `with_scoring_checker`) are not cached, a warning is logged.
+    #[must_use]
+    pub const fn with_test_cache(mut self, test_cache: bool) -> Self {
+        self.test_cache = test_cache;
+        self
+    }
+
     /// Sets the directory for build artifacts.
     #[must_use]
     pub fn with_build_folder_path(mut self, path: PathBuf) -> Self {
@@ -231,6 +494,33 @@ impl<T: ToOutput> Task<T> {
         self
     }
 
+    /// Derives the time limit from the main solution: its slowest run on the generated tests times `multiplier`,
+    /// rounded up to a multiple of `rounding` milliseconds. The partial solutions are judged with this limit
+    /// and it is written into the report and the exported packages. The search for robust tests judges the bad solutions
+    /// with the limit derived from the slowest run of the main solution so far, and a solution that passes a subtask
+    /// it should fail with the final limit is an error.
+    /// The time limit set with `with_time_limit` is still the limit of every run while the tests are generated and when the main solution is timed.
+    #[must_use]
+    pub const fn with_auto_time_limit(mut self, multiplier: f64, rounding: i32) -> Self {
+        self.auto_time_limit = Some((multiplier, rounding));
+        self
+    }
+
+    /// Sets the memory limit in megabytes for solutions, 0 means unlimited.
+    /// The default is 1024 MB, solutions that use more get the MLE verdict.
+    #[must_use]
+    pub const fn with_memory_limit(mut self, limit: i32) -> Self {
+        self.memory_limit = limit;
+        self
+    }
+
+    /// Sets the factor by which the time limit is multiplied for solutions in `language`.
+    #[must_use]
+    pub fn with_time_limit_multiplier(mut self, language: Language, multiplier: f32) -> Self {
+        self.time_limit_multipliers.insert(language, multiplier);
+        self
+    }
+
     /// Sets the path to the final ZIP archive containing all tests.
     #[must_use]
     pub fn with_tests_archive_path(mut self, path: PathBuf) -> Self {
@@ -238,6 +528,16 @@ impl<T: ToOutput> Task<T> {
         self
     }
 
+    /// Sets the format of the output package.
+    ///
+    /// `ExportFormat::Zip` (default) writes the tests into the tests archive.
+    /// Other formats write a package for the judging system into the problem directory instead.
+    #[must_use]
+    pub const fn with_export_format(mut self, format: ExportFormat) -> Self {
+        self.export_format = format;
+        self
+    }
+
     /// Sets the closure to determine input file names.
     #[must_use]
     pub fn with_get_input_file_name<F: Fn(i32, i32, i32) -> String + 'static>(mut self, f: F) -> Self {
@@ -260,7 +560,12 @@ impl<T: ToOutput> Task<T> {
     }
 
     /// Executes the task: compiles solutions, generates robust tests, and verifies outcomes.
-    pub fn run(self) -> Result<()> {
+    pub fn run(self) -> Result<TaskReport> {
+        self.run_command(Self::create_tests_inner)
+    }
+
+    /// Initializes the logger and runs a command, printing its error or the elapsed time.
+    pub(crate) fn run_command<R, F: FnOnce(&Self) -> Result<R>>(&self, command: F) -> Result<R> {
         LOGGER_INIT.call_once(|| {
             let mut builder = env_logger::builder();
             builder.filter(None, self.debug_level);
@@ -273,14 +578,17 @@ impl<T: ToOutput> Task<T> {
         });
 
         let start_time = std::time::Instant::now();
-        let res = self.create_tests_inner();
-        if let Err(err) = res {
-            error!("{}", style(&err).red().bright());
-            Err(err)
-        } else {
-            info!("Elapsed time: {}", style(format!("{:.2}s", start_time.elapsed().as_secs_f32())).bold());
-            self.logger.println(format!("{}", style("Success!").green().bright().bold())).ok();
-            Ok(())
+        let res = command(self);
+        match res {
+            Err(err) => {
+                error!("{}", style(&err).red().bright());
+                Err(err)
+            }
+            Ok(value) => {
+                info!("Elapsed time: {}", style(format!("{:.2}s", start_time.elapsed().as_secs_f32())).bold());
+                self.logger.println(format!("{}", style("Success!").green().bright().bold())).ok();
+                Ok(value)
+            }
         }
     }
 
@@ -300,15 +608,28 @@ impl<T: ToOutput> Task<T> {
     }
 
     /// This function builds solution and then calls `generate_tests`.
-    #[allow(clippy::too_many_lines)]
-    fn create_tests_inner(&self) -> Result<()> {
-        self.logger.println("").ok();
-        let text = format!("Creating tests for task \"{}\"", self.name);
-        self.print_title(&text);
+    fn create_tests_inner(&self) -> Result<TaskReport> {
+        let mut programs = self.prepare_programs("Creating tests")?;
+        let (test_files, mut report) = self.generate_tests(&mut programs)?;
+        let result = self
+            .verify_solutions(&mut programs, &test_files, &mut report.solutions)
+            .and_then(|()| self.package_tests(&programs, &test_files))
+            .and_then(|()| self.log_test_counts(&test_files));
+        report.time_limit = programs.time_limit;
+        report.set_test_generators();
+        // the report is also written if a solution fails, with the results up to the failing solution
+        self.write_report(&report)?;
+        result.map(|()| report)
+    }
+
+    /// Prints the title of the command, checks the task and compiles all of its programs.
+    pub(crate) fn prepare_programs(&self, title: &str) -> Result<TaskPrograms> {
+        self.print_command_title(title);
 
         if self.subtasks.is_empty() {
             warn!("No subtasks defined.");
         }
+        self.check_dependencies()?;
 
         // create build directory if it doesn't exist
         if !self.build_folder_path.exists() {
@@ -318,17 +639,136 @@ impl<T: ToOutput> Task<T> {
             })?;
         }
 
+        // add all source files (solution and partial solutions)
+        let (mut cpp_runner, checker_handle, interactor_handle) = self.create_runner()?;
+        let (solution_source, solution_handle) = self.add_main_solution(&mut cpp_runner, interactor_handle)?;
+        let mut validator_handles = Vec::new();
+        for subtask in &self.subtasks {
+            let mut subtask_validator_handles = Vec::new();
+            for validator_source in &subtask.validator_sources {
+                subtask_validator_handles.push(cpp_runner.add_program(validator_source)?);
+            }
+            for validator_file in &subtask.validator_files {
+                subtask_validator_handles.push(cpp_runner.add_program_file(validator_file, &read_source_file(validator_file)?, Language::Cpp)?);
+            }
+            validator_handles.push(subtask_validator_handles);
+        }
+        let mut solution_handles = Vec::new();
+        let mut solution_sources = Vec::new();
+        for solution in &self.solutions {
+            let source = solution.load_source()?;
+            let handle = if let Some(source_file) = &solution.source_file {
+                cpp_runner.add_program_file(source_file, &source, solution.language)?
+            } else {
+                cpp_runner.add_program_lang(&source, solution.language)?
+            };
+            cpp_runner.set_interactor(handle, interactor_handle);
+            solution_handles.push(handle);
+            solution_sources.push(source);
+        }
+
+        Ok(TaskPrograms {
+            cpp_runner,
+            checker_handle,
+            solution_source,
+            solution_handle,
+            validator_handles,
+            solution_handles,
+            solution_sources,
+            time_limit: self.time_limit,
+        })
+    }
+
+    /// Prints the title of a command with the name of the task.
+    pub(crate) fn print_command_title(&self, title: &str) {
+        self.logger.println("").ok();
+        let text = format!("{title} for task \"{}\"", self.name);
+        self.print_title(&text);
+    }
+
+    /// Adds the main solution to the runner and returns its source with its handle.
+    fn add_main_solution(&self, cpp_runner: &mut CppRunner, interactor_handle: Option<ProgramHandle>) -> Result<(String, ProgramHandle)> {
         // check if solution source exists
-        if self.solution_source.is_empty() {
+        if self.solution_source.is_empty() && self.solution_file.is_none() {
             return Err(Error::MissingSolution {});
         }
-        // add all cpp files (solution and partial solutions)
+        let (solution_source, solution_handle) = if let Some(solution_file) = &self.solution_file {
+            let solution_source = read_source_file(solution_file)?;
+            let solution_handle = cpp_runner.add_program_file(solution_file, &solution_source, self.solution_language)?;
+            (solution_source, solution_handle)
+        } else {
+            (self.solution_source.clone(), cpp_runner.add_program_lang(&self.solution_source, self.solution_language)?)
+        };
+        cpp_runner.set_interactor(solution_handle, interactor_handle);
+        Ok((solution_source, solution_handle))
+    }
+
+    /// Creates the runner with the time limit multipliers, the checker and the interactor of the task.
+    /// Returns the runner with the handles of the checker and the interactor, if there are any.
+    /// Solutions are added by the caller, which makes them run against the interactor.
+    fn create_runner(&self) -> Result<(CppRunner, Option<ProgramHandle>, Option<ProgramHandle>)> {
         let mut cpp_runner = CppRunner::new(&self.build_folder_path)?;
-        let solution_handle = cpp_runner.add_program(&self.solution_source)?;
-        let mut solution_handles = Vec::new();
-        for solution in &self.solutions {
-            solution_handles.push(cpp_runner.add_program(&solution.source)?);
+        for (&language, &multiplier) in &self.time_limit_multipliers {
+            cpp_runner.set_time_limit_multiplier(language, multiplier);
         }
+        let checker_handle = if let Some(checker_file) = &self.checker_file {
+            Some(cpp_runner.add_program_file(checker_file, &read_source_file(checker_file)?, Language::Cpp)?)
+        } else {
+            self.checker_source.as_ref().map(|checker_source| cpp_runner.add_program(checker_source)).transpose()?
+        };
+        cpp_runner.set_checker(checker_handle);
+        let interactor_handle = self.interactor_source.as_ref().map(|interactor_source| cpp_runner.add_program(interactor_source)).transpose()?;
+        Ok((cpp_runner, checker_handle, interactor_handle))
+    }
+
+    /// Judges a solution from a file on tests that already exist, with the checker and the limits of the task.
+    /// With `with_auto_time_limit`, the time limit is calibrated from the main solution on the tests first.
+    /// The language is detected from the file extension.
+    ///
+    /// * `tests_path` - Directory with the tests.
+    /// * `subtask_tests` - Input and output files of the tests of every subtask (relative to `tests_path`), in the order of subtasks.
+    pub fn judge_solution(&self, solution_file: &Path, tests_path: &Path, subtask_tests: &[Vec<(PathBuf, PathBuf)>]) -> Result<SolutionReport> {
+        if subtask_tests.len() != self.subtasks.len() {
+            return Err(Error::WrongSubtaskCount {
+                expected: self.subtasks.len(),
+                found: subtask_tests.len(),
+            });
+        }
+        let language = detect_language(solution_file)?;
+        let test_files: Vec<Vec<(PathBuf, PathBuf)>> = subtask_tests
+            .iter()
+            .map(|tests| tests.iter().map(|(input_file, output_file)| (tests_path.join(input_file), tests_path.join(output_file))).collect())
+            .collect();
+        self.judge_solution_file(solution_file, language, &test_files).map(|(report, _)| report)
+    }
+
+    /// Judges a solution from a file on the tests of the last run (listed in `layout.txt`), see `judge_solution`.
+    pub fn judge_solution_on_tests(&self, solution_file: &Path) -> Result<SolutionReport> {
+        let language = detect_language(solution_file)?;
+        let test_files = self.read_layout()?;
+        self.judge_solution_file(solution_file, language, &test_files.subtasks).map(|(report, _)| report)
+    }
+
+    /// Judges a solution from a file in `language` on the given tests, returns its report and the time limit it was judged with.
+    pub(crate) fn judge_solution_file(&self, solution_file: &Path, language: Language, test_files: &[Vec<(PathBuf, PathBuf)>]) -> Result<(SolutionReport, i32)> {
+        let (mut cpp_runner, _, interactor_handle) = self.create_runner()?;
+        let handle = cpp_runner.add_program_file(solution_file, &read_source_file(solution_file)?, language)?;
+        cpp_runner.set_interactor(handle, interactor_handle);
+        let time_limit = if self.auto_time_limit.is_some() {
+            let (_, solution_handle) = self.add_main_solution(&mut cpp_runner, interactor_handle)?;
+            self.calibrated_time_limit(test_files, &mut cpp_runner, solution_handle)?
+        } else {
+            self.time_limit
+        };
+        Ok((self.judge_program(&file_name(solution_file), test_files, &mut cpp_runner, handle, time_limit)?, time_limit))
+    }
+
+    /// Replaces the tests in the tests directory with newly generated samples and tests of every subtask.
+    /// Returns the report of the task without the results of the solutions.
+    pub(crate) fn generate_tests(&self, programs: &mut TaskPrograms) -> Result<(TestFiles, TaskReport)> {
+        // clear log file, the other commands append to the results of the last generation
+        let results_file = self.get_results_file();
+        fs::File::create(&results_file).map_err(|err| Error::IOError { err, file: path_str(&results_file) })?;
 
         // Prepare test directory
         if self.tests_path.exists() {
@@ -342,50 +782,288 @@ impl<T: ToOutput> Task<T> {
             file: path_str(&self.tests_path),
         })?;
 
-        // clear log file
-        fs::File::create(self.get_results_file()).map_err(|e| Error::IOError { err: e, file: path_str(&self.get_results_file()) })?;
+        let seed = self.get_seed();
+        self.log_result(&format!("Seed: {seed}"))?;
+
+        let sample_files = self.create_samples(&programs.solution_handles, programs.solution_handle, &mut programs.cpp_runner)?;
 
         let num_subtasks = self.subtasks.len();
         let mut global_test_id = 0;
         let mut all_test_files = Vec::new();
+        let mut test_records = Vec::new();
+        let mut subtask_reports = Vec::new();
+        let mut slowest_main_run = 0;
 
         for (subtask_idx, subtask) in self.subtasks.iter().enumerate() {
             self.print_progress((subtask_idx + 1) as i32, num_subtasks as i32, &format!("Subtask {}: {}", subtask_idx + 1, subtask.name));
-            self.create_tests_for_subtask(subtask_idx, subtask, &mut global_test_id, &mut all_test_files, &solution_handles, solution_handle, &mut cpp_runner)?;
+            let subtask_report = self.create_tests_for_subtask(
+                subtask_idx,
+                subtask,
+                &mut global_test_id,
+                &mut all_test_files,
+                &mut test_records,
+                seed,
+                &programs.solution_handles,
+                programs.solution_handle,
+                &programs.validator_handles[subtask_idx],
+                &mut programs.cpp_runner,
+                &mut slowest_main_run,
+            )?;
+            subtask_reports.push(subtask_report);
         }
+        self.write_seeds(seed, &test_records)?;
+        self.write_manifest(&test_records)?;
+        let test_files = TestFiles {
+            samples: sample_files,
+            subtasks: all_test_files,
+        };
+        self.write_layout(&test_files)?;
+        let report = TaskReport {
+            name: self.name.clone(),
+            seed,
+            time_limit: self.time_limit,
+            memory_limit: self.memory_limit,
+            samples: test_files.samples.len(),
+            tests_size: fs_extra::dir::get_size(&self.tests_path).unwrap_or(0),
+            subtasks: subtask_reports,
+            solutions: Vec::new(),
+        };
+        Ok((test_files, report))
+    }
 
+    /// Runs the main solution and all partial solutions on the tests and checks that they pass the expected subtasks.
+    /// Adds the reports of the main solution and of the partial solutions to `reports`.
+    pub(crate) fn verify_solutions(&self, programs: &mut TaskPrograms, test_files: &TestFiles, reports: &mut Vec<SolutionReport>) -> Result<()> {
+        self.calibrate_time_limit(programs, test_files)?;
         self.log_result("Running official solution:")?;
-        self.run_partial_solution(&all_test_files, &mut cpp_runner, solution_handle, self.solution_source.split('\n').count())?;
+        self.run_partial_solution(
+            &test_files.subtasks,
+            &mut programs.cpp_runner,
+            programs.solution_handle,
+            None,
+            programs.solution_source.split('\n').count(),
+            programs.time_limit,
+            reports,
+        )?;
 
-        for (i, partial) in solution_handles.iter().enumerate() {
+        for (i, partial) in programs.solution_handles.iter().enumerate() {
             self.log_result(&format!("Running partial solution {}: {}", i + 1, self.solutions[i].name))?;
-            self.run_partial_solution(&all_test_files, &mut cpp_runner, *partial, self.solutions[i].source.split('\n').count())?;
+            self.run_partial_solution(
+                &test_files.subtasks,
+                &mut programs.cpp_runner,
+                *partial,
+                Some(i),
+                programs.solution_sources[i].split('\n').count(),
+                programs.time_limit,
+                reports,
+            )?;
         }
+        Ok(())
+    }
 
-        self.archive_tests(&all_test_files)?;
+    /// With `with_auto_time_limit`, times the main solution on the tests and sets the time limit of the programs from its slowest run.
+    pub(crate) fn calibrate_time_limit(&self, programs: &mut TaskPrograms, test_files: &TestFiles) -> Result<()> {
+        programs.time_limit = self.calibrated_time_limit(&test_files.subtasks, &mut programs.cpp_runner, programs.solution_handle)?;
+        Ok(())
+    }
+
+    /// Time limit from the slowest run of the main solution on the tests with `with_auto_time_limit`, otherwise the time limit of the task.
+    fn calibrated_time_limit(&self, test_files: &[Vec<(PathBuf, PathBuf)>], cpp_runner: &mut CppRunner, solution_handle: ProgramHandle) -> Result<i32> {
+        let Some((multiplier, rounding)) = self.auto_time_limit else {
+            return Ok(self.time_limit);
+        };
+        let report = self.judge_program("main", test_files, cpp_runner, solution_handle, self.time_limit)?;
+        let max_time = report.subtasks.iter().flat_map(|subtask| &subtask.tests).filter_map(|test| test.time).max().unwrap_or(0);
+        let time_limit = auto_time_limit(max_time, multiplier, rounding);
+        self.log_result(&format!("Time limit: {time_limit}ms (slowest run of the main solution: {max_time}ms)"))?;
+        Ok(time_limit)
+    }
+
+    /// Time limit that the bad solutions are judged with in the search for robust tests. With `with_auto_time_limit`,
+    /// it is calibrated from the slowest run of the main solution so far, the final time limit is checked in `verify_solutions`.
+    pub(crate) fn search_time_limit(&self, slowest_main_run: i32) -> i32 {
+        self.auto_time_limit
+            .map_or(self.time_limit, |(multiplier, rounding)| auto_time_limit(slowest_main_run, multiplier, rounding))
+    }
+
+    /// Writes the tests into the tests archive or, for other export formats, into the package for the judging system.
+    pub(crate) fn package_tests(&self, programs: &TaskPrograms, test_files: &TestFiles) -> Result<()> {
+        self.export_package(self.export_format, programs, test_files)
+    }
 
+    /// Logs the size of the tests and the number of tests of every subtask.
+    pub(crate) fn log_test_counts(&self, test_files: &TestFiles) -> Result<()> {
         let tests_size = fs_extra::dir::get_size(&self.tests_path).unwrap_or(0) as f32 / 1_000_000.0;
         self.log_result(&format!("Tests size: {}", style(format!("{tests_size:.2}MB")).bold()))?;
 
         // Log test counts per subtask
-        for (i, tests) in all_test_files.iter().enumerate() {
+        if !test_files.samples.is_empty() {
+            self.log_result(&format!("Samples: {} tests", test_files.samples.len()))?;
+        }
+        for (i, tests) in test_files.subtasks.iter().enumerate() {
             self.log_result(&format!("Subtask {}: {} tests", i + 1, tests.len()))?;
         }
 
         Ok(())
     }
 
-    /// Archive all tests into a zip file
-    fn archive_tests(&self, test_files: &[Vec<(PathBuf, PathBuf)>]) -> Result<()> {
+    /// The seed of the task, a random one if it is not set.
+    pub(crate) fn get_seed(&self) -> u64 {
+        self.seed.unwrap_or_else(rand::random)
+    }
+
+    /// Indices of the subtask and all subtasks it includes (also through other subtasks), in increasing order.
+    pub(crate) fn included_subtasks(&self, subtask_idx: usize) -> Vec<usize> {
+        let mut included = BTreeSet::from([subtask_idx]);
+        let mut stack = vec![subtask_idx];
+        while let Some(idx) = stack.pop() {
+            for &dependency in &self.subtasks[idx].dependencies {
+                if included.insert(dependency) {
+                    stack.push(dependency);
+                }
+            }
+        }
+        included.into_iter().collect()
+    }
+
+    /// Checks that subtasks depend only on earlier subtasks and that partial solutions
+    /// are expected to pass all subtasks included in the subtasks they should pass.
+    fn check_dependencies(&self) -> Result<()> {
+        for (subtask_idx, subtask) in self.subtasks.iter().enumerate() {
+            if let Some(&dependency) = subtask.dependencies.iter().find(|&&dependency| dependency >= subtask_idx) {
+                return Err(Error::InvalidDependency {
+                    subtask_number: subtask_idx + 1,
+                    dependency_number: dependency + 1,
+                });
+            }
+        }
+        for (solution_idx, solution) in self.solutions.iter().enumerate() {
+            let mut passes_subtasks: Vec<usize> = solution.passes_subtasks.iter().copied().filter(|&idx| idx < self.subtasks.len()).collect();
+            passes_subtasks.sort_unstable();
+            for subtask_idx in passes_subtasks {
+                if let Some(dependency) = self.included_subtasks(subtask_idx).into_iter().find(|dependency| !solution.passes_subtasks.contains(dependency)) {
+                    return Err(Error::PartialSolutionDependency {
+                        partial_number: solution_idx + 1,
+                        partial_name: solution.name.clone(),
+                        subtask_number: subtask_idx + 1,
+                        dependency_number: dependency + 1,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the seed of the task and of every generated test into `seeds.txt`.
+    fn write_seeds(&self, seed: u64, test_records: &[TestRecord]) -> Result<()> {
+        let mut text = format!("# task seed {seed}\n# test subtask generator seed [parameters]\n");
+        for test_record in test_records {
+            if let TestSource::Generator { gen_idx, seed, params } = &test_record.source {
+                let line = format!("{} {} {} {seed} {params}", file_name(&test_record.input_path), test_record.subtask_idx + 1, gen_idx + 1);
+                text += line.trim_end();
+                text += "\n";
+            }
+        }
+        let seeds_path = self.problem_path.join("seeds.txt");
+        fs::write(&seeds_path, text).map_err(|err| Error::IOError { err, file: path_str(&seeds_path) })
+    }
+
+    /// Writes the report into `report.json` and `report.html`.
+    fn write_report(&self, report: &TaskReport) -> Result<()> {
+        let report_path = self.problem_path.join("report.json");
+        fs::write(&report_path, report.to_json()).map_err(|err| Error::IOError { err, file: path_str(&report_path) })?;
+        let html_path = self.problem_path.join("report.html");
+        fs::write(&html_path, report.to_html(&self.problem_path)).map_err(|err| Error::IOError { err, file: path_str(&html_path) })
+    }
+
+    /// Writes the original file of every hand-written test into `manifest.txt`.
+    fn write_manifest(&self, test_records: &[TestRecord]) -> Result<()> {
+        let mut text = "# test subtask original file\n".to_owned();
+        for test_record in test_records {
+            if let TestSource::File(path) = &test_record.source {
+                text += &format!("{} {} {}\n", file_name(&test_record.input_path), test_record.subtask_idx + 1, path_str(path));
+            }
+        }
+        let manifest_path = self.problem_path.join("manifest.txt");
+        fs::write(&manifest_path, text).map_err(|err| Error::IOError { err, file: path_str(&manifest_path) })
+    }
+
+    /// Writes the samples (subtask 0) and the tests of every subtask into `layout.txt`,
+    /// so that the tests can be found again without generating them.
+    fn write_layout(&self, test_files: &TestFiles) -> Result<()> {
+        let mut text = "# subtask input output\n".to_owned();
+        for (subtask_number, tests) in std::iter::once(&test_files.samples).chain(&test_files.subtasks).enumerate() {
+            for (input_file, output_file) in tests {
+                text += &format!("{subtask_number} {} {}\n", self.tests_relative_path(input_file), self.tests_relative_path(output_file));
+            }
+        }
+        let layout_path = self.get_layout_file();
+        fs::write(&layout_path, text).map_err(|err| Error::IOError { err, file: path_str(&layout_path) })
+    }
+
+    /// Reads the tests written by the last test generation from `layout.txt`.
+    pub(crate) fn read_layout(&self) -> Result<TestFiles> {
+        let layout_path = self.get_layout_file();
+        if !layout_path.is_file() {
+            return Err(Error::MissingTests { file: path_str(&layout_path) });
+        }
+        let text = fs::read_to_string(&layout_path).map_err(|err| Error::IOError { err, file: path_str(&layout_path) })?;
+        let mut test_files = TestFiles {
+            samples: Vec::new(),
+            subtasks: vec![Vec::new(); self.subtasks.len()],
+        };
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let fields: Vec<&str> = line.split(' ').collect();
+            let subtask_number = fields.first().and_then(|field| field.parse::<usize>().ok());
+            let (Some(subtask_number), [_, input_file, output_file]) = (subtask_number, fields.as_slice()) else {
+                return Err(Error::InvalidLayout {
+                    file: path_str(&layout_path),
+                    line: line.to_owned(),
+                });
+            };
+            let tests = if subtask_number == 0 {
+                &mut test_files.samples
+            } else if let Some(tests) = test_files.subtasks.get_mut(subtask_number - 1) {
+                tests
+            } else {
+                return Err(Error::InvalidLayout {
+                    file: path_str(&layout_path),
+                    line: line.to_owned(),
+                });
+            };
+            tests.push((self.tests_path.join(input_file), self.tests_path.join(output_file)));
+        }
+        Ok(test_files)
+    }
+
+    fn get_layout_file(&self) -> PathBuf {
+        self.problem_path.join("layout.txt")
+    }
+
+    /// Path of a test file relative to the tests directory.
+    fn tests_relative_path(&self, path: &Path) -> String {
+        path_str(path.strip_prefix(&self.tests_path).unwrap_or(path))
+    }
+
+    /// Archive the samples, all tests and the checker (source and executable), if there is one, into a zip file
+    pub(crate) fn archive_tests(&self, programs: &TaskPrograms, test_files: &TestFiles) -> Result<()> {
         let mut test_files_vec = Vec::new();
-        for subtask in test_files {
+        for subtask in std::iter::once(test_files.samples.as_slice()).chain(test_files.subtasks.iter().map(Vec::as_slice)) {
             for (input_file, output_file) in subtask {
-                test_files_vec.push(input_file.clone());
-                test_files_vec.push(output_file.clone());
+                test_files_vec.push(named_by_file_name(input_file));
+                test_files_vec.push(named_by_file_name(output_file));
             }
         }
+        if let Some(checker_handle) = programs.checker_handle {
+            let executable_path = programs.cpp_runner.get_executable_path(checker_handle).to_owned();
+            let executable_name = executable_path
+                .extension()
+                .map_or_else(|| "checker".to_owned(), |extension| format!("checker.{}", extension.to_string_lossy()));
+            test_files_vec.push((programs.cpp_runner.get_source_path(checker_handle).to_owned(), "checker.cpp".to_owned()));
+            test_files_vec.push((executable_path, executable_name));
+        }
 
-        archive_files(&test_files_vec, &self.tests_archive_path, &self.logger)?;
+        archive_named_files(&test_files_vec, &self.tests_archive_path, &self.logger)?;
 
         Ok(())
     }
@@ -1,17 +1,72 @@
+use crate::params::{ParamSpace, Params};
 use crate::to_output::ToOutput;
+use rand::SeedableRng;
+use rand::rngs::ChaCha8Rng;
+use std::any::TypeId;
+
+/// Random number generator passed to seeded test generators.
+///
+/// It is seeded from the task seed, so the same seed always produces the same tests.
+pub type GeneratorRng = ChaCha8Rng;
+
+/// Mixed into the seed of a test to pick the parameters of a parameterized generator,
+/// so the parameters do not take values from the random number generator of the test.
+const PARAMS_SEED: u64 = 0x5DEE_CE66_D1CE_4E5B;
 
 /// A struct that represents a test generator.
 /// It contains a function that generates a test.
 pub struct TestGenerator<T: ToOutput> {
-    function: Box<dyn Fn() -> T>,
+    function: Box<dyn Fn(&Params, &mut GeneratorRng) -> T>,
+    /// Parameters of the generator, empty if it is not parameterized
+    pub(crate) space: ParamSpace,
+    /// Type of the closure, it identifies the generator in the test cache
+    pub(crate) id: TypeId,
+    /// Whether the generator takes all of its randomness from the seed
+    pub(crate) seeded: bool,
 }
 
 impl<T: ToOutput> TestGenerator<T> {
+    /// Creates a generator that does its own randomness, so it ignores the seed.
     pub fn new<F: Fn() -> T + 'static>(function: F) -> Self {
-        Self { function: Box::new(function) }
+        Self {
+            function: Box::new(move |_, _| function()),
+            space: ParamSpace::new(),
+            id: TypeId::of::<F>(),
+            seeded: false,
+        }
+    }
+
+    /// Creates a generator that takes all of its randomness from the given generator.
+    pub fn new_seeded<F: Fn(&mut GeneratorRng) -> T + 'static>(function: F) -> Self {
+        Self {
+            function: Box::new(move |_, rng| function(rng)),
+            space: ParamSpace::new(),
+            id: TypeId::of::<F>(),
+            seeded: true,
+        }
+    }
+
+    /// Creates a seeded generator that also takes the values of the parameters in the space.
+    pub fn new_param<F: Fn(&Params, &mut GeneratorRng) -> T + 'static>(space: ParamSpace, function: F) -> Self {
+        Self {
+            function: Box::new(function),
+            space,
+            id: TypeId::of::<F>(),
+            seeded: true,
+        }
+    }
+
+    /// Parameters of the test with the given seed, picked uniformly from the space.
+    pub fn params_from_seed(&self, seed: u64) -> Params {
+        self.space.sample(&mut GeneratorRng::seed_from_u64(seed ^ PARAMS_SEED))
     }
 
-    pub fn generate(&self) -> T {
-        (self.function)()
+    /// Generates a test with the given values of the parameters, checks it and converts it to the input.
+    /// The conversion (for example the order of the edges of a `Graph`) continues with the random number generator of the test.
+    pub fn generate_input(&self, seed: u64, params: &Params, checker: fn(&T)) -> String {
+        let mut rng = GeneratorRng::seed_from_u64(seed);
+        let test = (self.function)(params, &mut rng);
+        checker(&test);
+        test.to_output_with_rng(&mut rng)
     }
 }
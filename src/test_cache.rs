@@ -0,0 +1,182 @@
+use crate::create_tests::{ManualTest, TestSource, trim_whitespace};
+use crate::params::Params;
+use crate::runner::cpp_runner::{CppRunner, ProgramHandle};
+use crate::task::path_str;
+use crate::{Error, Result, Task, ToOutput};
+use log::{debug, warn};
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Generated tests of a subtask: input, output and where the test comes from.
+pub type SubtaskTests = Vec<(String, String, TestSource)>;
+
+/// Programs that decide which tests are generated for a subtask.
+pub struct SubtaskPrograms<'handles> {
+    pub solution: ProgramHandle,
+    pub good_solutions: &'handles [(usize, ProgramHandle)],
+    pub bad_solutions: &'handles [(usize, ProgramHandle)],
+    pub validators: &'handles [ProgramHandle],
+}
+
+impl<T: ToOutput> Task<T> {
+    /// Directory where the tests of a subtask are cached.
+    pub(crate) fn test_cache_path(&self, subtask_idx: usize) -> PathBuf {
+        self.build_folder_path.join("tests_cache").join(format!("subtask{}", subtask_idx + 1))
+    }
+
+    /// Returns `false` with a warning if the tests of a subtask can not be cached, because something they depend on
+    /// could change without a change of the cache key: a checker written in Rust or a generator that is not seeded.
+    pub(crate) fn can_cache_tests(&self, subtask_idx: usize) -> bool {
+        let subtask = &self.subtasks[subtask_idx];
+        if self.checker.is_some() || self.scoring_checker.is_some() {
+            warn!(
+                "The tests of subtask {} are not cached, because changes of a checker written in Rust can not be detected",
+                subtask_idx + 1
+            );
+            return false;
+        }
+        if let Some(gen_idx) = (0..subtask.get_num_generators()).find(|&gen_idx| !subtask.get_generator(gen_idx).seeded) {
+            warn!(
+                "The tests of subtask {} are not cached, because generator {} is not seeded (use `with_seeded_test`)",
+                subtask_idx + 1,
+                gen_idx + 1
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Key of the cached tests of a subtask. It changes if anything that the generated tests depend on changes:
+    /// the seed, generators, solutions with their expected verdicts and scores, validators, checker, hand-written tests or the settings of the search.
+    /// Changes inside Rust closures are not seen, those are caught by `cached_tests_are_valid` for seeded generators and validators,
+    /// the tests are not cached at all with a Rust checker or a generator that is not seeded, see `can_cache_tests`.
+    pub(crate) fn test_cache_key(&self, subtask_idx: usize, seed: u64, programs: &SubtaskPrograms, manual_tests: &[ManualTest], cpp_runner: &CppRunner) -> Result<u64> {
+        let subtask = &self.subtasks[subtask_idx];
+        let mut hasher = DefaultHasher::new();
+        (seed, subtask_idx, &subtask.initial_counts, subtask.min_failures_per_solution, subtask.stress_tests).hash(&mut hasher);
+        (self.min_failures_per_solution, self.max_tries, self.trim_whitespace, self.time_limit, self.memory_limit).hash(&mut hasher);
+        self.auto_time_limit.map(|(multiplier, rounding)| (multiplier.to_bits(), rounding)).hash(&mut hasher);
+        self.time_limit_multipliers
+            .iter()
+            .map(|(language, multiplier)| (language.as_str(), multiplier.to_bits()))
+            .collect::<BTreeMap<_, _>>()
+            .hash(&mut hasher);
+
+        for gen_idx in 0..subtask.get_num_generators() {
+            let generator = subtask.get_generator(gen_idx);
+            (generator.id, generator.seeded, &generator.space).hash(&mut hasher);
+        }
+
+        cpp_runner.get_program_hash(programs.solution).hash(&mut hasher);
+        for &(_, handle) in programs.good_solutions {
+            cpp_runner.get_program_hash(handle).hash(&mut hasher);
+        }
+        "bad".hash(&mut hasher);
+        for &(_, handle) in programs.bad_solutions {
+            cpp_runner.get_program_hash(handle).hash(&mut hasher);
+        }
+        for &handle in programs.validators {
+            cpp_runner.get_program_hash(handle).hash(&mut hasher);
+        }
+        // the expectations decide which solutions are bad and which verdicts they may get on the robust tests
+        for solution in &self.solutions {
+            solution.expected_verdicts.iter().collect::<BTreeMap<_, _>>().hash(&mut hasher);
+            solution
+                .expected_scores
+                .iter()
+                .map(|(subtask, score)| (subtask, (score.start().to_bits(), score.end().to_bits())))
+                .collect::<BTreeMap<_, _>>()
+                .hash(&mut hasher);
+        }
+
+        (self.checker.is_some(), self.scoring_checker.is_some(), &self.checker_source, &self.interactor_source).hash(&mut hasher);
+        if let Some(checker_file) = &self.checker_file {
+            fs::read_to_string(checker_file).map_err(|err| Error::IOError { err, file: path_str(checker_file) })?.hash(&mut hasher);
+        }
+        manual_tests.hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+
+    /// Checks cached tests against the current generators and validators: tests of seeded generators
+    /// have to be generated again from their seed and every test has to pass the validators.
+    pub(crate) fn cached_tests_are_valid(&self, subtask_idx: usize, tests: &SubtaskTests, validators: &[ProgramHandle], cpp_runner: &CppRunner) -> Result<bool> {
+        let subtask = &self.subtasks[subtask_idx];
+        for (input, _, source) in tests {
+            if let TestSource::Generator { gen_idx, seed, params } = source
+                && *gen_idx < subtask.get_num_generators()
+                && subtask.get_generator(*gen_idx).seeded
+            {
+                if !subtask.get_generator(*gen_idx).space.contains(params) {
+                    debug!("Parameters of generator {} of subtask {} changed, the cached tests are not used", gen_idx + 1, subtask_idx + 1);
+                    return Ok(false);
+                }
+                let mut regenerated = subtask.generate_test_with_params(*gen_idx, *seed, params);
+                if self.trim_whitespace {
+                    regenerated = trim_whitespace(&regenerated);
+                }
+                if regenerated != *input {
+                    debug!("Generator {} of subtask {} changed, the cached tests are not used", gen_idx + 1, subtask_idx + 1);
+                    return Ok(false);
+                }
+            }
+            if let Err(message) = self.run_validators(input, subtask_idx, validators, cpp_runner)? {
+                debug!("Cached test of subtask {} is not valid anymore ({message}), the cached tests are not used", subtask_idx + 1);
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Reads the cached tests from the directory, if they were saved with the same key.
+pub fn read_cached_tests(dir: &Path, key: u64) -> Result<Option<SubtaskTests>> {
+    let read = |path: PathBuf| fs::read_to_string(&path).map_err(|err| Error::IOError { err, file: path_str(&path) });
+    let key_path = dir.join("key.txt");
+    if !key_path.is_file() || read(key_path)?.trim() != format!("{key:016x}") {
+        return Ok(None);
+    }
+
+    let mut tests = Vec::new();
+    for (test_idx, line) in read(dir.join("tests.txt"))?.lines().enumerate() {
+        let source = match line.split_once(' ') {
+            Some(("file", path)) => TestSource::File(PathBuf::from(path)),
+            Some(("generator", numbers)) => {
+                let mut numbers = numbers.splitn(3, ' ');
+                match (numbers.next().map(str::parse), numbers.next().map(str::parse), Params::parse(numbers.next().unwrap_or_default())) {
+                    (Some(Ok(gen_idx)), Some(Ok(seed)), Some(params)) => TestSource::Generator { gen_idx, seed, params },
+                    _ => return Ok(None),
+                }
+            }
+            _ => return Ok(None),
+        };
+        let input = read(dir.join(format!("{test_idx:03}.in")))?;
+        let output = read(dir.join(format!("{test_idx:03}.out")))?;
+        tests.push((input, output, source));
+    }
+    Ok(Some(tests))
+}
+
+/// Saves the tests of a subtask into the directory, replacing the tests cached before.
+pub fn write_cached_tests(dir: &Path, key: u64, tests: &SubtaskTests) -> Result<()> {
+    let write = |path: PathBuf, contents: &str| fs::write(&path, contents).map_err(|err| Error::IOError { err, file: path_str(&path) });
+    if dir.exists() {
+        fs::remove_dir_all(dir).map_err(|err| Error::IOError { err, file: path_str(dir) })?;
+    }
+    fs::create_dir_all(dir).map_err(|err| Error::IOError { err, file: path_str(dir) })?;
+
+    let mut index = String::new();
+    for (test_idx, (input, output, source)) in tests.iter().enumerate() {
+        match source {
+            TestSource::Generator { gen_idx, seed, params } => index += format!("generator {gen_idx} {seed} {params}").trim_end(),
+            TestSource::File(path) => index += &format!("file {}", path_str(path)),
+        }
+        index.push('\n');
+        write(dir.join(format!("{test_idx:03}.in")), input)?;
+        write(dir.join(format!("{test_idx:03}.out")), output)?;
+    }
+    write(dir.join("tests.txt"), &index)?;
+    // the key is written last, so tests that were not completely saved are never used
+    write(dir.join("key.txt"), &format!("{key:016x}\n"))
+}
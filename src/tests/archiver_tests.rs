@@ -1,17 +1,22 @@
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod archiver_tests {
-    use crate::Error;
-    use crate::archiver::archive_files;
+    use crate::archiver::{archive_named_files, named_by_file_name};
+    use crate::{Error, Result};
     use indicatif::MultiProgress;
     use std::io::Read;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use tempfile::TempDir;
 
     fn make_progress() -> MultiProgress {
         MultiProgress::new()
     }
 
+    fn archive_files(files: &[PathBuf], archive_path: &Path, logger: &MultiProgress) -> Result<()> {
+        let named_files = files.iter().map(|file| named_by_file_name(file)).collect::<Vec<_>>();
+        archive_named_files(&named_files, archive_path, logger)
+    }
+
     #[test]
     fn test_archive_empty_file_list() {
         let dir = TempDir::new().unwrap();
@@ -105,4 +110,18 @@ mod archiver_tests {
         expected.sort_unstable();
         assert_eq!(zip_names, expected);
     }
+
+    #[test]
+    fn test_archive_named_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("p1234.cpp");
+        std::fs::write(&path, "int main() {}").unwrap();
+
+        let archive_path = dir.path().join("named.zip");
+        archive_named_files(&[(path, "checker.cpp".to_owned())], &archive_path, &make_progress()).unwrap();
+
+        let archive_file = std::fs::File::open(&archive_path).unwrap();
+        let mut zip = zip::ZipArchive::new(archive_file).unwrap();
+        assert_eq!(zip.by_index(0).unwrap().name(), "checker.cpp");
+    }
 }
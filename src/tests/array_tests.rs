@@ -59,7 +59,8 @@ mod array_tests {
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod array_unit_tests {
-    use crate::{array_generator, array_to_string};
+    use crate::{GeneratorRng, array_generator, array_generator_custom_with_rng, array_generator_with_rng, array_to_string};
+    use rand::{RngExt, SeedableRng};
 
     #[test]
     fn test_array_to_string_empty_with_count() {
@@ -135,4 +136,16 @@ mod array_unit_tests {
             }
         }
     }
+
+    #[test]
+    fn test_array_generator_with_rng_is_seeded() {
+        let generator = array_generator_with_rng(1, 50, 1, 1000);
+        let first = generator(&mut GeneratorRng::seed_from_u64(3));
+        assert_eq!(generator(&mut GeneratorRng::seed_from_u64(3)), first);
+
+        let generator = array_generator_custom_with_rng(4, 4, |rng| rng.random_range(0..10) * 2);
+        let output = generator(&mut GeneratorRng::seed_from_u64(3));
+        assert_eq!(output, generator(&mut GeneratorRng::seed_from_u64(3)));
+        assert!(output.lines().nth(1).unwrap().split_whitespace().all(|value| value.parse::<i32>().unwrap() % 2 == 0));
+    }
 }
@@ -0,0 +1,82 @@
+/// Tests for the choice of generators in the robust test search (`GeneratorBandit`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod bandit_tests {
+    use crate::bandit::GeneratorBandit;
+    use crate::{Subtask, Task};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_best_generator() {
+        assert_eq!(GeneratorBandit::new(0).best_generator(), None);
+
+        let mut bandit = GeneratorBandit::new(3);
+        // untried generators are tried in order
+        assert_eq!(bandit.best_generator(), Some(0));
+        bandit.record(0, false);
+        assert_eq!(bandit.best_generator(), Some(1));
+        bandit.record(1, true);
+        assert_eq!(bandit.best_generator(), Some(1));
+        bandit.record(1, false);
+        bandit.record(1, false);
+        // 2/5 of generator 1 is below 1/2 of the untried generator 2
+        assert_eq!(bandit.best_generator(), Some(2));
+        bandit.record(2, false);
+        assert_eq!(bandit.best_generator(), Some(1));
+
+        let stats = bandit.into_stats();
+        assert_eq!((stats[1].tries, stats[1].hits), (3, 1));
+        assert!((stats[1].hit_rate() - 1.0 / 3.0).abs() < 1e-9);
+        assert!(stats[0].hit_rate().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_search_prefers_generators_that_break_solutions() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        let solution = "
+        #include <iostream>
+        int main() { int n; std::cin >> n; std::cout << n << std::endl; }
+        ";
+        // Fails only on large numbers
+        let bad_solution = "
+        #include <iostream>
+        int main() { int n; std::cin >> n; std::cout << (n >= 100 ? n + 1 : n) << std::endl; }
+        ";
+
+        let mut subtask = Subtask::new(100, "all");
+        for _ in 0..4 {
+            subtask = subtask.with_seeded_test(0, |rng| format!("{}\n", rng.random_range(0..100)));
+        }
+        subtask = subtask.with_seeded_test(0, |rng| format!("{}\n", rng.random_range(100..1_000_000)));
+
+        let report = Task::new("bandit", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_seed(1)
+            .with_solution_source(solution)
+            .with_subtask(subtask)
+            .with_partial_solution("bad", bad_solution, &[])
+            .with_min_failures(20)
+            .with_max_tries(100)
+            .run()
+            .unwrap();
+
+        let robust = report.subtasks[0].robust.as_ref().unwrap();
+        assert!(robust.is_complete());
+        let generators = &robust.generators;
+        assert_eq!(generators.len(), 5);
+        assert_eq!(generators[4].hits, 20);
+        assert!(generators[..4].iter().all(|generator| generator.hits == 0));
+        let other_tries: usize = generators[..4].iter().map(|generator| generator.tries).sum();
+        assert!(generators[4].tries > other_tries, "{generators:?}");
+        assert_eq!(generators.iter().map(|generator| generator.tries).sum::<usize>(), robust.tries);
+
+        let json = fs::read_to_string(task_path.join("report.json")).unwrap();
+        assert!(json.contains("\"generators\": [{\"tries\": "));
+        assert!(fs::read_to_string(task_path.join("report.html")).unwrap().contains("5: 20/"));
+    }
+}
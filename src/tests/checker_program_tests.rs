@@ -0,0 +1,192 @@
+/// Tests for compiled checker programs (`with_checker_source` / `with_checker_file`).
+///
+/// Checker programs follow the testlib convention: they are run as
+/// `checker input output answer` and exit with 0 (OK), 1 (WA), 2 (PE) or 3 (FAIL).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod checker_program_tests {
+    use crate::runner::checker::{CheckerVerdict, run_checker};
+    use crate::runner::cpp_runner::CppRunner;
+    use crate::tests::generic_tests::generic_tests::Test;
+    use crate::tests::test_shared::initialize_logger;
+    use crate::{Error, Subtask, Task};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    /// Accepts any divisor `d` of `n` with `1 < d < n`.
+    const DIVISOR_CHECKER: &str = r#"
+    #include <fstream>
+    #include <iostream>
+    using namespace std;
+    int main(int argc, char* argv[]) {
+        ifstream input(argv[1]), output(argv[2]), answer(argv[3]);
+        long long n, d;
+        input >> n;
+        if (!(output >> d)) {
+            cerr << "expected an integer";
+            return 2;
+        }
+        if (d <= 1 || d >= n || n % d != 0) {
+            cerr << d << " is not a proper divisor of " << n;
+            return 1;
+        }
+        return 0;
+    }
+    "#;
+
+    const SMALLEST_DIVISOR: &str = "
+    #include <iostream>
+    using namespace std;
+    int main() {
+        long long n;
+        cin >> n;
+        for (long long d = 2; ; d++) if (n % d == 0) { cout << d << endl; return 0; }
+    }
+    ";
+
+    const LARGEST_DIVISOR: &str = "
+    #include <iostream>
+    using namespace std;
+    int main() {
+        long long n;
+        cin >> n;
+        for (long long d = 2; ; d++) if (n % d == 0) { cout << n / d << endl; return 0; }
+    }
+    ";
+
+    const PRINTS_N: &str = "
+    #include <iostream>
+    using namespace std;
+    int main() {
+        long long n;
+        cin >> n;
+        cout << n << endl;
+    }
+    ";
+
+    fn composite_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{}\n", rng.random_range(2..1000) * rng.random_range(2..1000))
+    }
+
+    #[test]
+    fn checker_program_accepts_different_valid_outputs() {
+        let mut task = Test::new();
+
+        task.task = task
+            .task
+            .with_solution_source(SMALLEST_DIVISOR)
+            .with_checker_source(DIVISOR_CHECKER)
+            .with_subtask(Subtask::new(100, "").with_test(10, composite_generator))
+            // a different but valid answer passes thanks to the checker
+            .with_partial_solution("largest", LARGEST_DIVISOR, &[0])
+            .with_partial_solution("prints n", PRINTS_N, &[]);
+
+        task.test();
+    }
+
+    #[test]
+    fn checker_program_rejects_valid_partial_reported_as_failing() {
+        let mut task = Test::new();
+
+        task.task = task
+            .task
+            .with_solution_source(SMALLEST_DIVISOR)
+            .with_checker_source(DIVISOR_CHECKER)
+            .with_subtask(Subtask::new(100, "").with_test(10, composite_generator))
+            .with_partial_solution("prints n", PRINTS_N, &[0]);
+
+        let result = task.task.run();
+        assert!(
+            matches!(&result, Err(Error::PartialSolutionFailsSubtask { verdict, .. }) if verdict.starts_with("WA: ") && verdict.contains("is not a proper divisor")),
+            "got {result:?}"
+        );
+    }
+
+    #[test]
+    fn checker_program_verdicts() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+        let checker = runner.add_program(DIVISOR_CHECKER).unwrap();
+        runner.set_checker(Some(checker));
+
+        assert_eq!(runner.run_checker("12\n", "4\n", "2\n").unwrap(), Some(CheckerVerdict::Accepted));
+        assert_eq!(
+            runner.run_checker("12\n", "5\n", "2\n").unwrap(),
+            Some(CheckerVerdict::WrongAnswer("5 is not a proper divisor of 12".to_owned()))
+        );
+        assert_eq!(
+            runner.run_checker("12\n", "abc\n", "2\n").unwrap(),
+            Some(CheckerVerdict::PresentationError("expected an integer".to_owned()))
+        );
+
+        let failing_checker = runner.add_program("#include <iostream>\nint main() { std::cerr << \"bad answer file\"; return 3; }").unwrap();
+        runner.set_checker(Some(failing_checker));
+        assert!(matches!(runner.run_checker("", "", ""), Err(Error::CheckerFailed { code: Some(3), .. })));
+
+        // a checker that does not finish is killed after the timeout
+        let stuck_checker = runner.add_program("int main() { while (true) {} }").unwrap();
+        assert!(matches!(
+            run_checker(runner.get_executable_path(stuck_checker), "", "", "", Duration::from_millis(200)),
+            Err(Error::CheckerFailed { code: None, .. })
+        ));
+
+        runner.set_checker(None);
+        assert_eq!(runner.run_checker("", "", "").unwrap(), None);
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn checker_program_in_archive() {
+        initialize_logger();
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        // checker file includes a header next to it, like testlib.h
+        let checker_dir = tempdir.path().join("checker");
+        std::fs::create_dir_all(&checker_dir).unwrap();
+        std::fs::write(
+            checker_dir.join("divisor.h"),
+            "inline bool is_proper_divisor(long long n, long long d) { return d > 1 && d < n && n % d == 0; }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            checker_dir.join("checker.cpp"),
+            r#"
+            #include <fstream>
+            #include "divisor.h"
+            int main(int argc, char* argv[]) {
+                std::ifstream input(argv[1]), output(argv[2]);
+                long long n, d;
+                input >> n;
+                if (!(output >> d)) return 2;
+                return is_proper_divisor(n, d) ? 0 : 1;
+            }
+            "#,
+        )
+        .unwrap();
+
+        Task::new("checker archive", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_solution_source(SMALLEST_DIVISOR)
+            .with_checker_file(&checker_dir.join("checker.cpp"))
+            .with_subtask(Subtask::new(100, "").with_test(3, composite_generator))
+            .with_partial_solution("largest", LARGEST_DIVISOR, &[0])
+            .run()
+            .unwrap();
+
+        let archive_file = std::fs::File::open(task_path.join("tests.zip")).unwrap();
+        let mut zip = zip::ZipArchive::new(archive_file).unwrap();
+        let names = (0..zip.len()).map(|i| zip.by_index(i).unwrap().name().to_owned()).collect::<Vec<_>>();
+        assert!(names.iter().any(|name| name == "checker.cpp"), "archive: {names:?}");
+        assert!(names.iter().any(|name| name.starts_with("checker") && name != "checker.cpp"), "archive: {names:?}");
+        assert_eq!(names.len(), 3 * 2 + 2);
+
+        drop(tempdir);
+    }
+}
@@ -0,0 +1,131 @@
+/// Tests for the command-line interface (`cli::run`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod cli_tests {
+    use crate::{Error, Subtask, Task, cli};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Overflows for large numbers.
+    const INT_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    fn small_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))
+    }
+
+    fn large_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(2_000_000_000..3_000_000_000_i64), rng.random_range(2_000_000_000..3_000_000_000_i64))
+    }
+
+    fn task(path: &Path) -> Task<String> {
+        Task::new("cli", path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(40, "small").with_test(2, small_generator))
+            .with_subtask(Subtask::new(60, "large").with_test(2, large_generator))
+            .with_partial_solution("int", INT_SOLUTION, &[0])
+            .with_min_failures(1)
+    }
+
+    fn run(path: &Path, args: &[&str]) -> crate::Result<()> {
+        cli::run(task(path), args.iter().map(|&arg| arg.to_owned()))
+    }
+
+    #[test]
+    fn test_commands_on_generated_tests() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        run(&task_path, &["generate"]).unwrap();
+        assert!(!task_path.join("tests.zip").exists());
+        let layout = fs::read_to_string(task_path.join("layout.txt")).unwrap();
+        assert_eq!(layout.lines().next(), Some("# subtask input output"));
+        assert_eq!(layout.lines().filter(|line| line.starts_with("1 ")).count(), 2);
+        assert_eq!(layout.lines().filter(|line| line.starts_with("2 ")).count(), 2 + 1);
+        for line in layout.lines().skip(1) {
+            assert!(line.split(' ').skip(1).all(|file| task_path.join("tests").join(file).is_file()), "{line}");
+        }
+
+        run(&task_path, &["verify"]).unwrap();
+        // the results of the generation are kept, later commands append to them
+        let results = fs::read_to_string(task_path.join("results.txt")).unwrap();
+        assert!(results.starts_with("Seed: "), "{results}");
+        assert!(results.contains("Running partial solution 1: int"));
+
+        let solution_path = tempdir.path().join("submission.cpp");
+        fs::write(&solution_path, INT_SOLUTION).unwrap();
+        run(&task_path, &["run-solution", solution_path.to_str().unwrap()]).unwrap();
+        let results = fs::read_to_string(task_path.join("results.txt")).unwrap();
+        assert!(results.contains("Points 40/100"), "{results}");
+
+        run(&task_path, &["export", "--format", "cms"]).unwrap();
+        assert_eq!(fs::read_dir(task_path.join("cms").join("input")).unwrap().count(), 2 + 2 + 1);
+        run(&task_path, &["export"]).unwrap();
+        assert_eq!(zip::ZipArchive::new(fs::File::open(task_path.join("tests.zip")).unwrap()).unwrap().len(), 2 * (2 + 2 + 1));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_commands_need_generated_tests() {
+        let tempdir = TempDir::new().unwrap();
+
+        let result = run(tempdir.path(), &["verify"]);
+        assert!(matches!(&result, Err(Error::MissingTests { file }) if file.ends_with("layout.txt")), "got {result:?}");
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_stress_command() {
+        let tempdir = TempDir::new().unwrap();
+
+        run(tempdir.path(), &["stress", "1", "--count", "5"]).unwrap();
+        assert!(!tempdir.path().join("tests").exists());
+
+        // the partial solution has to pass the large subtask too
+        let result = cli::run(task(tempdir.path()).with_partial_solution("int large", INT_SOLUTION, &[0, 1]), ["stress".to_owned(), "2".to_owned()]);
+        assert!(
+            matches!(
+                &result,
+                Err(Error::PartialSolutionFailsSubtask {
+                    subtask_number: 2,
+                    partial_number: 2,
+                    ..
+                })
+            ),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let tempdir = TempDir::new().unwrap();
+
+        for args in [&["stress", "x"][..], &["stress", "3"], &["stress", "1", "--seed", "2"], &["export", "--format", "zip2"], &["build"]] {
+            let result = run(tempdir.path(), args);
+            assert!(matches!(result, Err(Error::InvalidArguments { .. })), "{args:?}: got {result:?}");
+        }
+        // the same error as from `Task::judge_solution_on_tests`
+        let result = run(tempdir.path(), &["run-solution", "a.java"]);
+        assert!(matches!(&result, Err(Error::UnsupportedLanguage { file }) if file.ends_with("a.java")), "got {result:?}");
+        run(tempdir.path(), &["--help"]).unwrap();
+
+        drop(tempdir);
+    }
+}
@@ -0,0 +1,186 @@
+/// Tests for the CMS task export (`Task::with_export_format(ExportFormat::Cms)`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod cms_tests {
+    #[cfg(unix)]
+    use crate::tests::test_shared::write_testlib_checker;
+    use crate::{Error, ExportFormat, Language, ScoreAggregation, Subtask, Task};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::fs;
+    #[cfg(unix)]
+    use std::path::Path;
+    #[cfg(unix)]
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    const PYTHON_SOLUTION: &str = "a, b = map(int, input().split())\nprint(a + b)\n";
+
+    fn generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))
+    }
+
+    /// Runs the exported checker and returns its stdout and stderr.
+    #[cfg(unix)]
+    fn run_checker(cms_path: &Path, correct_output: &str, contestant_output: &str) -> (String, String) {
+        let dir = cms_path.join("run");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("input"), "1 2\n").unwrap();
+        fs::write(dir.join("correct"), correct_output).unwrap();
+        fs::write(dir.join("contestant"), contestant_output).unwrap();
+        let output = Command::new(cms_path.join("check").join("checker"))
+            .arg(dir.join("input"))
+            .arg(dir.join("correct"))
+            .arg(dir.join("contestant"))
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        (String::from_utf8_lossy(&output.stdout).trim().to_owned(), String::from_utf8_lossy(&output.stderr).trim().to_owned())
+    }
+
+    #[test]
+    fn test_cms_task() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        Task::new("A + B Problem", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Cms)
+            .with_time_limit(1500)
+            .with_memory_limit(256)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(30, "small").with_test(3, generator))
+            .with_subtask(Subtask::new(70, "all").with_test(2, generator).with_aggregation(ScoreAggregation::Sum))
+            .with_partial_solution_lang("python", PYTHON_SOLUTION, Language::Python, &[0, 1])
+            .run()
+            .unwrap();
+
+        let cms_path = task_path.join("cms");
+        let yaml = fs::read_to_string(cms_path.join("task.yaml")).unwrap();
+        assert!(yaml.contains("name: a-b-problem\n"), "{yaml}");
+        assert!(yaml.contains("title: \"A + B Problem\"\n"));
+        assert!(yaml.contains("time_limit: 1.5\n"));
+        assert!(yaml.contains("memory_limit: 256\n"));
+        assert!(yaml.contains("n_input: 5\n"));
+        assert!(yaml.contains("score_type: GroupMin\n"));
+        assert!(yaml.contains("score_type_parameters: [[30, 3], [70, 2]]\n"));
+
+        for i in 0..5 {
+            let input = fs::read_to_string(cms_path.join("input").join(format!("input{i}.txt"))).unwrap();
+            let output = fs::read_to_string(cms_path.join("output").join(format!("output{i}.txt"))).unwrap();
+            let sum: i64 = input.split_whitespace().map(|x| x.parse::<i64>().unwrap()).sum();
+            assert_eq!(output.trim().parse::<i64>().unwrap(), sum);
+        }
+        assert!(!cms_path.join("input").join("input5.txt").exists());
+
+        let gen_file = fs::read_to_string(cms_path.join("gen").join("GEN")).unwrap();
+        assert!(gen_file.starts_with("#ST: 30\n#COPY: input/input0.txt\n"), "{gen_file}");
+        assert!(gen_file.contains("#ST: 70\n#COPY: input/input3.txt\n"));
+        assert_eq!(gen_file.lines().count(), 2 + 5);
+
+        assert!(!cms_path.join("check").exists());
+        assert_eq!(fs::read_to_string(cms_path.join("sol").join("main.cpp")).unwrap(), SOLUTION);
+        assert_eq!(fs::read_to_string(cms_path.join("sol").join("python.py")).unwrap(), PYTHON_SOLUTION);
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_cms_task_needs_memory_limit() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        let result = Task::new("unlimited", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Cms)
+            .with_memory_limit(0)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "all").with_test(2, generator))
+            .run();
+        assert!(matches!(&result, Err(Error::MissingMemoryLimit { format }) if format == "CMS"), "got {result:?}");
+        assert!(!task_path.join("cms").join("task.yaml").exists());
+
+        drop(tempdir);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_cms_checker_adapter() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        // testlib-style checker: checker <input> <output> <answer>
+        let checker = r#"
+        #include <cstdio>
+        #include <fstream>
+        #include <iostream>
+        int main(int argc, char* argv[]) {
+            std::ifstream output(argv[2]), answer(argv[3]);
+            long long found, expected;
+            output >> found;
+            answer >> expected;
+            if (found == expected) {
+                return 0;
+            }
+            if (found == expected + 1) {
+                std::cerr << "points 0.5 off by one";
+                return 7;
+            }
+            if (found < 0) {
+                std::cerr << "negative";
+                return 2;
+            }
+            std::cerr << "expected " << expected << ", found " << found;
+            return 1;
+        }
+        "#;
+
+        Task::new("checker", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Cms)
+            .with_checker_source(checker)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "").with_test(2, generator))
+            .run()
+            .unwrap();
+
+        let cms_path = task_path.join("cms");
+        assert!(fs::read_to_string(cms_path.join("check").join("checker.cpp")).unwrap().ends_with(checker));
+        assert_eq!(run_checker(&cms_path, "3\n", "3\n"), ("1".to_owned(), "Output is correct".to_owned()));
+        assert_eq!(run_checker(&cms_path, "3\n", "4\n"), ("0.5".to_owned(), "off by one".to_owned()));
+        assert_eq!(run_checker(&cms_path, "3\n", "5\n"), ("0".to_owned(), "expected 3, found 5".to_owned()));
+        assert_eq!(run_checker(&cms_path, "3\n", "-1\n"), ("0".to_owned(), "negative".to_owned()));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_cms_checker_file_with_testlib() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        let checker_file = write_testlib_checker(&tempdir.path().join("checker"));
+
+        Task::new("testlib checker", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Cms)
+            .with_checker_file(&checker_file)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "").with_test(2, generator))
+            .run()
+            .unwrap();
+
+        let cms_path = task_path.join("cms");
+        assert!(cms_path.join("check").join("testlib.h").exists());
+        assert_eq!(run_checker(&cms_path, "3\n", "3\n"), ("1".to_owned(), "ok".to_owned()));
+        assert_eq!(run_checker(&cms_path, "3\n", "4\n"), ("0".to_owned(), "numbers differ".to_owned()));
+
+        drop(tempdir);
+    }
+}
@@ -70,11 +70,11 @@ pub mod cpp_runner_tests {
         let mut runner = CppRunner::new(tempdir.path()).unwrap();
 
         let program_handle = runner.add_program(HELLO_WORLD_PROGRAM).unwrap();
-        let result = &runner.check_programs("", &[program_handle], 1000).unwrap()[0];
+        let result = &runner.check_programs("", &[program_handle], 1000, 0).unwrap()[0];
 
         assert!(matches!(result, RunResult::Ok(..)));
 
-        if let RunResult::Ok(_, output) = result {
+        if let RunResult::Ok(_, _, output) = result {
             assert_eq!(output.trim(), "Hello, World!");
         }
 
@@ -107,12 +107,12 @@ pub mod cpp_runner_tests {
 
         for i in 0..20 {
             let input = format!("{i}\n");
-            let results = runner.check_programs(&input, &program_handles, 1000).unwrap();
+            let results = runner.check_programs(&input, &program_handles, 1000, 0).unwrap();
 
             for (j, result) in results.iter().enumerate() {
                 assert!(matches!(result, RunResult::Ok(..)));
 
-                if let RunResult::Ok(_, output) = result {
+                if let RunResult::Ok(_, _, output) = result {
                     assert_eq!(output.trim(), format!("{j} {i}"));
                 }
             }
@@ -157,7 +157,7 @@ pub mod cpp_runner_tests {
         ";
 
         let program_handle = runner.add_program(program_source).unwrap();
-        let result = &runner.check_programs("1\n", &[program_handle], 1000).unwrap()[0];
+        let result = &runner.check_programs("1\n", &[program_handle], 1000, 0).unwrap()[0];
 
         // Check that the result is indeed a TLE
         assert!(matches!(result, RunResult::TimedOut));
@@ -165,6 +165,63 @@ pub mod cpp_runner_tests {
         drop(tempdir);
     }
 
+    #[test]
+    #[cfg(not(windows))]
+    fn test_runner_program_tle_below_one_second() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+
+        // Uses about 400 ms of CPU time
+        let program_source = "
+        #include <ctime>
+        int main() {
+            while (clock() < CLOCKS_PER_SEC * 4 / 10) {}
+            return 0;
+        }
+        ";
+
+        let program_handle = runner.add_program(program_source).unwrap();
+        // the CPU limit of the process is a whole second, but the exact limit is checked when it exits
+        assert!(matches!(runner.check_programs("", &[program_handle], 200, 0).unwrap()[0], RunResult::TimedOut));
+        assert!(matches!(runner.check_programs("", &[program_handle], 2000, 0).unwrap()[0], RunResult::Ok(..)));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_runner_program_mle() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+
+        let program_source = "
+        #include <iostream>
+        #include <vector>
+        int main() {
+            // Allocate and touch 256MB of memory
+            std::vector<char> memory(256 * 1024 * 1024, 1);
+            std::cout << (int)memory[12345] << std::endl;
+            return 0;
+        }
+        ";
+
+        let program_handle = runner.add_program(program_source).unwrap();
+        let results = runner.check_programs("", &[program_handle, program_handle], 2000, 64).unwrap();
+        assert!(matches!(results[0], RunResult::MemoryLimitExceeded), "Expected MLE but got {:?}", results[0]);
+
+        let result = &runner.check_programs("", &[program_handle], 2000, 512).unwrap()[0];
+        assert!(matches!(result, RunResult::Ok(..)), "Expected OK but got {result:?}");
+
+        if let RunResult::Ok(_, memory, _) = result {
+            assert!(*memory >= 256 * 1024, "Peak memory {memory}KB is lower than allocated");
+        }
+
+        drop(tempdir);
+    }
+
     #[test]
     #[cfg(not(windows))]
     fn test_runner_program_crash() {
@@ -183,7 +240,7 @@ pub mod cpp_runner_tests {
         ";
 
         let program_handle = runner.add_program(program_source).unwrap();
-        let result = &runner.check_programs("1\n", &[program_handle], 1000).unwrap()[0];
+        let result = &runner.check_programs("1\n", &[program_handle], 1000, 0).unwrap()[0];
 
         // Check that the result is indeed a crash
         assert!(matches!(result, RunResult::Crashed));
@@ -210,7 +267,7 @@ pub mod cpp_runner_tests {
         "#;
 
         let program_handle = runner.add_program(program_source).unwrap();
-        let task_handle = runner.add_task(program_handle, "".to_owned(), 1000);
+        let task_handle = runner.add_task(program_handle, "".to_owned(), 1000, 0);
 
         runner.run_tasks(None).unwrap();
 
@@ -236,11 +293,11 @@ pub mod cpp_runner_tests {
             let mut runner = CppRunner::new(tempdir.path()).unwrap();
 
             let program_handle = runner.add_program(HELLO_WORLD_PROGRAM).unwrap();
-            let result = &runner.check_programs("", &[program_handle], 1000).unwrap()[0];
+            let result = &runner.check_programs("", &[program_handle], 1000, 0).unwrap()[0];
 
             assert!(matches!(result, RunResult::Ok(..)));
 
-            if let RunResult::Ok(_, output) = result {
+            if let RunResult::Ok(_, _, output) = result {
                 assert_eq!(output.trim(), "Hello, World!");
             }
         }
@@ -250,4 +307,18 @@ pub mod cpp_runner_tests {
 
         drop(tempdir);
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_timer_verdict_unexpected_output() {
+        use crate::runner::exec_runner::timer_verdict;
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        let success = ExitStatus::from_raw(0);
+        assert_eq!(timer_verdict(success, b"debug output\n12 345"), Ok((12, 345)));
+        assert_eq!(timer_verdict(success, b"12 345\ndebug output"), Err(RunResult::Crashed));
+        assert_eq!(timer_verdict(success, b"12"), Err(RunResult::Crashed));
+        assert_eq!(timer_verdict(success, b""), Err(RunResult::Crashed));
+    }
 }
@@ -0,0 +1,151 @@
+/// Tests for subtask dependencies (`Subtask::with_dependency`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod dependency_tests {
+    use crate::{Error, ExportFormat, Solution, Subtask, Task};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io::Read;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Overflows for large numbers.
+    const INT_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Wrong for small numbers only.
+    const LARGE_ONLY_SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b + (a < 1000) << std::endl; }
+    ";
+
+    fn small_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))
+    }
+
+    fn large_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(2_000_000_000..3_000_000_000_i64), rng.random_range(2_000_000_000..3_000_000_000_i64))
+    }
+
+    fn read_zip(path: &Path) -> HashMap<String, String> {
+        let mut zip = zip::ZipArchive::new(fs::File::open(path).unwrap()).unwrap();
+        let mut files = HashMap::new();
+        for i in 0..zip.len() {
+            let mut file = zip.by_index(i).unwrap();
+            let mut contents = String::new();
+            file.read_to_string(&mut contents).unwrap();
+            files.insert(file.name().to_owned(), contents);
+        }
+        files
+    }
+
+    fn task(name: &str, path: &Path, dependency: bool) -> Task<String> {
+        let mut large = Subtask::new(70, "large").with_test(2, large_generator);
+        if dependency {
+            large = large.with_dependency(0);
+        }
+        Task::new(name, path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(30, "small").with_test(3, small_generator))
+            .with_subtask(large)
+    }
+
+    #[test]
+    fn test_dependency_tests_are_scored() {
+        let tempdir = TempDir::new().unwrap();
+
+        // passes the tests of the large subtask, but not the included tests of the small one
+        let large_only = || Solution::new("large only".to_owned(), LARGE_ONLY_SOLUTION.to_owned(), &[]).with_expected_score(1, 0.0..=0.0);
+        task("dependency", &tempdir.path().join("dependency"), true)
+            .with_partial_solution("int", INT_SOLUTION, &[0])
+            .with_partial(large_only())
+            .run()
+            .unwrap();
+
+        let result = task("no dependency", &tempdir.path().join("no dependency"), false).with_partial(large_only()).run();
+        assert!(
+            matches!(result, Err(Error::PartialSolutionScoreOutOfRange { subtask_number: 2, partial_number: 1, score, .. }) if score > 0.99),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_invalid_dependency() {
+        let tempdir = TempDir::new().unwrap();
+
+        let result = Task::new("invalid", tempdir.path())
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(50, "").with_test(1, small_generator).with_dependency(1))
+            .with_subtask(Subtask::new(50, "").with_test(1, small_generator))
+            .run();
+        assert!(
+            matches!(
+                result,
+                Err(Error::InvalidDependency {
+                    subtask_number: 1,
+                    dependency_number: 2
+                })
+            ),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_partial_solution_dependency() {
+        let tempdir = TempDir::new().unwrap();
+
+        let result = task("partial dependency", tempdir.path(), true).with_partial_solution("large only", LARGE_ONLY_SOLUTION, &[1]).run();
+        assert!(
+            matches!(&result, Err(Error::PartialSolutionDependency { partial_number: 1, partial_name, subtask_number: 2, dependency_number: 1 }) if partial_name == "large only"),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_exported_dependencies() {
+        let tempdir = TempDir::new().unwrap();
+
+        let task_path = tempdir.path().join("polygon");
+        task("polygon", &task_path, true).with_export_format(ExportFormat::Polygon).run().unwrap();
+        let xml = &read_zip(&task_path.join("polygon.zip"))["problem.xml"];
+        assert!(
+            xml.contains("<dependencies>\n                        <dependency group=\"1\"/>\n                    </dependencies>"),
+            "{xml}"
+        );
+
+        let task_path = tempdir.path().join("cms");
+        task("cms", &task_path, true).with_export_format(ExportFormat::Cms).with_sample("1 2\n".to_owned()).run().unwrap();
+        let yaml = fs::read_to_string(task_path.join("cms").join("task.yaml")).unwrap();
+        assert!(
+            yaml.contains("score_type_parameters: [[0, \"^(000)$\"], [30, \"^(001|002|003)$\"], [70, \"^(001|002|003|004|005)$\"]]\n"),
+            "{yaml}"
+        );
+
+        let task_path = tempdir.path().join("kattis");
+        task("kattis", &task_path, true).with_export_format(ExportFormat::Kattis).run().unwrap();
+        let files = read_zip(&task_path.join("kattis.zip"));
+        assert_eq!(files.keys().filter(|name| name.starts_with("data/secret/subtask1/")).count(), 2 * 3 + 1);
+        assert_eq!(files.keys().filter(|name| name.starts_with("data/secret/subtask2/")).count(), 2 * 5 + 1);
+        assert_eq!(files["data/secret/subtask2/05.in"].split_whitespace().map(|x| x.len() < 4).collect::<Vec<_>>(), [true, true]);
+
+        drop(tempdir);
+    }
+}
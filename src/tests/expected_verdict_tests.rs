@@ -0,0 +1,100 @@
+/// Tests for the expected verdicts of partial solutions (`Task::with_partial_solution_verdicts`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod expected_verdict_tests {
+    use crate::{Error, ExportFormat, Subtask, Task, Verdict};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Correct, but runs for three seconds on large numbers.
+    const SLOW_SOLUTION: &str = "
+    #include <chrono>
+    #include <iostream>
+    int main() {
+        long long a, b; std::cin >> a >> b;
+        auto start = std::chrono::steady_clock::now();
+        while (a > 1000 && std::chrono::steady_clock::now() - start < std::chrono::milliseconds(3000)) {}
+        std::cout << a + b << std::endl;
+    }
+    ";
+
+    /// Overflows for large numbers.
+    const INT_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    fn small_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))
+    }
+
+    fn large_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(2_000_000_000..3_000_000_000_i64), rng.random_range(2_000_000_000..3_000_000_000_i64))
+    }
+
+    fn task(path: &Path, source: &str, min_failures: usize) -> Task<String> {
+        Task::new("verdicts", path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_time_limit(1000)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(40, "small").with_test(2, small_generator))
+            .with_subtask(Subtask::new(60, "large").with_test(1, large_generator))
+            .with_partial_solution_verdicts("brute force", source, &[0], &[(1, &[Verdict::TimedOut])])
+            .with_min_failures(min_failures)
+    }
+
+    #[test]
+    fn test_expected_verdicts() {
+        let tempdir = TempDir::new().unwrap();
+        let report = task(tempdir.path(), SLOW_SOLUTION, 1).with_export_format(ExportFormat::Kattis).run().unwrap();
+        let subtask = &report.solutions[1].subtasks[1];
+        assert_eq!(subtask.verdicts.keys().copied().collect::<Vec<_>>(), vec![Verdict::TimedOut]);
+        assert_eq!(subtask.tests.len(), 1 + 1);
+
+        // the brute force is exported as a submission that exceeds the time limit
+        let zip = zip::ZipArchive::new(fs::File::open(tempdir.path().join("kattis.zip")).unwrap()).unwrap();
+        assert!(zip.file_names().any(|name| name.starts_with("submissions/time_limit_exceeded/")));
+        assert!(!zip.file_names().any(|name| name.starts_with("submissions/wrong_answer/")));
+    }
+
+    #[test]
+    fn test_unexpected_verdict_in_generation() {
+        let tempdir = TempDir::new().unwrap();
+        let result = task(tempdir.path(), INT_SOLUTION, 1).run();
+        assert!(
+            matches!(
+                result,
+                Err(Error::PartialSolutionUnexpectedVerdict {
+                    subtask_number: 2,
+                    partial_number: 1,
+                    verdict: Verdict::WrongAnswer,
+                    ..
+                })
+            ),
+            "got {result:?}"
+        );
+        assert!(tempdir.path().join("failing_test.in").is_file());
+    }
+
+    #[test]
+    fn test_unexpected_verdict_in_verification() {
+        let tempdir = TempDir::new().unwrap();
+        let result = task(tempdir.path(), INT_SOLUTION, 0).run();
+        let Err(Error::PartialSolutionUnexpectedVerdict { verdict, expected, test, .. }) = result else {
+            unreachable!("got {result:?}");
+        };
+        assert_eq!(verdict, Verdict::WrongAnswer);
+        assert_eq!(expected, "TLE");
+        assert!(Path::new(&test).is_file(), "{test}");
+    }
+}
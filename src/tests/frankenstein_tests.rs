@@ -3,6 +3,7 @@ mod frankenstein_tests {
     use crate::tests::test_shared::initialize_logger;
     use crate::{Subtask, Task};
     use rand::RngExt;
+    use std::path::Path;
     use tempfile::TempDir;
 
     #[test]
@@ -65,27 +66,80 @@ mod frankenstein_tests {
             .with_min_failures(5);
 
         // Run task
-        // It should succeed, finding tests in range (10, 20).
+        // It should succeed, every bad solution failing on at least 5 tests.
 
-        let res = task.run();
-        res.unwrap();
+        let report = task.run().unwrap();
+        assert!(report.subtasks[0].robust.as_ref().unwrap().is_complete());
 
-        // Verify generated tests are in range (10, 20)
-        // We can read the generated tests from task.tests_path
-        let tests_dir = task_path.join("tests");
-        let entries = std::fs::read_dir(tests_dir).unwrap();
-        let mut count = 0;
-        for entry in entries {
+        let tests = read_tests(&task_path);
+        assert!(tests.iter().filter(|&&n| n > 10).count() >= 5, "bad_a should fail on at least 5 tests: {tests:?}");
+        assert!(tests.iter().filter(|&&n| n < 20).count() >= 5, "bad_b should fail on at least 5 tests: {tests:?}");
+
+        drop(tempdir);
+    }
+
+    /// Reads the numbers in the generated tests.
+    fn read_tests(task_path: &Path) -> Vec<i32> {
+        let mut tests = Vec::new();
+        for entry in std::fs::read_dir(task_path.join("tests")).unwrap() {
             let path = entry.unwrap().path();
             if path.extension().is_some_and(|ext| ext == "in") {
                 let content = std::fs::read_to_string(path).unwrap();
-                let n: i32 = content.trim().parse().unwrap();
-                assert!(n > 10 && n < 20, "Generated test {n} is not in overlap range (10, 20)");
-                count += 1;
+                tests.push(content.trim().parse().unwrap());
             }
         }
-        assert!(count >= 5, "Should have generated at least 5 tests");
+        tests
+    }
 
-        drop(tempdir);
+    #[test]
+    fn test_frankenstein_disjoint() {
+        initialize_logger();
+        let tempdir = TempDir::new().unwrap();
+        let task_name = "frankenstein_disjoint";
+        let task_path = tempdir.path().join(task_name);
+
+        let source_main = "
+        #include <iostream>
+        int main() { int n; std::cin >> n; std::cout << n << std::endl; }
+        ";
+
+        // Bad Solution A: Fails if x > 30
+        let source_bad_a = "
+        #include <iostream>
+        int main() { int n; std::cin >> n; std::cout << (n > 30 ? n + 1 : n) << std::endl; }
+        ";
+
+        // Bad Solution B: Fails if x < 10
+        // No test breaks both of them, so every robust test breaks only one.
+        let source_bad_b = "
+        #include <iostream>
+        int main() { int n; std::cin >> n; std::cout << (n < 10 ? n - 1 : n) << std::endl; }
+        ";
+
+        let subtask = Subtask::new(0, "").with_test(0, || {
+            let mut rng = rand::rng();
+            format!("{}", rng.random_range(0..50))
+        });
+
+        let report = Task::new(task_name, &task_path)
+            .with_solution_source(source_main)
+            .with_subtask(subtask)
+            .with_partial_solution("bad_a", source_bad_a, &[])
+            .with_partial_solution("bad_b", source_bad_b, &[])
+            .with_min_failures(5)
+            .run()
+            .unwrap();
+
+        let robust = report.subtasks[0].robust.as_ref().unwrap();
+        assert!(robust.is_complete());
+        assert_eq!(robust.failures.len(), 2);
+        assert!(robust.failures.iter().all(|(_, count)| *count >= 5), "{:?}", robust.failures);
+
+        let tests = read_tests(&task_path);
+        assert!(tests.iter().filter(|&&n| n > 30).count() >= 5, "bad_a should fail on at least 5 tests: {tests:?}");
+        assert!(tests.iter().filter(|&&n| n < 10).count() >= 5, "bad_b should fail on at least 5 tests: {tests:?}");
+        // every test breaks a bad solution that still needed failures
+        assert_eq!(tests.len(), robust.found);
+        assert!(tests.iter().all(|&n| !(10..=30).contains(&n)), "{tests:?}");
     }
 }
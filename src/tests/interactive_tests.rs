@@ -0,0 +1,162 @@
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod interactive_tests {
+    use crate::runner::cpp_runner::CppRunner;
+    use crate::runner::exec_runner::RunResult;
+    use crate::tests::generic_tests::generic_tests::Test;
+    use crate::tests::test_shared::initialize_logger;
+    use crate::{Error, Subtask};
+    use rand::RngExt;
+    use tempfile::TempDir;
+
+    // Reads the secret number from the input file and answers queries "? x" with 1 if secret >= x.
+    // The solution reports the answer with "! x" and may ask at most 30 queries.
+    const INTERACTOR: &str = r#"
+    #include <cstdio>
+    #include <fstream>
+    int main(int argc, char* argv[]) {
+        std::ifstream in(argv[1]);
+        std::ofstream out(argv[2]);
+        int secret;
+        in >> secret;
+        int queries = 0;
+        while (true) {
+            char type;
+            int x;
+            if (scanf(" %c %d", &type, &x) != 2) return 1;
+            if (type == '!') {
+                out << secret << "\n";
+                return x == secret ? 0 : 1;
+            }
+            if (++queries > 30) return 1;
+            printf("%d\n", secret >= x ? 1 : 0);
+            fflush(stdout);
+        }
+    }
+    "#;
+
+    const BINARY_SEARCH: &str = r#"
+    #include <iostream>
+    using namespace std;
+    int main() {
+        int low = 1, high = 1000000;
+        while (low < high) {
+            int mid = (low + high + 1) / 2;
+            cout << "? " << mid << endl;
+            int reply;
+            cin >> reply;
+            if (reply) low = mid; else high = mid - 1;
+        }
+        cout << "! " << low << endl;
+        return 0;
+    }
+    "#;
+
+    const LINEAR_SEARCH: &str = r#"
+    #include <iostream>
+    using namespace std;
+    int main() {
+        int x = 1;
+        while (true) {
+            cout << "? " << x + 1 << endl;
+            int reply;
+            if (!(cin >> reply)) return 0;
+            if (!reply) break;
+            x++;
+        }
+        cout << "! " << x << endl;
+        return 0;
+    }
+    "#;
+
+    fn secret_generator(max_secret: i32) -> impl Fn() -> String {
+        move || format!("{}\n", rand::rng().random_range(1..=max_secret))
+    }
+
+    #[test]
+    fn test_interactive_task() {
+        let mut task = Test::new();
+
+        let subtask1 = Subtask::new(30, "secret <= 30").with_test(5, secret_generator(30));
+        let subtask2 = Subtask::new(70, "secret <= 10^6").with_test(5, secret_generator(1_000_000));
+
+        task.task = task
+            .task
+            .with_solution_source(BINARY_SEARCH)
+            .with_interactor(INTERACTOR)
+            .with_subtask(subtask1)
+            .with_subtask(subtask2)
+            .with_partial_solution("linear", LINEAR_SEARCH, &[0]);
+
+        task.test();
+    }
+
+    #[test]
+    fn test_interactive_main_solution_rejected() {
+        let mut task = Test::new();
+
+        let subtask = Subtask::new(100, "").with_test(5, secret_generator(1_000_000));
+
+        task.task = task.task.with_solution_source(LINEAR_SEARCH).with_interactor(INTERACTOR).with_subtask(subtask);
+
+        assert!(matches!(task.task.run(), Err(Error::SolutionFailed { .. })));
+    }
+
+    #[test]
+    fn test_interactive_verdicts() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+
+        let interactor = runner.add_program(INTERACTOR).unwrap();
+        let binary_search = runner.add_program(BINARY_SEARCH).unwrap();
+        let linear_search = runner.add_program(LINEAR_SEARCH).unwrap();
+        let infinite_loop = runner.add_program("int main() { while (true) {} }").unwrap();
+        for program in [binary_search, linear_search, infinite_loop] {
+            runner.set_interactor(program, Some(interactor));
+        }
+
+        // other programs of the runner still run normally
+        let echo = runner.add_program("#include <iostream>\nint main() { int x; std::cin >> x; std::cout << x << std::endl; }").unwrap();
+        let results = runner.check_programs("42\n", &[echo], 1000, 0).unwrap();
+        assert!(matches!(&results[0], RunResult::Ok(_, _, output) if output.trim() == "42"), "got {:?}", results[0]);
+
+        let results = runner.check_programs("123456\n", &[binary_search, linear_search, infinite_loop], 1000, 0).unwrap();
+        assert!(matches!(&results[0], RunResult::Ok(_, _, output) if output.trim() == "123456"), "got {:?}", results[0]);
+        assert!(matches!(results[1], RunResult::WrongAnswer), "got {:?}", results[1]);
+        assert!(matches!(results[2], RunResult::TimedOut), "got {:?}", results[2]);
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_interactor_with_large_stderr() {
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+
+        // writes more to stderr than fits into a pipe before answering
+        let interactor = runner
+            .add_program(
+                r#"
+                #include <cstdio>
+                #include <fstream>
+                int main(int argc, char* argv[]) {
+                    for (int i = 0; i < 100000; i++) fprintf(stderr, "debug line %d\n", i);
+                    int x;
+                    if (scanf("%d", &x) != 1) return 1;
+                    std::ofstream(argv[2]) << x << "\n";
+                    return 0;
+                }
+                "#,
+            )
+            .unwrap();
+        let solution = runner.add_program("#include <iostream>\nint main() { std::cout << 7 << std::endl; }").unwrap();
+        runner.set_interactor(solution, Some(interactor));
+
+        let results = runner.check_programs("\n", &[solution], 1000, 0).unwrap();
+        assert!(matches!(&results[0], RunResult::Ok(_, _, output) if output.trim() == "7"), "got {:?}", results[0]);
+
+        drop(tempdir);
+    }
+}
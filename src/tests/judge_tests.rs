@@ -0,0 +1,91 @@
+/// Tests for judging a solution on existing tests (`Task::judge_solution`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod judge_tests {
+    use crate::{Error, Subtask, Task, Verdict};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Overflows for large numbers.
+    const INT_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    fn small_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))
+    }
+
+    fn large_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(2_000_000_000..3_000_000_000_i64), rng.random_range(2_000_000_000..3_000_000_000_i64))
+    }
+
+    fn task(path: &Path) -> Task<String> {
+        Task::new("judge", path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(40, "small").with_test(3, small_generator))
+            .with_subtask(Subtask::new(60, "large").with_test(2, large_generator))
+    }
+
+    #[test]
+    fn test_judge_solution_on_generated_tests() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        task(&task_path).run().unwrap();
+        let task = task(&task_path);
+
+        let solution_file = tempdir.path().join("int.cpp");
+        fs::write(&solution_file, INT_SOLUTION).unwrap();
+        let report = task.judge_solution_on_tests(&solution_file).unwrap();
+
+        assert_eq!(report.name, "int.cpp");
+        assert_eq!(report.max_points, 100);
+        assert!((report.points - 40.0).abs() < 1e-9, "got {}", report.points);
+        assert_eq!(report.subtasks.len(), 2);
+        assert!(report.subtasks[0].passed());
+        assert_eq!(report.subtasks[0].tests.len(), 3);
+        assert!(report.subtasks[0].tests.iter().all(|test| test.verdict == Verdict::Ok && test.time.is_some() && test.memory.is_some()));
+        assert!(!report.subtasks[1].passed());
+        assert_eq!(report.subtasks[1].verdicts.get(&Verdict::WrongAnswer), Some(&2));
+        assert!(report.subtasks[1].points.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_judge_solution_on_given_tests() {
+        let tempdir = TempDir::new().unwrap();
+        let tests_path = tempdir.path().join("my_tests");
+        fs::create_dir_all(&tests_path).unwrap();
+        fs::write(tests_path.join("a.in"), "1 2\n").unwrap();
+        fs::write(tests_path.join("a.out"), "3\n").unwrap();
+        fs::write(tests_path.join("b.in"), "3000000000 3000000000\n").unwrap();
+        fs::write(tests_path.join("b.out"), "6000000000\n").unwrap();
+        let solution_file = tempdir.path().join("int.cpp");
+        fs::write(&solution_file, INT_SOLUTION).unwrap();
+
+        let task = task(&tempdir.path().join("task"));
+        let layout = vec![vec![(PathBuf::from("a.in"), PathBuf::from("a.out"))], vec![(PathBuf::from("b.in"), PathBuf::from("b.out"))]];
+        let report = task.judge_solution(&solution_file, &tests_path, &layout).unwrap();
+        assert!((report.points - 40.0).abs() < 1e-9, "got {}", report.points);
+        assert_eq!(report.subtasks[1].tests[0].verdict, Verdict::WrongAnswer);
+        assert_eq!(report.subtasks[1].tests[0].input_file, tests_path.join("b.in"));
+
+        let result = task.judge_solution(&solution_file, &tests_path, &layout[..1]);
+        assert!(matches!(result, Err(Error::WrongSubtaskCount { expected: 2, found: 1 })), "got {result:?}");
+
+        let unknown_file = tempdir.path().join("int.txt");
+        fs::write(&unknown_file, INT_SOLUTION).unwrap();
+        let result = task.judge_solution(&unknown_file, &tests_path, &layout);
+        assert!(matches!(result, Err(Error::UnsupportedLanguage { .. })), "got {result:?}");
+    }
+}
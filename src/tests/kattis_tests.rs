@@ -0,0 +1,186 @@
+/// Tests for the Kattis problem package export (`Task::with_export_format(ExportFormat::Kattis)`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod kattis_tests {
+    #[cfg(unix)]
+    use crate::tests::test_shared::write_testlib_checker;
+    use crate::{ExportFormat, Language, ScoreAggregation, Subtask, Task};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::collections::HashMap;
+    use std::io::Read;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Overflows for large numbers.
+    const INT_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    const PYTHON_SOLUTION: &str = "a, b = map(int, input().split())\nprint(a + b)\n";
+
+    fn small_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))
+    }
+
+    fn large_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(2_000_000_000..3_000_000_000_i64), rng.random_range(2_000_000_000..3_000_000_000_i64))
+    }
+
+    fn read_zip(path: &Path) -> HashMap<String, String> {
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut files = HashMap::new();
+        for i in 0..zip.len() {
+            let mut file = zip.by_index(i).unwrap();
+            let mut contents = String::new();
+            file.read_to_string(&mut contents).unwrap();
+            files.insert(file.name().to_owned(), contents);
+        }
+        files
+    }
+
+    #[test]
+    fn test_kattis_package() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        Task::new("A + B Problem", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Kattis)
+            .with_time_limit(2000)
+            .with_memory_limit(512)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(30, "small").with_test(3, small_generator))
+            .with_subtask(Subtask::new(70, "large").with_test(2, large_generator).with_aggregation(ScoreAggregation::Sum))
+            .with_partial_solution("int overflow", INT_SOLUTION, &[0])
+            .with_partial_solution_lang("python", PYTHON_SOLUTION, Language::Python, &[0, 1])
+            .with_min_failures(2)
+            .run()
+            .unwrap();
+
+        let files = read_zip(&task_path.join("kattis.zip"));
+        assert_eq!(files["problem.yaml"], "name: \"A + B Problem\"\ntype: scoring\nvalidation: default\nlimits:\n    memory: 512\n");
+        assert_eq!(files[".timelimit"], "2\n");
+
+        assert_eq!(files.keys().filter(|name| name.starts_with("data/secret/subtask1/")).count(), 2 * 3 + 1);
+        assert_eq!(files.keys().filter(|name| name.starts_with("data/secret/subtask2/")).count(), 2 * 4 + 1);
+        assert_eq!(files["data/secret/subtask1/01.in"].split_whitespace().count(), 2);
+        assert!(files["data/secret/subtask2/04.ans"].trim().parse::<i64>().unwrap() >= 4_000_000_000);
+        assert_eq!(files["data/secret/testdata.yaml"], "on_reject: continue\ngrader_flags: sum\nrange: 0 100\n");
+        assert_eq!(files["data/secret/subtask1/testdata.yaml"], "on_reject: break\ngrader_flags: min\naccept_score: 30\nrange: 0 30\n");
+        assert_eq!(files["data/secret/subtask2/testdata.yaml"], "on_reject: continue\ngrader_flags: sum\naccept_score: 17.5\nrange: 0 70\n");
+
+        assert_eq!(files["submissions/accepted/main.cpp"], SOLUTION);
+        assert_eq!(files["submissions/accepted/python.py"], PYTHON_SOLUTION);
+        assert_eq!(files["submissions/wrong_answer/int-overflow.cpp"], INT_SOLUTION);
+        assert!(!files.keys().any(|name| name.starts_with("output_validators/")));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_kattis_output_validator() {
+        use crate::runner::gcc::Gcc;
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        // testlib-style checker: checker <input> <output> <answer>
+        let checker = r#"
+        #include <fstream>
+        #include <iostream>
+        int main(int argc, char* argv[]) {
+            std::ifstream output(argv[2]), answer(argv[3]);
+            long long found, expected;
+            output >> found;
+            answer >> expected;
+            if (found == expected) {
+                return 0;
+            }
+            std::cerr << "expected " << expected << ", found " << found;
+            return 1;
+        }
+        "#;
+
+        Task::new("checker", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Kattis)
+            .with_checker_source(checker)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "").with_test(2, small_generator))
+            .run()
+            .unwrap();
+
+        let files = read_zip(&task_path.join("kattis.zip"));
+        assert!(files["problem.yaml"].contains("validation: custom\n"));
+        let source = &files["output_validators/checker/checker.cpp"];
+        assert!(source.ends_with(checker));
+
+        let dir = tempdir.path().join("validator");
+        std::fs::create_dir_all(dir.join("feedback")).unwrap();
+        std::fs::write(dir.join("validator.cpp"), source).unwrap();
+        let validator = Gcc::new().unwrap().compile(&dir.join("validator.cpp"), None).unwrap();
+        std::fs::write(dir.join("input"), "1 2\n").unwrap();
+        std::fs::write(dir.join("answer"), "3\n").unwrap();
+
+        let run = |team_output: &str| {
+            let mut child = Command::new(&validator)
+                .arg(dir.join("input"))
+                .arg(dir.join("answer"))
+                .arg(dir.join("feedback"))
+                .stdin(Stdio::piped())
+                .spawn()
+                .unwrap();
+            child.stdin.take().unwrap().write_all(team_output.as_bytes()).unwrap();
+            let code = child.wait().unwrap().code();
+            (code, std::fs::read_to_string(dir.join("feedback").join("judgemessage.txt")).unwrap().trim().to_owned())
+        };
+        assert_eq!(run("3\n"), (Some(42), String::new()));
+        assert_eq!(run("4\n"), (Some(43), "expected 3, found 4".to_owned()));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_kattis_checker_file_with_testlib() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        let checker_file = write_testlib_checker(&tempdir.path().join("checker"));
+
+        Task::new("testlib checker", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Kattis)
+            .with_checker_file(&checker_file)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "").with_test(2, small_generator))
+            .run()
+            .unwrap();
+
+        // the validator compiles from its directory, which has the headers of the checker
+        let files = read_zip(&task_path.join("kattis.zip"));
+        let dir = tempdir.path().join("validator");
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["checker.cpp", "testlib.h"] {
+            std::fs::write(dir.join(name), &files[&format!("output_validators/checker/{name}")]).unwrap();
+        }
+        assert_eq!(
+            files["output_validators/checker/testlib.h"],
+            std::fs::read_to_string(tempdir.path().join("checker").join("testlib.h")).unwrap()
+        );
+        crate::runner::gcc::Gcc::new().unwrap().compile(&dir.join("checker.cpp"), None).unwrap();
+
+        drop(tempdir);
+    }
+}
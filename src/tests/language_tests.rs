@@ -0,0 +1,164 @@
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod language_tests {
+    use crate::Error::CompilerError;
+    use crate::Language;
+    use crate::runner::cpp_runner::CppRunner;
+    use crate::runner::exec_runner::RunResult;
+    use crate::tests::generic_tests::generic_tests::Test;
+    use crate::tests::test_shared::initialize_logger;
+    use crate::{Subtask, array_generator};
+    use tempfile::TempDir;
+
+    const C_PROGRAM: &str = r#"
+    #include <stdio.h>
+    int main() {
+        int a, b;
+        scanf("%d %d", &a, &b);
+        printf("%d\n", a + b);
+        return 0;
+    }
+    "#;
+
+    const RUST_PROGRAM: &str = "
+    use std::io::Read;
+    fn main() {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input).unwrap();
+        let sum: i64 = input.split_whitespace().map(|x| x.parse::<i64>().unwrap()).sum();
+        println!(\"{sum}\");
+    }
+    ";
+
+    const PYTHON_PROGRAM: &str = "
+a, b = map(int, input().split())
+print(a + b)
+";
+
+    fn run_sum(source: &str, language: Language) -> RunResult {
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+        let program_handle = runner.add_program_lang(source, language).unwrap();
+        runner.check_programs("2 3\n", &[program_handle], 5000, 0).unwrap().remove(0)
+    }
+
+    #[test]
+    fn test_language_from_extension() {
+        assert_eq!(Language::from_extension("cpp"), Some(Language::Cpp));
+        assert_eq!(Language::from_extension("cc"), Some(Language::Cpp));
+        assert_eq!(Language::from_extension("c"), Some(Language::C));
+        assert_eq!(Language::from_extension("rs"), Some(Language::Rust));
+        assert_eq!(Language::from_extension("PY"), Some(Language::Python));
+        assert_eq!(Language::from_extension("java"), None);
+    }
+
+    #[test]
+    fn test_run_c_program() {
+        initialize_logger();
+        let result = run_sum(C_PROGRAM, Language::C);
+        assert!(matches!(&result, RunResult::Ok(_, _, output) if output.trim() == "5"), "got {result:?}");
+    }
+
+    #[test]
+    fn test_run_rust_program() {
+        initialize_logger();
+        let result = run_sum(RUST_PROGRAM, Language::Rust);
+        assert!(matches!(&result, RunResult::Ok(_, _, output) if output.trim() == "5"), "got {result:?}");
+    }
+
+    #[test]
+    fn test_run_python_program() {
+        initialize_logger();
+        let result = run_sum(PYTHON_PROGRAM, Language::Python);
+        assert!(matches!(&result, RunResult::Ok(_, _, output) if output.trim() == "5"), "got {result:?}");
+    }
+
+    #[test]
+    fn test_python_syntax_error() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+
+        let faulty_program = "print(1 +\n";
+        assert!(matches!(runner.add_program_lang(faulty_program, Language::Python), Err(CompilerError { .. })));
+        // the check has to fail again and not pick up the cached source
+        assert!(matches!(runner.add_program_lang(faulty_program, Language::Python), Err(CompilerError { .. })));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_same_source_different_languages() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+
+        let source = "int main() { return 0; }";
+        let cpp_handle = runner.add_program_lang(source, Language::Cpp).unwrap();
+        let c_handle = runner.add_program_lang(source, Language::C).unwrap();
+        assert_ne!(cpp_handle, c_handle);
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_time_limit_with_multiplier() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+        let cpp_handle = runner.add_program_lang(C_PROGRAM, Language::C).unwrap();
+        let python_handle = runner.add_program_lang(PYTHON_PROGRAM, Language::Python).unwrap();
+        assert_eq!(runner.get_time_limit(cpp_handle, 1000), 1000);
+        assert_eq!(runner.get_time_limit(python_handle, 1000), 3000);
+        runner.set_time_limit_multiplier(Language::Python, 2.5);
+        assert_eq!(runner.get_time_limit(python_handle, 1000), 2500);
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_task_with_python_partial_solution() {
+        let mut task = Test::new();
+
+        let solution_contents = r#"
+        #include <iostream>
+        using namespace std;
+
+        int main() {
+            int n;
+            cin>>n;
+            long long sum=0;
+            for(int i=0;i<n;i++) {
+                int a;
+                cin>>a;
+                sum+=a;
+            }
+            cout<<sum<<"\n";
+            return 0;
+        }
+        "#;
+
+        // correct, but only looks at the first element
+        let partial_solution_contents = "
+n = int(input())
+a = list(map(int, input().split()))
+print(a[0])
+";
+
+        let subtask1 = Subtask::new(0, "").with_test(5, array_generator(1, 1, 1, 100));
+        let subtask2 = Subtask::new(0, "").with_test(5, array_generator(2, 100, 1, 100));
+
+        task.task = task
+            .task
+            .with_solution_source(solution_contents)
+            .with_subtask(subtask1)
+            .with_subtask(subtask2)
+            .with_partial_solution_lang("python", partial_solution_contents, Language::Python, &[0])
+            .with_time_limit_multiplier(Language::Python, 2.0);
+
+        task.test();
+    }
+}
@@ -0,0 +1,142 @@
+/// Tests for hand-written tests loaded with `Subtask::with_tests_from_dir`.
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod manual_tests {
+    use crate::{Error, Subtask, Task};
+    use log::LevelFilter;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Overflows for large numbers.
+    const INT_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    fn write_tests(dir: &Path, files: &[(&str, &str)]) {
+        fs::create_dir_all(dir).unwrap();
+        for (name, contents) in files {
+            fs::write(dir.join(name), contents).unwrap();
+        }
+    }
+
+    fn task(path: &Path, subtask: Subtask<String>) -> Task<String> {
+        Task::new("manual", path).with_debug_level(LevelFilter::Trace).with_solution_source(SOLUTION).with_subtask(subtask)
+    }
+
+    #[test]
+    fn test_tests_from_dir() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        let dir = tempdir.path().join("manual");
+        write_tests(
+            &dir,
+            &[
+                ("big.in", "2000000000 2000000000\n"),
+                ("big.ans", "4000000000\n"),
+                ("small.in", "1  2"),
+                ("small_copy.in", "1 2\n"),
+                ("notes.txt", "not a test"),
+            ],
+        );
+
+        task(&task_path, Subtask::new(100, "").with_tests_from_dir(&dir).with_test(1, || "5 6\n".to_owned()))
+            .with_seed(7)
+            .run()
+            .unwrap();
+
+        let mut tests = Vec::new();
+        for entry in fs::read_dir(task_path.join("tests")).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().unwrap() == "in" {
+                let output = fs::read_to_string(path.with_extension("out")).unwrap();
+                tests.push((fs::read_to_string(&path).unwrap(), output));
+            }
+        }
+        tests.sort();
+        assert_eq!(
+            tests,
+            [
+                ("1 2\n".to_owned(), "3\n".to_owned()),
+                ("2000000000 2000000000\n".to_owned(), "4000000000\n".to_owned()),
+                ("5 6\n".to_owned(), "11\n".to_owned())
+            ]
+        );
+
+        let manifest = fs::read_to_string(task_path.join("manifest.txt")).unwrap();
+        let mut lines: Vec<Vec<&str>> = manifest.lines().map(|line| line.split(' ').collect()).collect();
+        assert_eq!(lines.len(), 3, "{manifest}");
+        assert_eq!(lines.remove(0).join(" "), "# test subtask original file");
+        // tests are shuffled, so the manifest is sorted by the original files
+        lines.sort_by_key(|fields| fields[2]);
+        for (fields, original) in lines.iter().zip(["big.in", "small.in"]) {
+            assert_eq!(fields[1], "1");
+            assert_eq!(fields[2], dir.join(original).to_string_lossy());
+            assert_eq!(
+                fs::read_to_string(task_path.join("tests").join(fields[0])).unwrap().split_whitespace().collect::<Vec<_>>(),
+                fs::read_to_string(dir.join(original)).unwrap().split_whitespace().collect::<Vec<_>>()
+            );
+        }
+
+        let seeds = fs::read_to_string(task_path.join("seeds.txt")).unwrap();
+        assert_eq!(seeds.lines().count(), 2 + 1, "{seeds}");
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_wrong_manual_output() {
+        let tempdir = TempDir::new().unwrap();
+        let dir = tempdir.path().join("manual");
+        write_tests(&dir, &[("01.in", "1 2\n"), ("01.out", "4\n")]);
+
+        let result = task(&tempdir.path().join("task"), Subtask::new(100, "").with_tests_from_dir(&dir)).run();
+        assert!(
+            matches!(&result, Err(Error::ManualTestFailed { subtask_number: 1, solution_name, verdict, file }) if solution_name == "Main solution" && verdict == "WA" && file.ends_with("01.in")),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_partial_solution_fails_manual_test() {
+        let tempdir = TempDir::new().unwrap();
+        let dir = tempdir.path().join("manual");
+        write_tests(&dir, &[("01.in", "2000000000 2000000000\n")]);
+
+        let result = task(&tempdir.path().join("task"), Subtask::new(100, "").with_tests_from_dir(&dir))
+            .with_partial_solution("int", INT_SOLUTION, &[0])
+            .run();
+        assert!(
+            matches!(&result, Err(Error::ManualTestFailed { solution_name, .. }) if solution_name == "Partial solution 1 (int)"),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_invalid_manual_test() {
+        let tempdir = TempDir::new().unwrap();
+        let dir = tempdir.path().join("manual");
+        write_tests(&dir, &[("01.in", "1 2 3\n")]);
+
+        let subtask = Subtask::new(100, "")
+            .with_validator(|input| if input.split_whitespace().count() == 2 { Ok(()) } else { Err("expected two numbers".to_owned()) })
+            .with_tests_from_dir(&dir);
+        let result = task(&tempdir.path().join("task"), subtask).run();
+        assert!(
+            matches!(&result, Err(Error::InvalidManualTest { subtask_number: 1, message, file }) if message == "expected two numbers" && file.ends_with("01.in")),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+}
@@ -1,23 +1,81 @@
 mod archiver_tests;
 mod array_tests;
+mod bandit_tests;
+mod checker_program_tests;
 mod checker_tests;
+mod cli_tests;
+mod cms_tests;
 mod cpp_runner_tests;
+mod dependency_tests;
+mod expected_verdict_tests;
 mod frankenstein_tests;
 mod gcc_tests;
 mod generic_tests;
 mod graph_tests;
+mod interactive_tests;
+mod judge_tests;
+mod kattis_tests;
+mod language_tests;
+mod manual_tests;
+mod param_test_tests;
+mod partial_scoring_tests;
 mod partial_solution_tests;
+mod polygon_tests;
+mod report_tests;
+mod sample_tests;
+mod seed_tests;
+mod shrink_tests;
 mod solution_tests;
+mod source_file_tests;
 mod stack_limit_tests;
 mod subtask_tests;
+mod test_cache_tests;
 mod test_generator_tests;
+mod time_limit_tests;
 mod to_output_tests;
+mod validator_tests;
 
 #[cfg(test)]
 mod test_shared {
     use crate::logger_format::logger_format;
     use crate::task::LOGGER_INIT;
     use log::LevelFilter;
+    use std::path::{Path, PathBuf};
+
+    /// Minimal stand-in for testlib.h with the functions used by `TESTLIB_CHECKER`.
+    const TESTLIB_HEADER: &str = r#"
+    #include <cstdio>
+    #include <cstdlib>
+    #include <fstream>
+    enum TResult { _ok = 0, _wa = 1 };
+    struct InStream {
+        std::ifstream file;
+        long long readLong() { long long x = 0; file >> x; return x; }
+    };
+    InStream inf, ouf, ans;
+    void registerTestlibCmd(int argc, char* argv[]) { inf.file.open(argv[1]); ouf.file.open(argv[2]); ans.file.open(argv[3]); }
+    void quitf(TResult result, const char* message) { std::fprintf(stderr, "%s", message); std::exit(result); }
+    "#;
+
+    /// Checker that accepts an output equal to the answer, written against testlib.
+    const TESTLIB_CHECKER: &str = r#"
+    #include "testlib.h"
+    int main(int argc, char* argv[]) {
+        registerTestlibCmd(argc, argv);
+        if (ouf.readLong() == ans.readLong()) quitf(_ok, "ok");
+        quitf(_wa, "numbers differ");
+    }
+    "#;
+
+    /// Writes a checker file that includes `testlib.h` from its directory, together with the header.
+    /// Returns the path of the checker file.
+    #[cfg(test)]
+    pub fn write_testlib_checker(dir: &Path) -> PathBuf {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("testlib.h"), TESTLIB_HEADER).unwrap();
+        std::fs::write(dir.join("checker.cpp"), TESTLIB_CHECKER).unwrap();
+        dir.join("checker.cpp")
+    }
 
     #[cfg(test)]
     pub fn initialize_logger() {
@@ -0,0 +1,111 @@
+/// Tests for parameterized generators (`Subtask::with_param_test`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod param_test_tests {
+    use crate::params::Params;
+    use crate::{Error, GeneratorRng, ParamSpace, Subtask, Task};
+    use log::LevelFilter;
+    use rand::{RngExt, SeedableRng};
+    use std::fs;
+    use std::ops::RangeInclusive;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_param_space() {
+        let space = ParamSpace::new().with_param("n", 1..=100).unwrap().with_param("density", 0..=100).unwrap();
+        let mut rng = GeneratorRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let params = space.sample(&mut rng);
+            assert!(space.contains(&params));
+            assert!((1..=100).contains(&params.get("n").unwrap()));
+
+            let mutated = space.mutate(&params, &mut rng);
+            assert!(space.contains(&mutated));
+            let changed = params.values().iter().zip(mutated.values()).filter(|(param, other)| param != other).count();
+            assert!(changed <= 1);
+
+            assert_eq!(Params::parse(&params.to_string()), Some(params));
+        }
+
+        let params = Params::parse("n=5 density=-3").unwrap();
+        assert_eq!((params.get("n").unwrap(), params.get("density").unwrap()), (5, -3));
+        assert_eq!(params.to_string(), "n=5 density=-3");
+        assert!(!space.contains(&params));
+        assert_eq!(Params::parse(""), Some(Params::default()));
+        assert_eq!(Params::parse("n=x"), None);
+        assert!(matches!(params.get("m"), Err(Error::UnknownParameter { .. })));
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        let space = ParamSpace::new().with_param("n", 1..=100).unwrap();
+        assert!(matches!(space.clone().with_param("n", 1..=5), Err(Error::InvalidParameter { .. })));
+        assert!(matches!(space.clone().with_param("max val", 1..=5), Err(Error::InvalidParameter { .. })));
+        assert!(matches!(space.clone().with_param("", 1..=5), Err(Error::InvalidParameter { .. })));
+        assert!(matches!(space.with_param("m", RangeInclusive::new(5, 1)), Err(Error::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn test_param_test_is_reproducible() {
+        let subtask = Subtask::new(0, "t").with_param_test(1, ParamSpace::new().with_param("n", 1..=10).unwrap(), |params, rng| {
+            format!("{} {}\n", params.get("n").unwrap(), rng.random_range(0..1000))
+        });
+        let params = Params::parse("n=7").unwrap();
+        let test = subtask.generate_test_with_params(0, 3, &params);
+        assert!(test.starts_with("7 "));
+        assert_eq!(subtask.generate_test_with_params(0, 3, &params), test);
+        assert_eq!(subtask.generate_test(0, 3), subtask.generate_test_with_params(0, 3, &subtask.params_from_seed(0, 3)));
+    }
+
+    #[test]
+    fn test_search_mutates_params_of_robust_tests() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        let solution = "
+        #include <iostream>
+        int main() { long long n, m; std::cin >> n >> m; std::cout << n + m << std::endl; }
+        ";
+        // Fails only when n is near the top of its range
+        let bad_solution = "
+        #include <iostream>
+        int main() { long long n, m; std::cin >> n >> m; std::cout << (n >= 980000 ? n : n + m) << std::endl; }
+        ";
+
+        let space = ParamSpace::new().with_param("n", 0..=1_000_000).unwrap().with_param("maxval", 1..=10).unwrap();
+        let subtask = Subtask::new(100, "all").with_param_test(5, space, |params, rng| {
+            format!("{} {}\n", params.get("n").unwrap(), rng.random_range(1..=params.get("maxval").unwrap()))
+        });
+
+        let report = Task::new("params", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_seed(1)
+            .with_solution_source(solution)
+            .with_subtask(subtask)
+            .with_partial_solution("bad", bad_solution, &[])
+            .with_min_failures(20)
+            .with_max_tries(300)
+            .run()
+            .unwrap();
+
+        let subtask_report = &report.subtasks[0];
+        let robust = subtask_report.robust.as_ref().unwrap();
+        assert!(robust.is_complete(), "{robust:?}");
+
+        // every test records its parameters and is reproduced by them
+        let seeds = fs::read_to_string(task_path.join("seeds.txt")).unwrap();
+        let robust_tests = subtask_report.tests.iter().filter(|test| test.params.get("n").unwrap() >= 980_000).count();
+        assert!(robust_tests >= 20);
+        let subtask = Subtask::new(100, "all").with_param_test(5, ParamSpace::new(), |params, rng| {
+            format!("{} {}\n", params.get("n").unwrap(), rng.random_range(1..=params.get("maxval").unwrap()))
+        });
+        for test in &subtask_report.tests {
+            let input = fs::read_to_string(&test.input_file).unwrap();
+            assert_eq!(subtask.generate_test_with_params(0, test.seed.unwrap(), &test.params), input);
+            assert!(seeds.contains(&format!(" {} {}\n", test.seed.unwrap(), test.params)));
+        }
+
+        let json = fs::read_to_string(task_path.join("report.json")).unwrap();
+        assert!(json.contains("\"params\": {\"n\": "));
+    }
+}
@@ -0,0 +1,174 @@
+/// Tests for partial scoring (`with_scoring_checker`, `ScoreAggregation`, `Solution::with_expected_score`).
+///
+/// The task used in the end-to-end tests is an optimisation problem: given an even
+/// number `n`, print a number `x` with `0 <= x <= n`. The output gets `x / n` of the points.
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod partial_scoring_tests {
+    use crate::runner::checker::CheckerVerdict;
+    use crate::runner::cpp_runner::CppRunner;
+    use crate::tests::test_shared::initialize_logger;
+    use crate::{Error, ScoreAggregation, Solution, Subtask, Task};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use tempfile::TempDir;
+
+    fn fraction_checker(input: &str, _correct: &str, program: &str) -> (f64, String) {
+        let n = input.trim().parse::<f64>().unwrap();
+        match program.trim().parse::<f64>() {
+            Ok(x) if (0.0..=n).contains(&x) => (x / n, format!("{x} out of {n}")),
+            _ => (0.0, "invalid output".to_owned()),
+        }
+    }
+
+    fn even_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{}\n", 2 * rng.random_range(1..1000))
+    }
+
+    const PRINTS_N: &str = "
+    #include <iostream>
+    int main() { long long n; std::cin >> n; std::cout << n << std::endl; }
+    ";
+
+    const PRINTS_HALF: &str = "
+    #include <iostream>
+    int main() { long long n; std::cin >> n; std::cout << n / 2 << std::endl; }
+    ";
+
+    const PRINTS_ZERO: &str = "
+    #include <iostream>
+    int main() { std::cout << 0 << std::endl; }
+    ";
+
+    fn scoring_task(task_path: &std::path::Path, aggregation: ScoreAggregation) -> Task<String> {
+        Task::new("partial scoring", task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_scoring_checker(fraction_checker)
+            .with_solution_source(PRINTS_N)
+            .with_subtask(Subtask::new(40, "").with_test(5, even_generator).with_aggregation(aggregation))
+    }
+
+    #[test]
+    fn test_score_aggregation() {
+        assert!((ScoreAggregation::Min.aggregate(&[1.0, 0.5, 0.25]) - 0.25).abs() < 1e-9);
+        assert!((ScoreAggregation::Sum.aggregate(&[1.0, 0.5, 0.0, 0.5]) - 0.5).abs() < 1e-9);
+        assert!((ScoreAggregation::Min.aggregate(&[]) - 1.0).abs() < 1e-9);
+        assert_eq!(ScoreAggregation::default(), ScoreAggregation::Min);
+    }
+
+    #[test]
+    fn test_verdict_from_score() {
+        assert_eq!(CheckerVerdict::from_score(1.0, "ok".to_owned()).unwrap(), CheckerVerdict::Accepted);
+        assert_eq!(CheckerVerdict::from_score(0.0, "no".to_owned()).unwrap(), CheckerVerdict::WrongAnswer("no".to_owned()));
+        assert_eq!(CheckerVerdict::from_score(0.25, "some".to_owned()).unwrap(), CheckerVerdict::PartiallyCorrect(0.25, "some".to_owned()));
+        assert!((CheckerVerdict::PartiallyCorrect(0.25, String::new()).score() - 0.25).abs() < 1e-9);
+        assert!(matches!(CheckerVerdict::from_score(1.5, String::new()), Err(Error::CheckerFailed { code: None, .. })));
+        assert!(matches!(CheckerVerdict::from_score(-0.5, String::new()), Err(Error::CheckerFailed { code: None, .. })));
+    }
+
+    #[test]
+    fn test_checker_program_points() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+
+        // same output as testlib quitp(0.5, "half")
+        let checker = runner.add_program("#include <iostream>\nint main() { std::cerr << \"points 0.5 half\"; return 7; }").unwrap();
+        runner.set_checker(Some(checker));
+        assert_eq!(runner.run_checker("", "", "").unwrap(), Some(CheckerVerdict::PartiallyCorrect(0.5, "half".to_owned())));
+
+        let checker = runner.add_program("#include <iostream>\nint main() { std::cerr << \"0.75\"; return 7; }").unwrap();
+        runner.set_checker(Some(checker));
+        assert_eq!(runner.run_checker("", "", "").unwrap(), Some(CheckerVerdict::PartiallyCorrect(0.75, String::new())));
+
+        let checker = runner.add_program("#include <iostream>\nint main() { std::cerr << \"points many\"; return 7; }").unwrap();
+        runner.set_checker(Some(checker));
+        assert!(matches!(runner.run_checker("", "", ""), Err(Error::CheckerFailed { code: Some(7), .. })));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_sum_aggregation_expected_score() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        scoring_task(&task_path, ScoreAggregation::Sum)
+            .with_partial(Solution::new("half".to_owned(), PRINTS_HALF.to_owned(), &[]).with_expected_score(0, 0.5..=0.5))
+            .with_partial(Solution::new("zero".to_owned(), PRINTS_ZERO.to_owned(), &[]).with_expected_score(0, 0.0..=0.0))
+            .run()
+            .unwrap();
+
+        let results = std::fs::read_to_string(task_path.join("results.txt")).unwrap();
+        assert!(results.contains("Points 20/40"), "{results}");
+        assert!(results.contains("Points 0/40"), "{results}");
+        assert!(results.contains("Points 40/40"), "{results}");
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_min_aggregation_partial_solution() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        // n / 4 * 2 gets exactly half of the points when n is a multiple of 4 and a bit less otherwise
+        let rounded = "
+        #include <iostream>
+        int main() { long long n; std::cin >> n; std::cout << n / 4 * 2 << std::endl; }
+        ";
+
+        scoring_task(&task_path, ScoreAggregation::Min)
+            .with_partial(Solution::new("rounded".to_owned(), rounded.to_owned(), &[]).with_expected_score(0, 0.0..=0.5))
+            .with_min_failures(3)
+            .run()
+            .unwrap();
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_score_out_of_range() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        let result = scoring_task(&task_path, ScoreAggregation::Sum)
+            .with_partial(Solution::new("half".to_owned(), PRINTS_HALF.to_owned(), &[]).with_expected_score(0, 0.9..=1.0))
+            .run();
+        assert!(
+            matches!(&result, Err(Error::PartialSolutionScoreOutOfRange { partial_number: 1, subtask_number: 1, score, .. }) if (score - 0.5).abs() < 1e-9),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_partially_correct_good_solution_fails() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        let result = scoring_task(&task_path, ScoreAggregation::Sum).with_partial_solution("half", PRINTS_HALF, &[0]).run();
+        assert!(
+            matches!(&result, Err(Error::PartialSolutionFailsSubtask { verdict, .. }) if verdict.starts_with("PC 0.50")),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_expected_score_roles() {
+        let solution = Solution::new("s".to_owned(), String::new(), &[0, 1])
+            .with_expected_score(0, 0.2..=0.4)
+            .with_expected_score(1, 0.5..=1.0)
+            .with_expected_score(2, 1.0..=1.0);
+        assert!(solution.should_fail(0));
+        assert!(!solution.should_fail(1));
+        assert!(!solution.passes_subtasks.contains(&1));
+        assert!(!solution.should_fail(2));
+        assert!(solution.passes_subtasks.contains(&2));
+    }
+}
@@ -1,7 +1,7 @@
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod partial_solution_tests {
-    use crate::array_generator;
+    use crate::{Verdict, array_generator};
     use crate::tests::generic_tests::generic_tests::Test;
 
     #[test]
@@ -242,4 +242,71 @@ mod partial_solution_tests {
 
         task.test();
     }
+
+    #[test]
+    fn test_partial_solution_mle() {
+        let mut task = Test::new();
+        task.task = task.task.with_memory_limit(64);
+
+        // create solution file
+        let solution_contents = r#"
+        #include <iostream>
+        using namespace std;
+
+        int main() {
+            int n;
+            cin>>n;
+            long long sum=0;
+            for(int i=0;i<n;i++) {
+                int a;
+                cin>>a;
+                sum+=a;
+            }
+            cout<<sum<<"\n";
+            return 0;
+        }
+        "#;
+
+        task.task = task.task.with_solution_source(solution_contents);
+
+        // create partial solution file (it uses n^2 memory)
+        let partial_solution_contents = r#"
+        #include <iostream>
+        #include <vector>
+        using namespace std;
+
+        int main() {
+            int n;
+            cin>>n;
+            vector<vector<int>> table(n, vector<int>(n, 1));
+            long long sum=0;
+            for(int i=0;i<n;i++) {
+                int a;
+                cin>>a;
+                sum+=a*table[i][n-1-i];
+            }
+            cout<<sum<<"\n";
+            return 0;
+        }
+        "#;
+
+        // subtask 1, n is at most 100
+        let subtask1 = crate::Subtask::new(0, "").with_test(5, array_generator(1, 100, 1, 100));
+
+        // subtask 2, n is at least 5000
+        let subtask2 = crate::Subtask::new(0, "").with_test(5, array_generator(5000, 10000, 1, 100));
+
+        // create subtasks
+        task.task = task
+            .task
+            .with_subtask(subtask1)
+            .with_subtask(subtask2)
+            // add partial solutions
+            .with_partial_solution("partial", partial_solution_contents, &[0]);
+
+        let report = task.task.run().unwrap();
+        let partial = &report.solutions[1];
+        assert_eq!(partial.name, "partial");
+        assert!(partial.subtasks[1].verdicts.contains_key(&Verdict::MemoryLimitExceeded), "{:?}", partial.subtasks[1].verdicts);
+    }
 }
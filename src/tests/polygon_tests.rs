@@ -0,0 +1,194 @@
+/// Tests for the Polygon package export (`Task::with_export_format(ExportFormat::Polygon)`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod polygon_tests {
+    use crate::export::TOKEN_CHECKER;
+    use crate::runner::checker::CheckerVerdict;
+    use crate::runner::cpp_runner::CppRunner;
+    use crate::tests::test_shared::initialize_logger;
+    use crate::{Error, ExportFormat, Language, ScoreAggregation, Subtask, Task};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::collections::HashMap;
+    use std::io::Read;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Overflows for large numbers.
+    const INT_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    const PYTHON_SOLUTION: &str = "a, b = map(int, input().split())\nprint(a + b)\n";
+
+    fn small_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))
+    }
+
+    fn large_generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(2_000_000_000..3_000_000_000_i64), rng.random_range(2_000_000_000..3_000_000_000_i64))
+    }
+
+    fn read_zip(path: &Path) -> HashMap<String, String> {
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut files = HashMap::new();
+        for i in 0..zip.len() {
+            let mut file = zip.by_index(i).unwrap();
+            let mut contents = String::new();
+            file.read_to_string(&mut contents).unwrap();
+            files.insert(file.name().to_owned(), contents);
+        }
+        files
+    }
+
+    #[test]
+    fn test_polygon_package() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        Task::new("A + B Problem", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Polygon)
+            .with_time_limit(1000)
+            .with_memory_limit(256)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(30, "small").with_test(3, small_generator))
+            .with_subtask(Subtask::new(70, "large").with_test(2, large_generator).with_aggregation(ScoreAggregation::Sum))
+            .with_partial_solution("int overflow", INT_SOLUTION, &[0])
+            .with_partial_solution_lang("python", PYTHON_SOLUTION, Language::Python, &[0, 1])
+            .with_min_failures(2)
+            .run()
+            .unwrap();
+
+        assert!(!task_path.join("tests.zip").exists());
+        let files = read_zip(&task_path.join("polygon.zip"));
+        let tests = files.keys().filter(|name| name.starts_with("tests/")).count();
+        assert_eq!(tests, 2 * (3 + 4));
+        assert_eq!(files["tests/01"].split_whitespace().count(), 2);
+        assert!(files["tests/07.a"].trim().parse::<i64>().unwrap() >= 4_000_000_000);
+        assert_eq!(files["files/check.cpp"], TOKEN_CHECKER);
+        assert_eq!(files["solutions/main.cpp"], SOLUTION);
+        assert_eq!(files["solutions/int-overflow.cpp"], INT_SOLUTION);
+        assert_eq!(files["solutions/python.py"], PYTHON_SOLUTION);
+
+        let xml = &files["problem.xml"];
+        assert!(xml.contains(r#"<problem revision="1" short-name="a-b-problem">"#), "{xml}");
+        assert!(xml.contains(r#"<name language="english" value="A + B Problem"/>"#));
+        assert!(xml.contains("<time-limit>1000</time-limit>"));
+        assert!(xml.contains("<memory-limit>268435456</memory-limit>"));
+        assert!(xml.contains("<test-count>7</test-count>"));
+        assert_eq!(xml.matches(r#"<test method="manual" group="1"/>"#).count(), 3);
+        assert_eq!(xml.matches(r#"<test method="manual" group="2" points="17.5"/>"#).count(), 4);
+        assert!(xml.contains(r#"<group feedback-policy="complete" name="1" points="30" points-policy="complete-group"/>"#));
+        assert!(xml.contains(r#"<group feedback-policy="complete" name="2" points="70" points-policy="each-test"/>"#));
+        assert!(xml.contains(r#"<source path="files/check.cpp" type="cpp.g++17"/>"#));
+        assert!(xml.contains("<solution tag=\"main\">\n                <source path=\"solutions/main.cpp\" type=\"cpp.g++17\"/>"));
+        assert!(xml.contains("<solution tag=\"rejected\">\n                <source path=\"solutions/int-overflow.cpp\" type=\"cpp.g++17\"/>"));
+        assert!(xml.contains("<solution tag=\"accepted\">\n                <source path=\"solutions/python.py\" type=\"python.3\"/>"));
+        assert!(!xml.contains("<interactor>"));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_polygon_package_needs_memory_limit() {
+        let tempdir = TempDir::new().unwrap();
+
+        let result = Task::new("unlimited", tempdir.path())
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Polygon)
+            .with_memory_limit(0)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "small").with_test(2, small_generator))
+            .run();
+        assert!(matches!(&result, Err(Error::MissingMemoryLimit { format }) if format == "Polygon"), "got {result:?}");
+        assert!(!tempdir.path().join("polygon.zip").exists());
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_polygon_package_with_checker_and_interactor() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        let interactor = r#"
+        #include <fstream>
+        #include <iostream>
+        int main(int argc, char* argv[]) {
+            std::ifstream input(argv[1]);
+            std::ofstream output(argv[2]);
+            long long a, b, c;
+            input >> a >> b;
+            std::cout << a << " " << b << std::endl;
+            std::cin >> c;
+            output << c << std::endl;
+            return c == a + b ? 0 : 1;
+        }
+        "#;
+
+        Task::new("interactive", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Polygon)
+            .with_interactor(interactor)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "").with_test(2, small_generator))
+            .run()
+            .unwrap();
+
+        let files = read_zip(&task_path.join("polygon.zip"));
+        assert_eq!(files["files/interactor.cpp"], interactor);
+        assert!(files["problem.xml"].contains("<interactor>\n            <source path=\"files/interactor.cpp\" type=\"cpp.g++17\"/>"));
+
+        let task_path = tempdir.path().join("checker");
+        let checker = "int main() { return 0; }";
+        Task::new("checker", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Polygon)
+            .with_checker_source(checker)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "").with_test(2, small_generator))
+            .run()
+            .unwrap();
+
+        let files = read_zip(&task_path.join("polygon.zip"));
+        assert_eq!(files["files/check.cpp"], checker);
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_token_checker() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+        let checker = runner.add_program(TOKEN_CHECKER).unwrap();
+        runner.set_checker(Some(checker));
+
+        assert_eq!(runner.run_checker("", "1  2\n3\n\n", "1 2 3\n").unwrap(), Some(CheckerVerdict::Accepted));
+        assert_eq!(runner.run_checker("", "", "").unwrap(), Some(CheckerVerdict::Accepted));
+        assert_eq!(
+            runner.run_checker("", "1 5 3\n", "1 2 3\n").unwrap(),
+            Some(CheckerVerdict::WrongAnswer("token 2 differs: expected 2, found 5".to_owned()))
+        );
+        assert_eq!(
+            runner.run_checker("", "1 2\n", "1 2 3\n").unwrap(),
+            Some(CheckerVerdict::WrongAnswer("output ended before token 3, expected 3".to_owned()))
+        );
+        assert_eq!(
+            runner.run_checker("", "1 2 3 4\n", "1 2 3\n").unwrap(),
+            Some(CheckerVerdict::WrongAnswer("extra token 4: 4".to_owned()))
+        );
+
+        drop(tempdir);
+    }
+}
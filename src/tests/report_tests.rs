@@ -0,0 +1,114 @@
+/// Tests for the report of a run (`Task::run` and `report.json`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod report_tests {
+    use crate::{Error, Solution, Subtask, Task, Verdict};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Overflows for large numbers.
+    const INT_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    #[test]
+    fn test_report_of_run() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        let report = Task::new("report", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_seed(7)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(40, "small").with_seeded_test(2, |rng| format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))))
+            .with_subtask(Subtask::new(60, "large").with_seeded_test(1, |rng| format!("{} {}\n", rng.random_range(0..3_000_000_000_i64), rng.random_range(0..3_000_000_000_i64))))
+            .with_partial_solution("int", INT_SOLUTION, &[0])
+            .with_min_failures(2)
+            .run()
+            .unwrap();
+
+        assert_eq!(report.seed, 7);
+        assert_eq!(report.subtasks.len(), 2);
+        assert_eq!(report.subtasks[0].tests.len(), 2);
+        assert!(report.subtasks[0].robust.as_ref().is_some_and(|robust| robust.target == 0));
+        let robust = report.subtasks[1].robust.as_ref().unwrap();
+        assert_eq!((robust.found, robust.target), (2, 2));
+        assert!(robust.tries >= 2);
+        assert_eq!(report.subtasks[1].tests.len(), 1 + 2);
+        assert!(report.subtasks[1].tests.iter().all(|test| test.generator == Some(0) && test.seed.is_some()));
+        let tests_size: u64 = report.subtasks.iter().map(|subtask| subtask.size).sum();
+        assert!(tests_size > 0 && tests_size <= report.tests_size);
+
+        assert_eq!(report.solutions.len(), 2);
+        assert_eq!(report.solutions[0].name, "main");
+        assert_eq!(report.solutions[0].max_points, 100);
+        assert!(report.solutions[0].subtasks.iter().all(crate::SubtaskReport::passed));
+        let partial = &report.solutions[1];
+        assert_eq!(partial.name, "int");
+        assert!((partial.points - 40.0).abs() < 1e-9, "got {}", partial.points);
+        assert!(partial.subtasks[1].tests.iter().any(|test| test.verdict == Verdict::WrongAnswer));
+        assert!(partial.subtasks.iter().flat_map(|subtask| &subtask.tests).all(|test| test.generator == Some(0)));
+
+        let json = fs::read_to_string(task_path.join("report.json")).unwrap();
+        assert_eq!(json, report.to_json());
+        assert!(json.contains("\"name\": \"report\""));
+        assert!(json.contains("\"seed\": 7"));
+        assert!(json.contains("\"robust\": {\"found\": 2, \"target\": 2"));
+        assert!(json.contains("\"verdict\": \"WA\""));
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+        assert_eq!(json.matches('[').count(), json.matches(']').count());
+
+        let html = fs::read_to_string(task_path.join("report.html")).unwrap();
+        assert_eq!(html, report.to_html(&task_path));
+        assert!(html.contains("<th>main</th><th>int</th>"));
+        assert!(html.contains("Subtask 2: large"));
+        assert!(html.contains("<th>40/40</th><th>40/40</th>"));
+        assert!(html.contains("<th>100/100</th><th>40/100</th>"));
+        assert!(html.contains("class=\"failed\""));
+        assert!(html.contains("Slowest correct run"));
+        assert_eq!(html.matches("<tr><td class=\"test\"><a href=").count(), 2 + 3);
+        let input_file = &report.subtasks[0].tests[0].input_file;
+        let link = input_file.strip_prefix(&task_path).unwrap().to_string_lossy().replace('\\', "/");
+        assert!(html.contains(&format!("<a href=\"{link}\">")));
+        let (solution_name, slowest) = report.slowest_correct_run().unwrap();
+        assert_eq!(solution_name, "main");
+        assert!(slowest.time.unwrap() <= report.time_limit);
+
+        let mut unlimited = report.clone();
+        unlimited.time_limit = 0;
+        let html = unlimited.to_html(&task_path);
+        assert!(!html.contains("Slowest correct run"));
+        assert!(!html.contains("inf") && !html.contains("NaN"));
+    }
+
+    #[test]
+    fn test_report_written_when_solution_fails() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        let result = Task::new("report", &task_path)
+            .with_seed(7)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(40, "small").with_seeded_test(2, |rng| format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))))
+            .with_subtask(Subtask::new(60, "large").with_seeded_test(1, |rng| format!("{} {}\n", rng.random_range(0..3_000_000_000_i64), rng.random_range(0..3_000_000_000_i64))))
+            .with_partial(Solution::new("int".to_owned(), INT_SOLUTION.to_owned(), &[0]).with_expected_verdicts(1, &[Verdict::TimedOut]))
+            .with_min_failures(0)
+            .run();
+
+        assert!(matches!(result, Err(Error::PartialSolutionUnexpectedVerdict { .. })), "got {result:?}");
+        let json = fs::read_to_string(task_path.join("report.json")).unwrap();
+        assert!(json.contains("\"name\": \"main\""));
+        assert!(json.contains("\"name\": \"int\""));
+        assert!(json.contains("\"verdict\": \"WA\""));
+
+        let html = fs::read_to_string(task_path.join("report.html")).unwrap();
+        assert!(html.contains("<th>main</th><th>int</th>"));
+        assert!(html.contains("class=\"failed\""));
+    }
+}
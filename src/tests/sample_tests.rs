@@ -0,0 +1,150 @@
+/// Tests for sample tests (`Task::with_sample` and `Task::with_sample_output`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod sample_tests {
+    use crate::{Error, ExportFormat, Subtask, Task};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io::Read;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Overflows for large numbers.
+    const INT_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    fn generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))
+    }
+
+    fn read_zip(path: &Path) -> HashMap<String, String> {
+        let mut zip = zip::ZipArchive::new(fs::File::open(path).unwrap()).unwrap();
+        let mut files = HashMap::new();
+        for i in 0..zip.len() {
+            let mut file = zip.by_index(i).unwrap();
+            let mut contents = String::new();
+            file.read_to_string(&mut contents).unwrap();
+            files.insert(file.name().to_owned(), contents);
+        }
+        files
+    }
+
+    fn task(name: &str, path: &Path) -> Task<String> {
+        Task::new(name, path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "").with_test(2, generator))
+    }
+
+    #[test]
+    fn test_samples_are_written_first() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        task("samples", &task_path)
+            .with_sample("1 2".to_owned())
+            .with_sample_output("10   20\n".to_owned(), "30")
+            .with_partial_solution("int", INT_SOLUTION, &[0])
+            .run()
+            .unwrap();
+
+        let tests_path = task_path.join("tests");
+        assert_eq!(fs::read_to_string(tests_path.join("sample.01.in")).unwrap(), "1 2\n");
+        assert_eq!(fs::read_to_string(tests_path.join("sample.01.out")).unwrap(), "3\n");
+        assert_eq!(fs::read_to_string(tests_path.join("sample.02.in")).unwrap(), "10 20\n");
+        assert_eq!(fs::read_to_string(tests_path.join("sample.02.out")).unwrap(), "30\n");
+        assert!(!tests_path.join("sample.03.in").exists());
+
+        let mut zip = zip::ZipArchive::new(fs::File::open(task_path.join("tests.zip")).unwrap()).unwrap();
+        assert_eq!(zip.by_index(0).unwrap().name(), "sample.01.in");
+        assert_eq!(zip.by_index(3).unwrap().name(), "sample.02.out");
+        assert_eq!(zip.len(), 2 * (2 + 2));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_wrong_sample_output() {
+        let tempdir = TempDir::new().unwrap();
+
+        let result = task("wrong output", tempdir.path()).with_sample_output("1 2\n".to_owned(), "4\n").run();
+        assert!(
+            matches!(&result, Err(Error::SampleFailed { sample_number: 1, solution_name, verdict, .. }) if solution_name == "Main solution" && verdict == "WA"),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_partial_solution_must_pass_samples() {
+        let tempdir = TempDir::new().unwrap();
+
+        // the partial solution is not expected to pass any subtask, but it still has to pass the samples
+        let result = task("partial fails sample", tempdir.path())
+            .with_sample("1 2\n".to_owned())
+            .with_sample("2000000000 2000000000\n".to_owned())
+            .with_partial_solution("int", INT_SOLUTION, &[])
+            .run();
+        assert!(
+            matches!(&result, Err(Error::SampleFailed { sample_number: 2, solution_name, .. }) if solution_name == "Partial solution 1 (int)"),
+            "got {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_exported_samples() {
+        let tempdir = TempDir::new().unwrap();
+
+        let task_path = tempdir.path().join("polygon");
+        task("polygon", &task_path).with_export_format(ExportFormat::Polygon).with_sample("1 2\n".to_owned()).run().unwrap();
+        let files = read_zip(&task_path.join("polygon.zip"));
+        assert_eq!(files["tests/01"], "1 2\n");
+        assert_eq!(files["tests/01.a"], "3\n");
+        let xml = &files["problem.xml"];
+        assert!(xml.contains("<test-count>3</test-count>"), "{xml}");
+        assert!(xml.contains("<tests>\n                <test method=\"manual\" sample=\"true\" group=\"0\"/>\n                <test method=\"manual\" group=\"1\"/>"));
+        assert!(xml.contains(r#"<group feedback-policy="complete" name="0" points="0" points-policy="complete-group"/>"#));
+
+        let task_path = tempdir.path().join("cms");
+        task("cms", &task_path)
+            .with_export_format(ExportFormat::Cms)
+            .with_sample("1 2\n".to_owned())
+            .with_sample("3 4\n".to_owned())
+            .run()
+            .unwrap();
+        let cms_path = task_path.join("cms");
+        assert_eq!(fs::read_to_string(cms_path.join("input").join("input1.txt")).unwrap(), "3 4\n");
+        assert_eq!(fs::read_to_string(cms_path.join("output").join("output1.txt")).unwrap(), "7\n");
+        let yaml = fs::read_to_string(cms_path.join("task.yaml")).unwrap();
+        assert!(yaml.contains("n_input: 4\n"), "{yaml}");
+        assert!(yaml.contains("public_testcases: 0, 1\n"));
+        assert!(yaml.contains("score_type_parameters: [[0, 2], [100, 2]]\n"));
+        assert!(
+            fs::read_to_string(cms_path.join("gen").join("GEN"))
+                .unwrap()
+                .starts_with("#ST: 0\n#COPY: input/input0.txt\n#COPY: input/input1.txt\n#ST: 100\n")
+        );
+
+        let task_path = tempdir.path().join("kattis");
+        task("kattis", &task_path).with_export_format(ExportFormat::Kattis).with_sample("1 2\n".to_owned()).run().unwrap();
+        let files = read_zip(&task_path.join("kattis.zip"));
+        assert_eq!(files["data/sample/01.in"], "1 2\n");
+        assert_eq!(files["data/sample/01.ans"], "3\n");
+        assert_eq!(files.keys().filter(|name| name.starts_with("data/secret/subtask1/")).count(), 2 * 2 + 1);
+
+        drop(tempdir);
+    }
+}
@@ -0,0 +1,173 @@
+/// Tests for deterministic test generation (`Task::with_seed`, `Subtask::with_seeded_test`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod seed_tests {
+    use crate::{GeneratorRng, Graph, Subtask, Task, ToOutput, array_generator_with_rng};
+    use log::LevelFilter;
+    use rand::{RngExt, SeedableRng};
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const SUM_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int n; std::cin >> n; long long s = 0; for (int i = 0; i < n; i++) { long long x; std::cin >> x; s += x; } std::cout << s << std::endl; }
+    ";
+
+    /// Only adds the first element.
+    const FIRST_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int n; long long x = 0; std::cin >> n >> x; std::cout << x << std::endl; }
+    ";
+
+    const EDGES_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int n, m; std::cin >> n >> m; std::cout << m << std::endl; }
+    ";
+
+    fn array_subtask() -> Subtask<String> {
+        Subtask::new(50, "arrays")
+            .with_seeded_test(3, array_generator_with_rng(1, 10, 1, 100))
+            .with_seeded_test(2, |rng| format!("1\n{}\n", rng.random_range(1..=100)))
+    }
+
+    fn graph_subtask() -> Subtask<Graph> {
+        Subtask::new(50, "graphs").with_seeded_test(4, |rng| {
+            let n = rng.random_range(2..=20);
+            Graph::new_random_connected_with_rng(n, rng.random_range(n - 1..=2 * n), rng)
+        })
+    }
+
+    fn run_array_task(task_path: &Path, seed: u64) {
+        Task::new("seeded", task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_seed(seed)
+            .trim_whitespace(false)
+            .with_solution_source(SUM_SOLUTION)
+            .with_subtask(array_subtask())
+            .with_subtask(array_subtask())
+            .with_partial_solution("first", FIRST_SOLUTION, &[])
+            .run()
+            .unwrap();
+    }
+
+    fn read(path: &Path) -> Vec<u8> {
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    fn test_same_seed_gives_identical_archive() {
+        let tempdir = TempDir::new().unwrap();
+        let first = tempdir.path().join("first");
+        let second = tempdir.path().join("second");
+        run_array_task(&first, 42);
+        run_array_task(&second, 42);
+
+        assert_eq!(read(&first.join("tests.zip")), read(&second.join("tests.zip")));
+        assert_eq!(read(&first.join("seeds.txt")), read(&second.join("seeds.txt")));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_different_seed_gives_different_tests() {
+        let tempdir = TempDir::new().unwrap();
+        let first = tempdir.path().join("first");
+        let second = tempdir.path().join("second");
+        run_array_task(&first, 1);
+        run_array_task(&second, 2);
+
+        assert_ne!(read(&first.join("tests.zip")), read(&second.join("tests.zip")));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_seeds_reproduce_tests() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        run_array_task(&task_path, 7);
+
+        let seeds = std::fs::read_to_string(task_path.join("seeds.txt")).unwrap();
+        assert!(seeds.starts_with("# task seed 7\n"), "{seeds}");
+
+        let subtask = array_subtask();
+        let mut num_tests = 0;
+        for line in seeds.lines().filter(|line| !line.starts_with('#')) {
+            let parts = line.split(' ').collect::<Vec<_>>();
+            let gen_idx = parts[2].parse::<usize>().unwrap() - 1;
+            let seed = parts[3].parse::<u64>().unwrap();
+            let saved = std::fs::read_to_string(task_path.join("tests").join(parts[0])).unwrap();
+            assert_eq!(subtask.generate_test(gen_idx, seed), saved, "test {}", parts[0]);
+            num_tests += 1;
+        }
+        assert_eq!(num_tests, std::fs::read_dir(task_path.join("tests")).unwrap().count() / 2);
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_seeded_graphs() {
+        let tempdir = TempDir::new().unwrap();
+        let run = |name: &str| {
+            let task_path = tempdir.path().join(name);
+            Task::new("graphs", &task_path)
+                .with_debug_level(LevelFilter::Trace)
+                .with_seed(5)
+                .with_solution_source(EDGES_SOLUTION)
+                .with_subtask(graph_subtask())
+                .run()
+                .unwrap();
+            read(&task_path.join("tests.zip"))
+        };
+        assert_eq!(run("first"), run("second"));
+
+        let graph_output = |seed| {
+            let mut rng = GeneratorRng::seed_from_u64(seed);
+            Graph::new_random_bipartite_with_rng(30, 50, &mut rng).to_output_with_rng(&mut rng)
+        };
+        assert_eq!(graph_output(3), graph_output(3));
+        assert_ne!(graph_output(3), graph_output(4));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_nested_graph_output_is_seeded() {
+        #[derive(ToOutput)]
+        struct Input {
+            graphs: Vec<Graph>,
+            query: (i32, Graph),
+        }
+
+        let input_output = |seed| {
+            let mut rng = GeneratorRng::seed_from_u64(seed);
+            let input = Input {
+                graphs: vec![Graph::new_random_with_rng(10, 20, &mut rng), Graph::new_random_tree_with_rng(10, &mut rng)],
+                query: (7, Graph::new_random_with_rng(10, 20, &mut rng)),
+            };
+            input.to_output_with_rng(&mut rng)
+        };
+        assert_eq!(input_output(3), input_output(3));
+        assert_ne!(input_output(3), input_output(4));
+    }
+
+    #[test]
+    fn test_random_seed_is_recorded() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        Task::new("unseeded", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_solution_source(SUM_SOLUTION)
+            .with_subtask(Subtask::new(100, "").with_test(2, || "1\n5\n".to_owned()))
+            .run()
+            .unwrap();
+
+        let seeds = std::fs::read_to_string(task_path.join("seeds.txt")).unwrap();
+        assert!(seeds.starts_with("# task seed "));
+        assert_eq!(seeds.lines().count(), 2 + 1);
+        let results = std::fs::read_to_string(task_path.join("results.txt")).unwrap();
+        assert!(results.starts_with("Seed: "), "{results}");
+
+        drop(tempdir);
+    }
+}
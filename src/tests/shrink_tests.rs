@@ -0,0 +1,77 @@
+/// Tests for the minimization of failing tests (`shrink::minimize`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod shrink_tests {
+    use crate::shrink::minimize;
+    use crate::{Subtask, Task};
+    use rand::RngExt;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_minimize_deletes_lines_and_tokens() {
+        let input = "5\n1 2 3\n4 13 6\n7 8 9\n";
+        let minimized = minimize(input, 1000, |candidate| Ok(candidate.split_whitespace().any(|token| token == "13"))).unwrap();
+        assert_eq!(minimized, "13\n");
+    }
+
+    #[test]
+    fn test_minimize_halves_numbers() {
+        let input = "3\n100 200 300\n";
+        let minimized = minimize(input, 1000, |candidate| {
+            let numbers: Vec<i64> = candidate.split_whitespace().map(|token| token.parse().unwrap()).collect();
+            Ok(numbers.iter().sum::<i64>() >= 50)
+        })
+        .unwrap();
+        assert_eq!(minimized, "75\n");
+    }
+
+    #[test]
+    fn test_minimize_stops_after_max_tries() {
+        let input = "1  2 3 4 5 6 7 8\n\n";
+        let mut tries = 0;
+        let minimized = minimize(input, 3, |_| {
+            tries += 1;
+            Ok(false)
+        })
+        .unwrap();
+        assert_eq!(tries, 3);
+        assert_eq!(minimized, input);
+    }
+
+    #[test]
+    fn test_failing_test_is_minimized() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        let solution = "
+        #include <iostream>
+        #include <algorithm>
+        int main() { int n; std::cin >> n; int m = -1; for (int i = 0; i < n; i++) { int x = 0; std::cin >> x; m = std::max(m, x); } std::cout << m << std::endl; }
+        ";
+        // Wrong when the maximum is larger than 90
+        let wrong_solution = "
+        #include <iostream>
+        #include <algorithm>
+        int main() { int n; std::cin >> n; int m = -1; for (int i = 0; i < n; i++) { int x = 0; std::cin >> x; m = std::max(m, x); } std::cout << std::min(m, 90) << std::endl; }
+        ";
+
+        let subtask = Subtask::new(100, "all").with_seeded_test(5, |rng| {
+            let values: Vec<String> = (0..100).map(|_| rng.random_range(0..100).to_string()).collect();
+            format!("100\n{}\n", values.join(" "))
+        });
+        let result = Task::new("shrink", &task_path)
+            .with_seed(1)
+            .with_solution_source(solution)
+            .with_subtask(subtask)
+            .with_partial_solution("wrong", wrong_solution, &[0])
+            .run();
+        assert!(matches!(result, Err(crate::Error::PartialSolutionFailsSubtask { .. })));
+
+        let failing_test = fs::read_to_string(task_path.join("failing_test.in")).unwrap();
+        let minimized = fs::read_to_string(task_path.join("failing_test_min.in")).unwrap();
+        assert!(minimized.len() < 10, "{minimized}");
+        assert!(minimized.len() < failing_test.len());
+        assert!(minimized.split_whitespace().any(|token| token.parse::<i32>().unwrap() > 90));
+    }
+}
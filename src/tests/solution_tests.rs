@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod solution_tests {
-    use crate::Solution;
+    use crate::{Language, Solution};
+    use std::path::Path;
 
     #[test]
     fn test_solution_new_basic() {
@@ -60,4 +61,20 @@ mod solution_tests {
         assert!(!sol.should_fail(1));
         assert_eq!(sol.passes_subtasks.len(), 1);
     }
+
+    #[test]
+    fn test_solution_language() {
+        let sol = Solution::new("sol_lang".to_owned(), "src".to_owned(), &[0]);
+        assert_eq!(sol.language, Language::Cpp);
+        let sol = sol.with_language(Language::Python);
+        assert_eq!(sol.language, Language::Python);
+    }
+
+    #[test]
+    fn test_solution_from_file_detects_language() {
+        let sol = Solution::from_file("sol_file".to_owned(), Path::new("sol/brute.py"), &[0]).unwrap();
+        assert_eq!(sol.language, Language::Python);
+        assert_eq!(sol.source_file.as_deref(), Some(Path::new("sol/brute.py")));
+        assert!(!sol.should_fail(0));
+    }
 }
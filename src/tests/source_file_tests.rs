@@ -0,0 +1,133 @@
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod source_file_tests {
+    use crate::Error;
+    use crate::Language;
+    use crate::runner::cpp_runner::CppRunner;
+    use crate::runner::exec_runner::RunResult;
+    use crate::tests::generic_tests::generic_tests::Test;
+    use crate::tests::test_shared::initialize_logger;
+    use crate::{Subtask, array_generator};
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = r#"
+    #include <iostream>
+    using namespace std;
+
+    int main() {
+        int n;
+        cin>>n;
+        long long sum=0;
+        for(int i=0;i<n;i++) {
+            int a;
+            cin>>a;
+            sum+=a;
+        }
+        cout<<sum<<"\n";
+        return 0;
+    }
+    "#;
+
+    const PARTIAL_SOLUTION: &str = "
+n = int(input())
+a = list(map(int, input().split()))
+print(a[0])
+";
+
+    #[test]
+    fn test_task_with_solution_files() {
+        let mut task = Test::new();
+
+        let sources = TempDir::new().unwrap();
+        let solution_path = sources.path().join("sol.cpp");
+        let partial_path = sources.path().join("first.py");
+        std::fs::write(&solution_path, SOLUTION).unwrap();
+        std::fs::write(&partial_path, PARTIAL_SOLUTION).unwrap();
+
+        let subtask1 = Subtask::new(0, "").with_test(5, array_generator(1, 1, 1, 100));
+        let subtask2 = Subtask::new(0, "").with_test(5, array_generator(2, 100, 1, 100));
+
+        task.task = task
+            .task
+            .with_solution_file(&solution_path)
+            .unwrap()
+            .with_subtask(subtask1)
+            .with_subtask(subtask2)
+            .with_partial_solution_file("first", &partial_path, &[0])
+            .unwrap();
+
+        task.test();
+        drop(sources);
+    }
+
+    #[test]
+    fn test_missing_solution_file() {
+        let mut task = Test::<String>::new();
+        let path = task.task.problem_path.join("missing.cpp");
+        task.task = task.task.with_solution_file(&path).unwrap();
+
+        assert!(matches!(task.task.run(), Err(Error::IOError { .. })));
+    }
+
+    #[test]
+    fn test_unsupported_solution_file() {
+        let task = Test::<String>::new();
+        let path = task.task.problem_path.join("sol.java");
+        assert!(matches!(task.task.with_solution_file(&path), Err(Error::UnsupportedLanguage { .. })));
+
+        let task = Test::<String>::new();
+        let path = task.task.problem_path.join("partial.java");
+        assert!(matches!(task.task.with_partial_solution_file("partial", &path, &[]), Err(Error::UnsupportedLanguage { .. })));
+    }
+
+    #[test]
+    fn test_compile_error_reports_file_name() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(&tempdir.path().join("build")).unwrap();
+
+        let source_path = tempdir.path().join("broken_solution.cpp");
+        std::fs::write(&source_path, "int main() {\n    this is a compile error\n}\n").unwrap();
+
+        let result = runner.add_program_file(&source_path, &std::fs::read_to_string(&source_path).unwrap(), Language::Cpp);
+        assert!(
+            matches!(&result, Err(Error::CompilerError { stderr, .. }) if stderr.contains("broken_solution.cpp:2")),
+            "error does not point to the file: {result:?}"
+        );
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_source_file_rebuilt_only_on_change() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let build_path = tempdir.path().join("build");
+        let source_path = tempdir.path().join("print.cpp");
+        let program = |value: i32| format!("#include <iostream>\nint main() {{ std::cout << {value} << std::endl; }}\n");
+
+        let run = |expected: &str| {
+            let mut runner = CppRunner::new(&build_path).unwrap();
+            let handle = runner.add_program_file(&source_path, &std::fs::read_to_string(&source_path).unwrap(), Language::Cpp).unwrap();
+            let result = runner.check_programs("", &[handle], 1000, 0).unwrap().remove(0);
+            assert!(matches!(&result, RunResult::Ok(_, _, output) if output.trim() == expected), "got {result:?}");
+        };
+
+        std::fs::write(&source_path, program(1)).unwrap();
+        run("1");
+        let num_files = std::fs::read_dir(&build_path).unwrap().count();
+
+        // unchanged file reuses the binary
+        run("1");
+        assert_eq!(std::fs::read_dir(&build_path).unwrap().count(), num_files);
+
+        // changed file is rebuilt
+        std::fs::write(&source_path, program(2)).unwrap();
+        run("2");
+        assert_eq!(std::fs::read_dir(&build_path).unwrap().count(), num_files + 1);
+
+        drop(tempdir);
+    }
+}
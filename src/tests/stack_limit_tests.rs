@@ -36,13 +36,13 @@ pub mod stack_limit_tests {
         "#;
 
         let program_handle = runner.add_program(program_source).unwrap();
-        let results = runner.check_programs("", &[program_handle], 2000).unwrap();
+        let results = runner.check_programs("", &[program_handle], 2000, 0).unwrap();
         let result = &results[0];
 
         // This is expected to FAIL currently (it will return Crashed due to stack overflow)
         assert!(matches!(result, RunResult::Ok(..)), "Expected OK but got {result:?}");
 
-        if let RunResult::Ok(_, output) = result {
+        if let RunResult::Ok(_, _, output) = result {
             assert_eq!(output.trim(), "Success");
         }
 
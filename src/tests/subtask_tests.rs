@@ -1,7 +1,8 @@
 #[cfg(test)]
 #[allow(clippy::expect_used)]
 mod subtask_tests {
-    use crate::Subtask;
+    use crate::{GeneratorRng, Subtask};
+    use rand::SeedableRng;
 
     #[test]
     fn test_subtask_new() {
@@ -35,14 +36,16 @@ mod subtask_tests {
     #[test]
     fn test_generate_random_test_no_generators_returns_none() {
         let st = Subtask::<String>::new(0, "empty");
-        assert!(st.generate_random_test().is_none());
+        let mut rng = GeneratorRng::seed_from_u64(0);
+        assert!(st.generate_random_test(&mut rng).is_none());
     }
 
     #[test]
     fn test_generate_random_test_single_generator() {
         let st = Subtask::new(0, "t").with_test(1, || "42\n".to_owned());
+        let mut rng = GeneratorRng::seed_from_u64(0);
         for _ in 0..10 {
-            let result = st.generate_random_test();
+            let result = st.generate_random_test(&mut rng);
             assert_eq!(result.map(|x| x.0).as_deref(), Some("42\n"));
         }
     }
@@ -50,13 +53,14 @@ mod subtask_tests {
     #[test]
     fn test_generate_random_test_multiple_generators_returns_one_of_values() {
         let st = Subtask::new(0, "t").with_test(1, || "A".to_owned()).with_test(1, || "B".to_owned()).with_test(1, || "C".to_owned());
+        let mut rng = GeneratorRng::seed_from_u64(0);
 
         let mut seen = std::collections::HashSet::new();
         // Run enough times to likely hit all three generators
         for _ in 0..200 {
-            let val = st.generate_random_test().expect("should return Some");
+            let val = st.generate_random_test(&mut rng).expect("should return Some");
             assert!(["A", "B", "C"].contains(&val.0.as_str()), "unexpected: {}", val.0);
-            seen.insert(val);
+            seen.insert(val.0);
         }
         // With 200 trials, all 3 should be seen (probability of missing one is ~(2/3)^200 ≈ 0)
         assert_eq!(seen.len(), 3, "expected all generators to be used");
@@ -69,10 +73,11 @@ mod subtask_tests {
         let counter = Arc::new(AtomicUsize::new(0));
         let counter_clone = Arc::clone(&counter);
         let st = Subtask::new(0, "t").with_test(1, move || counter_clone.fetch_add(1, Ordering::SeqCst).to_string());
+        let mut rng = GeneratorRng::seed_from_u64(0);
 
-        let _ = st.generate_random_test();
-        let _ = st.generate_random_test();
-        let _ = st.generate_random_test();
+        let _ = st.generate_random_test(&mut rng);
+        let _ = st.generate_random_test(&mut rng);
+        let _ = st.generate_random_test(&mut rng);
         assert_eq!(counter.load(Ordering::SeqCst), 3);
     }
 }
@@ -0,0 +1,172 @@
+/// Tests for the test cache (`Task::with_test_cache`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test_cache_tests {
+    use crate::{GeneratorRng, Solution, Subtask, Task, TaskReport, Verdict};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Overflows for large numbers.
+    const INT_SOLUTION: &str = "
+    #include <iostream>
+    int main() { int a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    fn read_tests(task_path: &Path) -> BTreeMap<String, String> {
+        let mut tests = BTreeMap::new();
+        for entry in fs::read_dir(task_path.join("tests")).unwrap() {
+            let path = entry.unwrap().path();
+            tests.insert(path.file_name().unwrap().to_string_lossy().into_owned(), fs::read_to_string(&path).unwrap());
+        }
+        tests
+    }
+
+    /// The part of the sum above 2^31 makes the partial solution overflow.
+    fn generator(rng: &mut GeneratorRng) -> String {
+        format!("{} {}\n", rng.random_range(2_000_000_000..3_000_000_000_i64), rng.random_range(0..1000))
+    }
+
+    /// Returns for every subtask if its tests were taken from the cache.
+    fn cached_subtasks(report: &TaskReport) -> Vec<bool> {
+        report.subtasks.iter().map(|subtask| subtask.robust.is_none()).collect()
+    }
+
+    #[test]
+    fn test_unchanged_subtasks_are_reused() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        let run = |count: i32| {
+            Task::new("cache", &task_path)
+                .with_debug_level(LevelFilter::Trace)
+                .with_seed(5)
+                .with_test_cache(true)
+                .with_solution_source(SOLUTION)
+                .with_subtask(Subtask::new(50, "").with_seeded_test(2, generator))
+                .with_subtask(Subtask::new(50, "").with_seeded_test(count, generator))
+                .with_partial_solution("int", INT_SOLUTION, &[])
+                .with_min_failures(2)
+                .run()
+                .unwrap()
+        };
+
+        assert_eq!(cached_subtasks(&run(2)), [false, false]);
+        let tests = read_tests(&task_path);
+
+        // nothing changed, so no tests are generated
+        assert_eq!(cached_subtasks(&run(2)), [true, true]);
+        assert_eq!(read_tests(&task_path), tests);
+
+        // only the tests of the changed subtask are generated again
+        assert_eq!(cached_subtasks(&run(3)), [true, false]);
+        let new_tests = read_tests(&task_path);
+        assert_eq!(new_tests.keys().filter(|name| name.starts_with("test.02.")).count(), 2 * (3 + 2));
+        assert!(tests.iter().filter(|(name, _)| name.starts_with("test.01.")).all(|(name, test)| new_tests[name] == *test));
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_unseeded_generator_and_rust_checker_are_not_cached() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        let task = || {
+            Task::new("not cached", &task_path)
+                .with_debug_level(LevelFilter::Trace)
+                .with_seed(5)
+                .with_test_cache(true)
+                .with_solution_source(SOLUTION)
+                .with_subtask(Subtask::new(50, "").with_seeded_test(2, generator))
+                .with_subtask(Subtask::new(50, "").with_seeded_test(2, generator).with_test(1, || "1 2\n".to_owned()))
+        };
+
+        task().run().unwrap();
+        assert_eq!(cached_subtasks(&task().run().unwrap()), [true, false]);
+        let report = task().with_checker(|_, output, answer| output.trim() == answer.trim()).run().unwrap();
+        assert_eq!(cached_subtasks(&report), [false, false]);
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_changed_seeded_generator_is_detected() {
+        static CHANGED: AtomicBool = AtomicBool::new(false);
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        let run = || {
+            Task::new("changed generator", &task_path)
+                .with_debug_level(LevelFilter::Trace)
+                .with_seed(5)
+                .with_test_cache(true)
+                .with_solution_source(SOLUTION)
+                .with_subtask(Subtask::new(100, "").with_seeded_test(3, |rng| {
+                    let bound = if CHANGED.load(Ordering::Relaxed) { 10 } else { 1_000_000 };
+                    format!("{} {}\n", rng.random_range(bound..2 * bound), rng.random_range(bound..2 * bound))
+                }))
+                .run()
+                .unwrap();
+        };
+
+        run();
+        CHANGED.store(true, Ordering::Relaxed);
+        run();
+        for test in read_tests(&task_path).values().step_by(2) {
+            assert!(test.split_whitespace().all(|number| number.len() == 2), "{test}");
+        }
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_changed_expectations_are_detected() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        let run = |solution: Solution| {
+            Task::new("expectations", &task_path)
+                .with_debug_level(LevelFilter::Trace)
+                .with_seed(5)
+                .with_test_cache(true)
+                .with_solution_source(SOLUTION)
+                .with_subtask(Subtask::new(100, "").with_seeded_test(2, generator))
+                .with_partial(solution)
+                .with_min_failures(2)
+                .run()
+                .unwrap()
+        };
+        let int_solution = || Solution::new("int".to_owned(), INT_SOLUTION.to_owned(), &[]);
+
+        assert_eq!(cached_subtasks(&run(int_solution())), [false]);
+        assert_eq!(cached_subtasks(&run(int_solution())), [true]);
+        assert_eq!(cached_subtasks(&run(int_solution().with_expected_verdicts(0, &[Verdict::WrongAnswer]))), [false]);
+        let report = run(int_solution().with_expected_verdicts(0, &[Verdict::WrongAnswer]).with_expected_score(0, 0.0..=0.0));
+        assert_eq!(cached_subtasks(&report), [false]);
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_cache_is_disabled_by_default() {
+        let tempdir = TempDir::new().unwrap();
+
+        Task::new("no cache", tempdir.path())
+            .with_debug_level(LevelFilter::Trace)
+            .with_seed(5)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "").with_test(2, || "1 2\n".to_owned()).with_test(1, || "3 4\n".to_owned()))
+            .run()
+            .unwrap();
+        assert!(tempdir.path().join("build").is_dir());
+        assert!(!tempdir.path().join("build").join("tests_cache").exists());
+
+        drop(tempdir);
+    }
+}
@@ -1,18 +1,19 @@
 #[cfg(test)]
 mod test_generator_tests {
+    use crate::params::Params;
     use crate::test::TestGenerator;
 
     #[test]
     fn test_generator_new_and_generate() {
         let generator = TestGenerator::new(|| "hello world".to_owned());
-        assert_eq!(generator.generate(), "hello world");
+        assert_eq!(generator.generate_input(0, &Params::default(), |_| {}), "hello world");
     }
 
     #[test]
     fn test_generator_multiple_calls() {
         let generator = TestGenerator::new(|| "42\n".to_owned());
         for _ in 0..20 {
-            assert_eq!(generator.generate(), "42\n");
+            assert_eq!(generator.generate_input(0, &Params::default(), |_| {}), "42\n");
         }
     }
 
@@ -27,15 +28,15 @@ mod test_generator_tests {
             format!("{n}")
         });
 
-        assert_eq!(generator.generate(), "0");
-        assert_eq!(generator.generate(), "1");
-        assert_eq!(generator.generate(), "2");
+        assert_eq!(generator.generate_input(0, &Params::default(), |_| {}), "0");
+        assert_eq!(generator.generate_input(0, &Params::default(), |_| {}), "1");
+        assert_eq!(generator.generate_input(0, &Params::default(), |_| {}), "2");
         assert_eq!(counter.load(Ordering::SeqCst), 3);
     }
 
     #[test]
     fn test_generator_multiline_output() {
         let generator = TestGenerator::new(|| "3\n1 2 3\n".to_owned());
-        assert_eq!(generator.generate(), "3\n1 2 3\n");
+        assert_eq!(generator.generate_input(0, &Params::default(), |_| {}), "3\n1 2 3\n");
     }
 }
@@ -0,0 +1,91 @@
+/// Tests for the time limit derived from the main solution (`Task::with_auto_time_limit`).
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod time_limit_tests {
+    use crate::task::auto_time_limit;
+    use crate::{ExportFormat, Subtask, Task, Verdict};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl; }
+    ";
+
+    /// Correct, but runs for three seconds.
+    const SLOW_SOLUTION: &str = "
+    #include <chrono>
+    #include <iostream>
+    int main() {
+        auto start = std::chrono::steady_clock::now();
+        while (std::chrono::steady_clock::now() - start < std::chrono::milliseconds(3000)) {}
+        long long a, b; std::cin >> a >> b; std::cout << a + b << std::endl;
+    }
+    ";
+
+    fn generator() -> String {
+        let mut rng = rand::rng();
+        format!("{} {}\n", rng.random_range(0..1000), rng.random_range(0..1000))
+    }
+
+    #[test]
+    fn test_auto_time_limit_rounding() {
+        assert_eq!(auto_time_limit(123, 2.0, 100), 300);
+        assert_eq!(auto_time_limit(150, 2.0, 100), 300);
+        assert_eq!(auto_time_limit(151, 2.0, 100), 400);
+        assert_eq!(auto_time_limit(0, 3.0, 100), 100);
+        assert_eq!(auto_time_limit(10, 2.5, 1), 25);
+    }
+
+    #[test]
+    fn test_auto_time_limit() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        let report = Task::new("auto", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_export_format(ExportFormat::Cms)
+            .with_time_limit(5000)
+            .with_auto_time_limit(2.0, 1000)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "all").with_test(3, generator))
+            .with_partial_solution("slow", SLOW_SOLUTION, &[])
+            .run()
+            .unwrap();
+
+        // the main solution runs far below 500ms
+        assert_eq!(report.time_limit, 1000);
+        assert!(report.solutions[0].subtasks[0].passed());
+        // the robust tests are searched for with the calibrated limit, the slow solution would pass them with the limit of 5000ms
+        let robust = report.subtasks[0].robust.as_ref().unwrap();
+        assert_eq!((robust.found, robust.target), (5, 5));
+        assert_eq!(report.solutions[1].subtasks[0].verdicts.get(&Verdict::TimedOut), Some(&(3 + 5)));
+        assert!(report.solutions[1].points.abs() < 1e-9);
+
+        let task_yaml = fs::read_to_string(task_path.join("cms").join("task.yaml")).unwrap();
+        assert!(task_yaml.contains("time_limit: 1"), "{task_yaml}");
+        assert!(fs::read_to_string(task_path.join("results.txt")).unwrap().contains("Time limit: 1000ms"));
+    }
+
+    #[test]
+    fn test_judge_solution_with_auto_time_limit() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+        let task = || {
+            Task::new("auto", &task_path)
+                .with_debug_level(LevelFilter::Trace)
+                .with_time_limit(5000)
+                .with_auto_time_limit(2.0, 1000)
+                .with_solution_source(SOLUTION)
+                .with_subtask(Subtask::new(100, "all").with_test(2, generator))
+        };
+        task().run().unwrap();
+
+        let solution_file = tempdir.path().join("slow.cpp");
+        fs::write(&solution_file, SLOW_SOLUTION).unwrap();
+        let report = task().judge_solution_on_tests(&solution_file).unwrap();
+        assert_eq!(report.subtasks[0].verdicts.get(&Verdict::TimedOut), Some(&2));
+    }
+}
@@ -0,0 +1,172 @@
+/// Tests for input validators (`Subtask::with_validator`, `with_validator_source`, `with_validator_file`).
+///
+/// Validators check the test input exactly as it is written to the test file.
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod validator_tests {
+    use crate::runner::cpp_runner::CppRunner;
+    use crate::runner::validator::run_validator;
+    use crate::tests::generic_tests::generic_tests::Test;
+    use crate::tests::test_shared::initialize_logger;
+    use crate::{Error, Subtask, Task};
+    use log::LevelFilter;
+    use rand::RngExt;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    const SOLUTION: &str = "
+    #include <iostream>
+    int main() { int n; std::cin >> n; std::cout << 2 * n << std::endl; }
+    ";
+
+    /// Accepts a single integer `1 <= n <= 100` on one line.
+    const RANGE_VALIDATOR: &str = r#"
+    #include <iostream>
+    #include <string>
+    using namespace std;
+    int main() {
+        string input((istreambuf_iterator<char>(cin)), istreambuf_iterator<char>());
+        size_t pos = 0;
+        int n = stoi(input, &pos);
+        if (n < 1 || n > 100) {
+            cerr << "n = " << n << " is out of range";
+            return 1;
+        }
+        if (input.substr(pos) != "\n") {
+            cerr << "expected end of line after n";
+            return 1;
+        }
+        return 0;
+    }
+    "#;
+
+    fn small_generator() -> String {
+        format!("{}\n", rand::rng().random_range(1..=100))
+    }
+
+    fn large_generator() -> String {
+        format!("{}\n", rand::rng().random_range(101..=200))
+    }
+
+    fn range_validator(input: &str) -> Result<(), String> {
+        let n = input.trim().parse::<i32>().map_err(|err| err.to_string())?;
+        if (1..=100).contains(&n) { Ok(()) } else { Err(format!("n = {n} is out of range")) }
+    }
+
+    #[test]
+    fn test_validators_accept_valid_tests() {
+        let mut task = Test::new();
+
+        task.task = task.task.with_solution_source(SOLUTION).with_subtask(
+            Subtask::new(100, "")
+                .with_test(10, small_generator)
+                .with_validator(range_validator)
+                .with_validator(|input| if input.ends_with('\n') { Ok(()) } else { Err("missing newline".to_owned()) })
+                .with_validator_source(RANGE_VALIDATOR),
+        );
+
+        task.test();
+    }
+
+    #[test]
+    fn test_closure_validator_rejects_test() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        let result = Task::new("validator", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(50, "").with_test(2, small_generator))
+            .with_subtask(Subtask::new(50, "").with_test(2, small_generator).with_test(2, large_generator).with_validator(range_validator))
+            .run();
+
+        assert!(
+            matches!(&result, Err(Error::InvalidTest { subtask_number: 2, gen_id: 2, message, .. }) if message.ends_with("is out of range")),
+            "got {result:?}"
+        );
+        let invalid_test = std::fs::read_to_string(task_path.join("invalid_test.in")).unwrap();
+        assert!(range_validator(&invalid_test).is_err());
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_validator_program_rejects_test() {
+        let tempdir = TempDir::new().unwrap();
+        let task_path = tempdir.path().join("task");
+
+        let result = Task::new("validator", &task_path)
+            .with_debug_level(LevelFilter::Trace)
+            .with_solution_source(SOLUTION)
+            .trim_whitespace(false)
+            .with_subtask(Subtask::new(100, "").with_test(2, || "5 \n".to_owned()).with_validator_source(RANGE_VALIDATOR))
+            .run();
+
+        assert!(
+            matches!(&result, Err(Error::InvalidTest { gen_id: 1, message, .. }) if message == "expected end of line after n"),
+            "got {result:?}"
+        );
+        assert_eq!(std::fs::read_to_string(task_path.join("invalid_test.in")).unwrap(), "5 \n");
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_validator_sees_trimmed_input() {
+        let mut task = Test::new();
+
+        // the trailing space is removed before the test is written, so the validator accepts it
+        task.task = task
+            .task
+            .with_solution_source(SOLUTION)
+            .trim_whitespace(true)
+            .with_subtask(Subtask::new(100, "").with_test(2, || "5 \n".to_owned()).with_validator_source(RANGE_VALIDATOR));
+
+        task.test();
+    }
+
+    #[test]
+    fn test_validator_file() {
+        let tempdir = TempDir::new().unwrap();
+        let validator_file = tempdir.path().join("validator.cpp");
+        std::fs::write(&validator_file, RANGE_VALIDATOR).unwrap();
+
+        let result = Task::new("validator", &tempdir.path().join("task"))
+            .with_debug_level(LevelFilter::Trace)
+            .with_solution_source(SOLUTION)
+            .with_subtask(Subtask::new(100, "").with_test(2, large_generator).with_validator_file(&validator_file))
+            .run();
+        assert!(matches!(result, Err(Error::InvalidTest { gen_id: 1, .. })), "got {result:?}");
+
+        drop(tempdir);
+    }
+
+    #[test]
+    fn test_runner_validator() {
+        initialize_logger();
+
+        let tempdir = TempDir::new().unwrap();
+        let mut runner = CppRunner::new(tempdir.path()).unwrap();
+        let validator = runner.add_program(RANGE_VALIDATOR).unwrap();
+        assert_eq!(runner.run_validator(validator, "42\n").unwrap(), Ok(()));
+        assert_eq!(runner.run_validator(validator, "0\n").unwrap(), Err("n = 0 is out of range".to_owned()));
+
+        let silent_validator = runner.add_program("int main() { return 3; }").unwrap();
+        assert_eq!(runner.run_validator(silent_validator, "").unwrap(), Err("validator exited with code Some(3)".to_owned()));
+
+        // a long message before the input is read does not block the validator
+        let noisy_validator = runner
+            .add_program("#include <iostream>\n#include <string>\nint main() { std::cerr << std::string(1 << 20, 'x'); std::string s; while (std::cin >> s) {} return 1; }")
+            .unwrap();
+        assert_eq!(runner.run_validator(noisy_validator, &"1 ".repeat(1 << 20)).unwrap().unwrap_err().len(), 1 << 20);
+
+        // a validator that does not finish is killed after the timeout
+        let stuck_validator = runner.add_program("int main() { while (true) {} }").unwrap();
+        assert!(matches!(
+            run_validator(runner.get_executable_path(stuck_validator), "", Duration::from_millis(200)),
+            Err(Error::ValidatorFailed { .. })
+        ));
+
+        drop(tempdir);
+    }
+}
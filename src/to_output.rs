@@ -1,5 +1,16 @@
+use crate::GeneratorRng;
+
 pub trait ToOutput {
     fn to_output(self) -> String;
+
+    /// Same as `to_output`, but types with a random output (like the order of the edges of a `Graph`)
+    /// take the randomness from `rng`. Generated tests are converted with it, so seeded tests are reproducible.
+    fn to_output_with_rng(self, _rng: &mut GeneratorRng) -> String
+    where
+        Self: Sized,
+    {
+        self.to_output()
+    }
 }
 
 pub use ezcp_macros::ToOutput;
@@ -44,24 +55,44 @@ impl_to_output!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize,
 
 impl<T: ToOutput> ToOutput for Vec<T> {
     fn to_output(self) -> String {
-        if self.is_empty() {
-            return String::new();
-        }
+        join_vec_outputs(self.into_iter().map(ToOutput::to_output))
+    }
 
-        let mut res = String::new();
-        for i in self {
-            let s = i.to_output();
-            res.push_str(&s);
-            if !s.ends_with('\n') {
-                res.push(' ');
-            }
+    fn to_output_with_rng(self, rng: &mut GeneratorRng) -> String {
+        join_vec_outputs(self.into_iter().map(|i| i.to_output_with_rng(rng)))
+    }
+}
+
+/// Joins the outputs of the elements of a vector with spaces, elements that end a line are not followed by a space.
+fn join_vec_outputs(outputs: impl Iterator<Item = String>) -> String {
+    let mut res = String::new();
+    for s in outputs {
+        res.push_str(&s);
+        if !s.ends_with('\n') {
+            res.push(' ');
         }
-        if res.ends_with(' ') {
-            res.pop();
-            res.push('\n');
+    }
+    if res.ends_with(' ') {
+        res.pop();
+        res.push('\n');
+    }
+    res
+}
+
+/// Joins the outputs of the elements of a tuple into one line, elements that end with whitespace are not followed by a space.
+fn join_tuple_outputs(outputs: &[String]) -> String {
+    let mut res = String::new();
+    for s in outputs {
+        res.push_str(s);
+        if !s.ends_with(|c: char| c.is_whitespace()) {
+            res.push(' ');
         }
-        res
     }
+    if res.ends_with(' ') {
+        res.pop();
+    }
+    res.push('\n');
+    res
 }
 
 macro_rules! impl_tuple_to_output {
@@ -70,19 +101,13 @@ macro_rules! impl_tuple_to_output {
             #[allow(non_snake_case)]
             fn to_output(self) -> String {
                 let ($($name,)+) = self;
-                let mut res = String::new();
-                $(
-                    let s = $name.to_output();
-                    res.push_str(&s);
-                    if !s.ends_with(|c: char| c.is_whitespace()) {
-                        res.push(' ');
-                    }
-                )+
-                if res.ends_with(' ') {
-                    res.pop();
-                }
-                res.push('\n');
-                res
+                join_tuple_outputs(&[$($name.to_output()),+])
+            }
+
+            #[allow(non_snake_case)]
+            fn to_output_with_rng(self, rng: &mut GeneratorRng) -> String {
+                let ($($name,)+) = self;
+                join_tuple_outputs(&[$($name.to_output_with_rng(rng)),+])
             }
         }
     };